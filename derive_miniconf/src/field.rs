@@ -0,0 +1,428 @@
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token, Lit, Meta, MetaList, NestedMeta, Token,
+};
+
+/// Represents a named field of a struct along with any `#[miniconf(...)]` attributes.
+pub struct StructField {
+    pub field: syn::Field,
+
+    /// A module providing custom `set()` and `get()` functions for the field, if specified using
+    /// `#[miniconf(with = "module")]`.
+    pub with: Option<syn::Path>,
+
+    /// The unit of the field, if specified using `#[miniconf(unit = "...")]`.
+    pub unit: Option<String>,
+
+    /// A description of the field, if specified using `#[miniconf(description = "...")]`.
+    pub description: Option<String>,
+
+    /// The inclusive lower bound of the field, if specified using `#[miniconf(min = ...)]`.
+    pub min: Option<Bound>,
+
+    /// The inclusive upper bound of the field, if specified using `#[miniconf(max = ...)]`.
+    pub max: Option<Bound>,
+
+    /// Specifies whether NaN and infinite values are rejected, using `#[miniconf(finite)]`.
+    pub finite: bool,
+
+    /// The values allowed for the field, if specified using `#[miniconf(one_of = [...])]`.
+    pub one_of: Option<Vec<String>>,
+
+    /// Specifies whether the field is (de)serialized as a whole, using `#[miniconf(atomic)]`.
+    pub atomic: bool,
+}
+
+/// A bound of a field, as specified using `#[miniconf(min = ...)]` or `#[miniconf(max = ...)]`.
+pub struct Bound {
+    /// The bound as written, which is reported in `Error::OutOfRange`.
+    pub text: String,
+
+    /// The bound as an expression of the field type.
+    pub expr: syn::Expr,
+}
+
+impl StructField {
+    pub fn new(field: syn::Field) -> syn::Result<Self> {
+        let mut with = None;
+        let mut unit = None;
+        let mut description = None;
+        let mut min = None;
+        let mut max = None;
+        let mut finite = false;
+        let mut one_of = None;
+        let mut atomic = false;
+
+        for meta in miniconf_attributes(&field.attrs)? {
+            let value = match meta {
+                NestedMeta::Meta(Meta::NameValue(value)) => value,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("finite") => {
+                    finite = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("atomic") => {
+                    atomic = true;
+                    continue;
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("one_of") => {
+                    let values = list
+                        .nested
+                        .into_iter()
+                        .map(|value| match value {
+                            NestedMeta::Lit(Lit::Str(lit)) => Ok(lit.value()),
+                            value => Err(syn::Error::new_spanned(
+                                value,
+                                "Miniconf `one_of` values must be specified as strings",
+                            )),
+                        })
+                        .collect::<syn::Result<_>>()?;
+                    one_of.replace(values);
+                    continue;
+                }
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "Unsupported miniconf attribute",
+                    ))
+                }
+            };
+
+            let name = value.path.get_ident().map(|ident| ident.to_string());
+
+            // Bounds may be specified as numeric literals or as strings (e.g. for negative values).
+            if let Some(bound @ ("min" | "max")) = name.as_deref() {
+                let (text, expr) = match &value.lit {
+                    Lit::Int(lit) => (lit.to_string(), parse_quote!(#lit)),
+                    Lit::Float(lit) => (lit.to_string(), parse_quote!(#lit)),
+                    Lit::Str(lit) => (lit.value(), lit.parse()?),
+                    lit => {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "Miniconf bounds must be specified as numbers",
+                        ))
+                    }
+                };
+                let bound_value = Bound { text, expr };
+
+                if bound == "min" {
+                    min.replace(bound_value);
+                } else {
+                    max.replace(bound_value);
+                }
+
+                continue;
+            }
+
+            let lit = match value.lit {
+                Lit::Str(lit) => lit,
+                lit => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "Miniconf attributes must be specified as strings",
+                    ))
+                }
+            };
+
+            match name.as_deref() {
+                Some("with") => {
+                    with.replace(lit.parse().map_err(|_| {
+                        syn::Error::new_spanned(&lit, "Invalid module path for `with`")
+                    })?);
+                }
+                Some("unit") => {
+                    unit.replace(lit.value());
+                }
+                Some("description") => {
+                    description.replace(lit.value());
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        value.path,
+                        "Unsupported miniconf attribute",
+                    ))
+                }
+            }
+        }
+
+        if atomic && with.is_some() {
+            return Err(syn::Error::new_spanned(
+                &field,
+                "Miniconf `atomic` and `with` cannot be combined",
+            ));
+        }
+
+        Ok(Self {
+            field,
+            with,
+            unit,
+            description,
+            min,
+            max,
+            finite,
+            one_of,
+            atomic,
+        })
+    }
+
+    /// Get the identifier of the field.
+    pub fn ident(&self) -> &syn::Ident {
+        // Note(unwrap): Only named fields are supported.
+        self.field.ident.as_ref().unwrap()
+    }
+
+    /// Get the `#[cfg(...)]` attributes of the field.
+    ///
+    /// # Note
+    /// These are attached to all code generated for the field so that fields that are not
+    /// present in the current configuration are omitted.
+    pub fn cfg_attrs(&self) -> Vec<&syn::Attribute> {
+        self.field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .collect()
+    }
+
+    /// Get an expression evaluating to 1 if the field is present in the current configuration
+    /// and 0 otherwise.
+    pub fn present_expr(&self) -> syn::Expr {
+        let predicates = self.cfg_attrs().into_iter().map(|attr| &attr.tokens);
+        parse_quote!(usize::from(true #(&& cfg!#predicates)*))
+    }
+
+    /// Determine if the field is a leaf node that does not recurse into its type.
+    pub fn is_leaf(&self) -> bool {
+        self.with.is_some() || self.atomic
+    }
+
+    /// Get the path of a `Miniconf` method of the type of the field.
+    ///
+    /// # Note
+    /// The path is spanned to the field, so that errors for types not implementing `Miniconf`
+    /// point at the offending field instead of the derive.
+    pub fn method(&self, method: &str) -> proc_macro2::TokenStream {
+        let ty = &self.field.ty;
+        let method = syn::Ident::new(method, proc_macro2::Span::call_site());
+        quote_spanned!(self.field.span()=> <#ty as miniconf::Miniconf>::#method)
+    }
+
+    /// Get the code used to construct the attributes of the field.
+    pub fn attributes_expr(&self) -> syn::Expr {
+        let unit = option_expr(&self.unit);
+        let description = option_expr(&self.description);
+        parse_quote! {
+            miniconf::Attributes {
+                unit: #unit,
+                description: #description,
+            }
+        }
+    }
+
+    /// Get the code used to set the value of the field.
+    pub fn set_expr(&self) -> syn::Expr {
+        let ident = self.ident();
+        if self.min.is_none() && self.max.is_none() && !self.finite && self.one_of.is_none() {
+            return self.set_into(parse_quote!(self.#ident));
+        }
+
+        // Bounded, finite and restricted fields are deserialized into a candidate value that is only stored
+        // if it is valid.
+        let ty = &self.field.ty;
+        let set = self.set_into(parse_quote!(candidate));
+        let below = bound_check(&self.min, ty, quote!(<));
+        let above = bound_check(&self.max, ty, quote!(>));
+        let min = option_expr(&self.min.as_ref().map(|bound| bound.text.clone()));
+        let max = option_expr(&self.max.as_ref().map(|bound| bound.text.clone()));
+
+        let finite_check = if self.finite {
+            quote! {
+                if !candidate.is_finite() {
+                    return Err(miniconf::Error::NotFinite);
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let allowed_check = match &self.one_of {
+            Some(values) => quote! {
+                const ALLOWED: &[&str] = &[#(#values),*];
+                if !ALLOWED.contains(&core::convert::AsRef::<str>::as_ref(&candidate)) {
+                    return Err(miniconf::Error::NotAllowed { allowed: ALLOWED });
+                }
+            },
+            None => quote!(),
+        };
+
+        parse_quote! {{
+            let mut candidate = self.#ident.clone();
+            #set?;
+
+            #finite_check
+            #allowed_check
+
+            if #below || #above {
+                return Err(miniconf::Error::OutOfRange {
+                    min: #min,
+                    max: #max,
+                });
+            }
+
+            self.#ident = candidate;
+            Ok(())
+        }}
+    }
+
+    /// Get the code used to set the value of the field into the provided place.
+    fn set_into(&self, place: syn::Expr) -> syn::Expr {
+        match &self.with {
+            Some(with) => parse_quote! {{
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::PathTooLong);
+                }
+
+                #with::set(&mut #place, value)
+            }},
+            None if self.atomic => parse_quote! {{
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::AtomicUpdateRequired);
+                }
+
+                #place = miniconf::serde_json_core::from_slice(value)?.0;
+                Ok::<(), miniconf::Error>(())
+            }},
+            None => {
+                let string_set = self.method("string_set");
+                parse_quote!(#string_set(&mut #place, topic_parts, value))
+            }
+        }
+    }
+
+    /// Get the code used to serialize the value of the field.
+    pub fn get_expr(&self) -> syn::Expr {
+        let ident = self.ident();
+        match &self.with {
+            Some(with) => parse_quote! {{
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::PathTooLong);
+                }
+
+                #with::get(&self.#ident, value)
+            }},
+            None if self.atomic => parse_quote! {{
+                if topic_parts.peek().is_some() {
+                    return miniconf::get_member(&self.#ident, topic_parts, value);
+                }
+
+                miniconf::serde_json_core::to_slice(&self.#ident, value)
+                    .map_err(|_| miniconf::Error::SerializationFailed)
+            }},
+            None => {
+                let string_get = self.method("string_get");
+                parse_quote!(#string_get(&self.#ident, topic_parts, value))
+            }
+        }
+    }
+
+    /// Get the code used to determine the size of the serialized value of the field.
+    pub fn value_len_expr(&self) -> syn::Expr {
+        let ident = self.ident();
+        match &self.with {
+            // Modules only serialize into a buffer, so their values cannot be measured.
+            Some(_) => parse_quote! {{
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::PathTooLong);
+                }
+
+                Err(miniconf::Error::SerializationFailed)
+            }},
+            None if self.atomic => parse_quote! {{
+                if topic_parts.peek().is_some() {
+                    return miniconf::member_len(&self.#ident, topic_parts);
+                }
+
+                miniconf::json_len(&self.#ident)
+            }},
+            None => {
+                let string_value_len = self.method("string_value_len");
+                parse_quote!(#string_value_len(&self.#ident, topic_parts))
+            }
+        }
+    }
+}
+
+fn option_expr(value: &Option<String>) -> syn::Expr {
+    match value {
+        Some(value) => parse_quote!(Some(#value)),
+        None => parse_quote!(None),
+    }
+}
+
+/// Generate a comparison of the `candidate` value against an optional bound.
+///
+/// # Note
+/// The bound is declared as a constant of the field type so that bounds which cannot be
+/// represented by the field type are rejected at compile time.
+fn bound_check(
+    bound: &Option<Bound>,
+    ty: &syn::Type,
+    comparison: proc_macro2::TokenStream,
+) -> syn::Expr {
+    match bound {
+        Some(Bound { expr, .. }) => parse_quote!({
+            const BOUND: #ty = #expr;
+            candidate #comparison BOUND
+        }),
+        None => parse_quote!(false),
+    }
+}
+
+/// Collect the contents of all `#[miniconf(...)]` attributes.
+pub fn miniconf_attributes(attrs: &[syn::Attribute]) -> syn::Result<Vec<NestedMeta>> {
+    let mut metas = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("miniconf")) {
+        let list = attr
+            .parse_args_with(parse_attribute_list)
+            .map_err(|_| syn::Error::new_spanned(attr, "Expected `#[miniconf(...)]`"))?;
+        metas.extend(list);
+    }
+
+    Ok(metas)
+}
+
+/// Parse the comma-separated contents of a `#[miniconf(...)]` attribute.
+///
+/// # Note
+/// In addition to the standard meta syntax, lists of literals may be specified as
+/// `name = [...]`. These are represented as `name(...)`.
+fn parse_attribute_list(input: ParseStream) -> syn::Result<Punctuated<NestedMeta, Token![,]>> {
+    let mut list = Punctuated::new();
+
+    while !input.is_empty() {
+        if input.peek(syn::Ident) && input.peek2(Token![=]) && input.peek3(token::Bracket) {
+            let path: syn::Path = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            let content;
+            let bracket = syn::bracketed!(content in input);
+            list.push_value(NestedMeta::Meta(Meta::List(MetaList {
+                path,
+                paren_token: token::Paren(bracket.span),
+                nested: content.parse_terminated(NestedMeta::parse)?,
+            })));
+        } else {
+            list.push_value(input.parse()?);
+        }
+
+        if input.is_empty() {
+            break;
+        }
+        list.push_punct(input.parse()?);
+    }
+
+    Ok(list)
+}
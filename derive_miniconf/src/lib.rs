@@ -1,6 +1,10 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, DeriveInput};
+use syn::{ext::IdentExt, parse_macro_input, parse_quote, DeriveInput};
+
+mod field;
+
+use field::{miniconf_attributes, StructField};
 
 /// Represents a type definition with associated generics.
 struct TypeDefinition {
@@ -71,15 +75,39 @@ impl TypeDefinition {
 ///     // Accessed with path `external`
 ///     external: bool,
 /// }
-#[proc_macro_derive(Miniconf)]
+/// ```
+///
+/// # Attributes
+/// * `#[miniconf(with = "module")]` - Use `module::set(&mut field, value)` and
+///   `module::get(&field, buffer)` to (de)serialize the field as a single leaf instead of
+///   recursing into its type. The functions have the same return types as
+///   `Miniconf::string_set()` and `Miniconf::string_get()`.
+/// * `#[miniconf(unit = "...")]` - Specify the unit of the field, exposed through
+///   `Miniconf::get_attributes()`.
+/// * `#[miniconf(description = "...")]` - Specify a description of the field, exposed through
+///   `Miniconf::get_attributes()`.
+/// * `#[miniconf(min = ..., max = ...)]` - Specify inclusive bounds of a numeric field. Values
+///   outside of the bounds are rejected with `Error::OutOfRange` and the field is left unchanged.
+///   Bounds must be representable by the type of the field, e.g. `max = 10.0` for floats, and may
+///   also be provided as strings, e.g. `min = "-1"`.
+/// * `#[miniconf(finite)]` - Reject NaN and infinite values of a floating point field with
+///   `Error::NotFinite`. The field is left unchanged.
+/// * `#[miniconf(one_of = ["a", "b"])]` - Restrict a string field to the listed values. Other
+///   values are rejected with `Error::NotAllowed` and the field is left unchanged.
+/// * `#[miniconf(atomic)]` - (De)serialize the field as a whole using serde, like types deriving
+///   `MiniconfAtomic`, instead of recursing into its type. Members of the field can only be read,
+///   and its type only needs to implement `Serialize` and `DeserializeOwned`.
+#[proc_macro_derive(Miniconf, attributes(miniconf))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     let typedef = TypeDefinition::new(input.generics, input.ident);
 
     match input.data {
-        syn::Data::Struct(struct_data) => derive_struct(typedef, struct_data, false),
-        syn::Data::Enum(enum_data) => derive_enum(typedef, enum_data),
+        syn::Data::Struct(struct_data) => {
+            derive_struct(typedef, struct_data, &input.attrs, false, false)
+        }
+        syn::Data::Enum(enum_data) => derive_enum(typedef, enum_data, &input.attrs),
         syn::Data::Union(_) => unimplemented!(),
     }
 }
@@ -87,7 +115,14 @@ pub fn derive(input: TokenStream) -> TokenStream {
 /// Derive the Miniconf trait for a custom type that must be updated atomically.
 ///
 /// This derive function should be used if the setting must be updated entirely at once (e.g.
-/// individual portions of the struct may not be updated independently).
+/// individual portions of the struct may not be updated independently). Individual members can
+/// still be read by their path, e.g. for debugging.
+///
+/// Some fields of the struct can be updated at once by setting a partial JSON object at the
+/// `~patch` path below the struct, e.g. `filter/~patch`. The provided fields are merged into the
+/// current value, while the other fields are left unchanged. Fields are keyed by their serde
+/// names. Nothing is updated if any of the values cannot be deserialized or if the object contains
+/// other keys.
 ///
 /// See [Miniconf](derive.Miniconf.html) for more information.
 ///
@@ -101,22 +136,55 @@ pub fn derive(input: TokenStream) -> TokenStream {
 ///
 /// #[derive(Miniconf)]
 /// struct Settings {
-///     // Accessed with path `filter`, but `filter/length` and `filter/coefficients` are
-///     inaccessible.
+///     // Accessed with path `filter`, while `filter/length` and `filter/coefficient` can only be
+///     // read.
 ///     filter: FilterParameters,
 ///
 ///     // Accessed with path `external`
 ///     external: bool,
 /// }
-#[proc_macro_derive(MiniconfAtomic)]
+/// ```
+///
+/// # Attributes
+/// * `#[miniconf(deny_unknown_fields)]` - Reject values of the struct containing keys other than
+///   the serde names of its fields with `Error::UnknownField` instead of ignoring them. The names
+///   respect `#[serde(rename)]` and `#[serde(rename_all)]` attributes.
+#[proc_macro_derive(MiniconfAtomic, attributes(miniconf))]
 pub fn derive_atomic(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    let attributes = match miniconf_attributes(&input.attrs) {
+        Ok(attributes) => attributes,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut deny_unknown_fields = false;
+    for meta in attributes {
+        match meta {
+            syn::NestedMeta::Meta(syn::Meta::Path(path))
+                if path.is_ident("deny_unknown_fields") =>
+            {
+                deny_unknown_fields = true;
+            }
+            meta => {
+                return syn::Error::new_spanned(meta, "Unsupported miniconf attribute")
+                    .to_compile_error()
+                    .into()
+            }
+        }
+    }
+
     let typedef = TypeDefinition::new(input.generics, input.ident);
 
     match input.data {
-        syn::Data::Struct(struct_data) => derive_struct(typedef, struct_data, true),
-        syn::Data::Enum(enum_data) => derive_enum(typedef, enum_data),
+        syn::Data::Struct(struct_data) => derive_struct(
+            typedef,
+            struct_data,
+            &input.attrs,
+            true,
+            deny_unknown_fields,
+        ),
+        syn::Data::Enum(enum_data) => derive_enum(typedef, enum_data, &input.attrs),
         syn::Data::Union(_) => unimplemented!(),
     }
 }
@@ -126,14 +194,28 @@ pub fn derive_atomic(input: TokenStream) -> TokenStream {
 /// # Args
 /// * `typedef` - The type definition.
 /// * `data` - The data associated with the struct definition.
+/// * `attrs` - The attributes of the struct.
 /// * `atomic` - specified true if the data must be updated atomically. If false, data must be
 ///   set at a terminal node.
+/// * `deny_unknown_fields` - specified true if atomic updates containing keys other than the
+///   field names must be rejected.
 ///
 /// # Returns
 /// A token stream of the generated code.
-fn derive_struct(mut typedef: TypeDefinition, data: syn::DataStruct, atomic: bool) -> TokenStream {
-    let fields = match data.fields {
-        syn::Fields::Named(syn::FieldsNamed { ref named, .. }) => named,
+fn derive_struct(
+    mut typedef: TypeDefinition,
+    data: syn::DataStruct,
+    attrs: &[syn::Attribute],
+    atomic: bool,
+    deny_unknown_fields: bool,
+) -> TokenStream {
+    let fields: Vec<StructField> = match data.fields {
+        syn::Fields::Named(syn::FieldsNamed { named, .. }) => {
+            match named.into_iter().map(StructField::new).collect() {
+                Ok(fields) => fields,
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
         _ => unimplemented!("Only named fields are supported in structs."),
     };
 
@@ -146,53 +228,118 @@ fn derive_struct(mut typedef: TypeDefinition, data: syn::DataStruct, atomic: boo
         let name = typedef.name;
         let (impl_generics, ty_generics, where_clause) = typedef.generics.split_for_impl();
 
+        // Values are (de)serialized by serde, so fields are keyed by their serde names.
+        let rename_all = match RenameRule::from_attributes(attrs) {
+            Ok(rule) => rule,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let serde_names: Vec<String> = fields
+            .iter()
+            .map(|f| {
+                serde_name(&f.field.attrs, "rename")
+                    .unwrap_or_else(|| rename_all.apply_to_field(&f.ident().unraw().to_string()))
+            })
+            .collect();
+
+        let check_fields = if deny_unknown_fields {
+            let field_names = fields.iter().zip(&serde_names).map(|(f, name)| {
+                let cfgs = f.cfg_attrs();
+                quote!(#(#cfgs)* #name)
+            });
+            quote! {
+                miniconf::deny_unknown_fields(value, &[#(#field_names),*])?;
+            }
+        } else {
+            quote!()
+        };
+
+        // Fields are patched by deserializing all provided values before assigning any of them.
+        let patched: Vec<syn::Ident> = fields
+            .iter()
+            .map(|f| quote::format_ident!("patched_{}", f.ident()))
+            .collect();
+        let patch_declarations = fields.iter().zip(&patched).map(|(f, patched)| {
+            let cfgs = f.cfg_attrs();
+            let ty = &f.field.ty;
+            quote! {
+                #(#cfgs)*
+                let mut #patched: Option<#ty> = None;
+            }
+        });
+        let patch_arms =
+            fields
+                .iter()
+                .zip(&patched)
+                .zip(&serde_names)
+                .map(|((f, patched), name)| {
+                    let cfgs = f.cfg_attrs();
+                    quote! {
+                        #(#cfgs)*
+                        #name => {
+                            #patched.replace(miniconf::serde_json_core::from_slice(member)?.0);
+                            Ok(true)
+                        }
+                    }
+                });
+        let patch_assignments = fields.iter().zip(&patched).map(|(f, patched)| {
+            let cfgs = f.cfg_attrs();
+            let name = f.ident();
+            quote! {
+                #(#cfgs)*
+                if let Some(value) = #patched {
+                    self.#name = value;
+                }
+            }
+        });
+
+        let iteration = iteration(leaf_iteration());
+
         let data = quote! {
             impl #impl_generics miniconf::Miniconf for #name #ty_generics #where_clause {
                 fn string_set(&mut self, mut topic_parts:
                 core::iter::Peekable<core::str::Split<char>>, value: &[u8]) ->
                 Result<(), miniconf::Error> {
                     if topic_parts.peek().is_some() {
-                        return Err(miniconf::Error::AtomicUpdateRequired);
+                        // A partial object can be merged into the current value using `~patch`.
+                        if topic_parts.next() != Some("~patch") || topic_parts.peek().is_some() {
+                            return Err(miniconf::Error::AtomicUpdateRequired);
+                        }
+
+                        #(#patch_declarations)*
+                        miniconf::patch_members(value, |key, member| {
+                            match key {
+                                #(#patch_arms)*
+                                _ => Ok(false),
+                            }
+                        })?;
+
+                        #(#patch_assignments)*
+                        return Ok(());
                     }
 
+                    #check_fields
                     *self = miniconf::serde_json_core::from_slice(value)?.0;
                     Ok(())
                 }
 
                 fn string_get(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>, value: &mut [u8]) -> Result<usize, miniconf::Error> {
+                    // Members can be read individually, e.g. for debugging.
                     if topic_parts.peek().is_some() {
-                        return Err(miniconf::Error::AtomicUpdateRequired);
+                        return miniconf::get_member(self, topic_parts, value);
                     }
 
                     miniconf::serde_json_core::to_slice(self, value).map_err(|_| miniconf::Error::SerializationFailed)
                 }
 
-                fn get_metadata(&self) -> miniconf::MiniconfMetadata {
-                    // Atomic structs have no children and a single index.
-                    miniconf::MiniconfMetadata {
-                        max_topic_size: 0,
-                        max_depth: 1,
-                    }
-                }
-
-                fn recurse_paths<const TS: usize>(&self, index: &mut [usize], topic: &mut miniconf::heapless::String<TS>) -> Option<()> {
-                    if index.len() == 0 {
-                        // Note: During expected execution paths using `into_iter()`, the size of
-                        // the index stack is checked in advance to make sure this condition
-                        // doesn't occur.  However, it's possible to happen if the user manually
-                        // calls `recurse_paths`.
-                        unreachable!("Index stack too small");
+                fn string_value_len(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<usize, miniconf::Error> {
+                    if topic_parts.peek().is_some() {
+                        return miniconf::member_len(self, topic_parts);
                     }
 
-                    let i = index[0];
-                    index[0] += 1;
-
-                    if i == 0 {
-                        Some(())
-                    } else {
-                        None
-                    }
+                    miniconf::json_len(self)
                 }
+
+                #iteration
             }
         };
 
@@ -200,44 +347,214 @@ fn derive_struct(mut typedef: TypeDefinition, data: syn::DataStruct, atomic: boo
     }
 
     let set_recurse_match_arms = fields.iter().map(|f| {
-        let match_name = &f.ident;
+        let cfgs = f.cfg_attrs();
+        let match_name = f.ident();
+        let set = f.set_expr();
         quote! {
+            #(#cfgs)*
             stringify!(#match_name) => {
-                self.#match_name.string_set(topic_parts, value)
+                #set
             }
         }
     });
 
     let get_recurse_match_arms = fields.iter().map(|f| {
-        let match_name = &f.ident;
+        let cfgs = f.cfg_attrs();
+        let match_name = f.ident();
+        let get = f.get_expr();
         quote! {
+            #(#cfgs)*
             stringify!(#match_name) => {
-                self.#match_name.string_get(topic_parts, value)
+                #get
+            }
+        }
+    });
+
+    let value_len_match_arms = fields.iter().map(|f| {
+        let cfgs = f.cfg_attrs();
+        let match_name = f.ident();
+        let value_len = f.value_len_expr();
+        quote! {
+            #(#cfgs)*
+            stringify!(#match_name) => {
+                #value_len
+            }
+        }
+    });
+
+    let attributes_match_arms = fields.iter().map(|f| {
+        let cfgs = f.cfg_attrs();
+        let match_name = f.ident();
+        let attributes = f.attributes_expr();
+
+        // Leaf fields have no children to recurse into.
+        let recurse = if f.is_leaf() {
+            quote!(Err(miniconf::Error::PathTooLong))
+        } else {
+            let string_attributes = f.method("string_attributes");
+            quote!(#string_attributes(&self.#match_name, topic_parts))
+        };
+
+        quote! {
+            #(#cfgs)*
+            stringify!(#match_name) => {
+                if topic_parts.peek().is_none() {
+                    return Ok(#attributes);
+                }
+
+                #recurse
             }
         }
     });
 
+    let type_name_match_arms = fields.iter().map(|f| {
+        let cfgs = f.cfg_attrs();
+        let match_name = f.ident();
+        let ty = &f.field.ty;
+
+        // Leaf fields have no children to recurse into.
+        let type_name = if f.is_leaf() {
+            quote! {{
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::PathTooLong);
+                }
+
+                Ok(core::any::type_name::<#ty>())
+            }}
+        } else {
+            let string_type_name = f.method("string_type_name");
+            quote!(#string_type_name(&self.#match_name, topic_parts))
+        };
+
+        quote! {
+            #(#cfgs)*
+            stringify!(#match_name) => #type_name
+        }
+    });
+
+    let variants_match_arms = fields.iter().map(|f| {
+        let cfgs = f.cfg_attrs();
+        let match_name = f.ident();
+
+        // Leaf fields are (de)serialized as a whole and are not treated as enums.
+        let variants = if f.is_leaf() {
+            quote! {{
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::PathTooLong);
+                }
+
+                Err(miniconf::Error::NotAnEnum)
+            }}
+        } else {
+            let string_variants = f.method("string_variants");
+            quote!(#string_variants(&self.#match_name, topic_parts))
+        };
+
+        quote! {
+            #(#cfgs)*
+            stringify!(#match_name) => #variants
+        }
+    });
+
+    let leaf_value_match_arms = fields.iter().map(|f| {
+        let cfgs = f.cfg_attrs();
+        let match_name = f.ident();
+
+        // Leaf fields are (de)serialized as a whole and are not reachable.
+        let leaf_value = if f.is_leaf() {
+            quote! {{
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::PathTooLong);
+                }
+
+                Ok(None)
+            }}
+        } else {
+            let string_leaf_value = f.method("string_leaf_value");
+            quote!(#string_leaf_value(&mut self.#match_name, topic_parts))
+        };
+
+        quote! {
+            #(#cfgs)*
+            stringify!(#match_name) => #leaf_value
+        }
+    });
+
+    let metadata_match_arms = fields.iter().map(|f| {
+        let cfgs = f.cfg_attrs();
+        let match_name = f.ident();
+
+        // Leaf fields have no children and a single index.
+        let metadata = if f.is_leaf() {
+            quote! {{
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::PathTooLong);
+                }
+
+                Ok(miniconf::MiniconfMetadata {
+                    max_topic_size: 0,
+                    max_depth: 1,
+                    path_count: 1,
+                })
+            }}
+        } else {
+            let string_metadata = f.method("string_metadata");
+            quote!(#string_metadata(&self.#match_name, topic_parts))
+        };
+
+        quote! {
+            #(#cfgs)*
+            stringify!(#match_name) => #metadata
+        }
+    });
+
     let iter_match_arms = fields.iter().enumerate().map(|(i, f)| {
-        let field_name = &f.ident;
+        let cfgs = f.cfg_attrs();
+        let field_name = f.ident();
+
+        // Fields are indexed by their position among the fields present in the current
+        // configuration.
+        let preceding = fields[..i].iter().map(|f| f.present_expr());
+        let position = quote!(0 #(+ #preceding)*);
+
+        // Leaf fields yield once for themselves and use a single index.
+        let recurse = if f.is_leaf() {
+            quote! {{
+                let i = index[1];
+                index[1] += 1;
+                i == 0
+            }}
+        } else {
+            let recurse_paths = f.method("recurse_paths");
+            quote!(#recurse_paths(&self.#field_name, &mut index[1..], topic).is_some())
+        };
+
         quote! {
-            #i => {
+            #(#cfgs)*
+            i if i == #position => {
                 let original_length = topic.len();
 
-                let postfix = if topic.len() != 0 {
+                let postfix = if !topic.is_empty() {
                     concat!("/", stringify!(#field_name))
                 } else {
                     stringify!(#field_name)
                 };
 
-                if topic.push_str(postfix).is_err() {
+                if topic.write_str(postfix).is_err() {
                     // Note: During expected execution paths using `into_iter()`, the size of the
                     // topic buffer is checked in advance to make sure this condition doesn't
                     // occur.  However, it's possible to happen if the user manually calls
-                    // `recurse_paths`.
-                    unreachable!("Topic buffer too short");
+                    // `recurse_paths`. The remaining paths of this struct are dropped instead of
+                    // panicking.
+                    miniconf::log::error!(
+                        "Topic buffer too short for `{}`",
+                        stringify!(#field_name)
+                    );
+                    topic.truncate(original_length);
+                    return None;
                 }
 
-                if self.#field_name.recurse_paths(&mut index[1..], topic).is_some() {
+                if #recurse {
                     return Some(());
                 }
 
@@ -251,11 +568,28 @@ fn derive_struct(mut typedef: TypeDefinition, data: syn::DataStruct, atomic: boo
         }
     });
 
-    let iter_metadata_arms = fields.iter().enumerate().map(|(i, f)| {
-        let field_name = &f.ident;
+    let iter_metadata = fields.iter().map(|f| {
+        let cfgs = f.cfg_attrs();
+        let field_name = f.ident();
+
+        // Leaf fields have no children and a single index.
+        let metadata = if f.is_leaf() {
+            quote! {
+                miniconf::MiniconfMetadata {
+                    max_topic_size: 0,
+                    max_depth: 1,
+                    path_count: 1,
+                }
+            }
+        } else {
+            let get_metadata = f.method("get_metadata");
+            quote!(#get_metadata(&self.#field_name))
+        };
+
         quote! {
-            #i => {
-                let mut meta = self.#field_name.get_metadata();
+            #(#cfgs)*
+            {
+                let mut meta = #metadata;
 
                 // If the subfield has additional paths, we need to add space for a separator.
                 if meta.max_topic_size > 0 {
@@ -264,11 +598,70 @@ fn derive_struct(mut typedef: TypeDefinition, data: syn::DataStruct, atomic: boo
 
                 meta.max_topic_size += stringify!(#field_name).len();
 
-                meta
+                maximum_sizes.max_topic_size = core::cmp::max(maximum_sizes.max_topic_size,
+                                                              meta.max_topic_size);
+                maximum_sizes.max_depth = core::cmp::max(maximum_sizes.max_depth,
+                                                         meta.max_depth);
+                maximum_sizes.path_count += meta.path_count;
+            }
+        }
+    });
+
+    let iteration = iteration(quote! {
+        fn string_metadata(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<miniconf::MiniconfMetadata, miniconf::Error> {
+            let field = match topic_parts.next() {
+                Some(field) => field,
+                None => return Ok(self.get_metadata()),
+            };
+
+            match field {
+                #(#metadata_match_arms ,)*
+                _ => Err(miniconf::Error::PathNotFound)
+            }
+        }
+
+        fn get_metadata(&self) -> miniconf::MiniconfMetadata {
+            // Check all child elements, collecting the maximum length + depth of any
+            // member.
+            let mut maximum_sizes = miniconf::MiniconfMetadata {
+                max_topic_size: 0,
+                max_depth: 0,
+                path_count: 0,
+            };
+
+            #(#iter_metadata)*
+
+            // We need an additional index depth for this node.
+            maximum_sizes.max_depth += 1;
+
+            maximum_sizes
+        }
+
+        fn recurse_paths(&self, index: &mut [usize], topic: &mut impl miniconf::iter::TopicBuffer) -> Option<()> {
+            if index.is_empty() {
+                // Note: During expected execution paths using `into_iter()`, the size of the
+                // index stack is checked in advance to make sure this condition doesn't occur.
+                // However, it's possible to happen if the user manually calls `recurse_paths`.
+                unreachable!("Index stack too small");
+            }
+
+            loop {
+                match index[0] {
+                    #(#iter_match_arms ,)*
+                    _ => return None,
+
+                };
             }
         }
     });
 
+    // Applying an object member by member requires the metadata of the members.
+    let set_members = if cfg!(feature = "set-only") {
+        quote!(Err(miniconf::Error::ExpectedLeaf))
+    } else {
+        quote!(miniconf::set_members(self, value))
+    };
+
     let (impl_generics, ty_generics, where_clause) = typedef.generics.split_for_impl();
     let name = typedef.name;
 
@@ -277,7 +670,11 @@ fn derive_struct(mut typedef: TypeDefinition, data: syn::DataStruct, atomic: boo
             fn string_set(&mut self, mut topic_parts:
             core::iter::Peekable<core::str::Split<char>>, value: &[u8]) ->
             Result<(), miniconf::Error> {
-                let field = topic_parts.next().ok_or(miniconf::Error::PathTooShort)?;
+                // A JSON object at the path of the struct itself updates its members.
+                let field = match topic_parts.next() {
+                    Some(field) => field,
+                    None => return #set_members,
+                };
 
                 match field {
                     #(#set_recurse_match_arms ,)*
@@ -294,78 +691,168 @@ fn derive_struct(mut typedef: TypeDefinition, data: syn::DataStruct, atomic: boo
                 }
             }
 
-            fn get_metadata(&self) -> miniconf::MiniconfMetadata {
-                // Loop through all child elements, collecting the maximum length + depth of any
-                // member.
-                let mut maximum_sizes = miniconf::MiniconfMetadata {
-                    max_topic_size: 0,
-                    max_depth: 0
-                };
+            fn string_value_len(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<usize, miniconf::Error> {
+                let field = topic_parts.next().ok_or(miniconf::Error::PathTooShort)?;
 
-                let mut index = 0;
-                loop {
-                    let metadata = match index {
-                        #(#iter_metadata_arms ,)*
-                        _ => break,
-                    };
+                match field {
+                    #(#value_len_match_arms ,)*
+                    _ => Err(miniconf::Error::PathNotFound)
+                }
+            }
 
-                    maximum_sizes.max_topic_size = core::cmp::max(maximum_sizes.max_topic_size,
-                                                                  metadata.max_topic_size);
-                    maximum_sizes.max_depth = core::cmp::max(maximum_sizes.max_depth,
-                                                             metadata.max_depth);
+            fn string_attributes(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<miniconf::Attributes, miniconf::Error> {
+                let field = topic_parts.next().ok_or(miniconf::Error::PathTooShort)?;
 
-                    index += 1;
+                match field {
+                    #(#attributes_match_arms ,)*
+                    _ => Err(miniconf::Error::PathNotFound)
                 }
+            }
 
-                // We need an additional index depth for this node.
-                maximum_sizes.max_depth += 1;
+            fn string_type_name(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<&'static str, miniconf::Error> {
+                let field = topic_parts.next().ok_or(miniconf::Error::PathTooShort)?;
 
-                maximum_sizes
+                match field {
+                    #(#type_name_match_arms ,)*
+                    _ => Err(miniconf::Error::PathNotFound)
+                }
             }
 
-            fn recurse_paths<const TS: usize>(&self, index: &mut [usize], topic: &mut miniconf::heapless::String<TS>) -> Option<()> {
-                if index.len() == 0 {
-                    // Note: During expected execution paths using `into_iter()`, the size of the
-                    // index stack is checked in advance to make sure this condition doesn't occur.
-                    // However, it's possible to happen if the user manually calls `recurse_paths`.
-                    unreachable!("Index stack too small");
+            fn string_variants(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<&'static [&'static str], miniconf::Error> {
+                let field = topic_parts.next().ok_or(miniconf::Error::PathTooShort)?;
+
+                match field {
+                    #(#variants_match_arms ,)*
+                    _ => Err(miniconf::Error::PathNotFound)
                 }
+            }
 
-                loop {
-                    match index[0] {
-                        #(#iter_match_arms ,)*
-                        _ => return None,
+            fn string_leaf_value(&mut self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<Option<&mut dyn miniconf::LeafValue>, miniconf::Error> {
+                let field = topic_parts.next().ok_or(miniconf::Error::PathTooShort)?;
 
-                    };
+                match field {
+                    #(#leaf_value_match_arms ,)*
+                    _ => Err(miniconf::Error::PathNotFound)
                 }
             }
+
+            #iteration
         }
     };
 
     TokenStream::from(expanded)
 }
 
-/// Derive the Miniconf trait for simple enums.
+/// Derive the Miniconf trait for enums.
+///
+/// # Note
+/// Enums are set and retrieved as a whole at their path. Variants that carry a single atomic value
+/// (e.g. `Edge(EdgeParameters)`) may additionally be addressed by their variant name, which
+/// switches to the variant and sets its data in a single operation.
 ///
 /// # Args
 /// * `typedef` - The type definition.
 /// * `data` - The data associated with the enum definition.
+/// * `attrs` - The attributes of the enum definition.
 ///
 /// # Returns
 /// A token stream of the generated code.
-fn derive_enum(mut typedef: TypeDefinition, data: syn::DataEnum) -> TokenStream {
-    // Only support simple enums, check each field
+fn derive_enum(
+    mut typedef: TypeDefinition,
+    data: syn::DataEnum,
+    attrs: &[syn::Attribute],
+) -> TokenStream {
+    // Only support unit variants and variants containing a single value.
+    let mut newtype_variants = Vec::new();
     for v in data.variants.iter() {
-        match v.fields {
+        match &v.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                newtype_variants.push((&v.ident, &fields.unnamed[0].ty));
+            }
             syn::Fields::Named(_) | syn::Fields::Unnamed(_) => {
-                unimplemented!("Only simple, C-like enums are supported.")
+                unimplemented!("Only unit variants and variants with a single value are supported.")
             }
             syn::Fields::Unit => {}
         }
     }
 
+    // The variants are listed by the names serde deserializes them from.
+    let rename_all = match RenameRule::from_attributes(attrs) {
+        Ok(rule) => rule,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let variant_names = data
+        .variants
+        .iter()
+        .filter(|v| !serde_skipped(&v.attrs))
+        .map(|v| {
+            serde_name(&v.attrs, "rename")
+                .unwrap_or_else(|| rename_all.apply_to_variant(&v.ident.to_string()))
+        });
+
+    let variants_arms = newtype_variants
+        .iter()
+        .map(|(variant, _)| quote!(stringify!(#variant)));
+
     typedef.add_serde_bound();
 
+    // The data of each variant is updated atomically, so it must be (de)serializable.
+    let where_clause = typedef.generics.make_where_clause();
+    for (_, ty) in newtype_variants.iter() {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: miniconf::DeserializeOwned));
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: miniconf::Serialize));
+    }
+
+    let set_variant_arms = newtype_variants.iter().map(|(variant, _)| {
+        quote! {
+            stringify!(#variant) => {
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::AtomicUpdateRequired);
+                }
+
+                *self = Self::#variant(miniconf::serde_json_core::from_slice(value)?.0);
+                Ok(())
+            }
+        }
+    });
+
+    let get_variant_arms = newtype_variants.iter().map(|(variant, _)| {
+        quote! {
+            stringify!(#variant) => {
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::AtomicUpdateRequired);
+                }
+
+                match self {
+                    Self::#variant(data) => miniconf::serde_json_core::to_slice(data, value)
+                        .map_err(|_| miniconf::Error::SerializationFailed),
+                    _ => Err(miniconf::Error::InactiveVariant),
+                }
+            }
+        }
+    });
+
+    let value_len_variant_arms = newtype_variants.iter().map(|(variant, _)| {
+        quote! {
+            stringify!(#variant) => {
+                if topic_parts.peek().is_some() {
+                    return Err(miniconf::Error::AtomicUpdateRequired);
+                }
+
+                match self {
+                    Self::#variant(data) => miniconf::json_len(data),
+                    _ => Err(miniconf::Error::InactiveVariant),
+                }
+            }
+        }
+    });
+
+    let iteration = iteration(leaf_iteration());
+
     let (impl_generics, ty_generics, where_clause) = typedef.generics.split_for_impl();
     let name = typedef.name;
 
@@ -374,51 +861,281 @@ fn derive_enum(mut typedef: TypeDefinition, data: syn::DataEnum) -> TokenStream
             fn string_set(&mut self, mut topic_parts:
             core::iter::Peekable<core::str::Split<char>>, value: &[u8]) ->
             Result<(), miniconf::Error> {
-                if topic_parts.peek().is_some() {
-                    // We don't support enums that can contain other values
-                    return Err(miniconf::Error::PathTooLong)
-                }
+                let variant = match topic_parts.next() {
+                    Some(variant) => variant,
+                    None => {
+                        *self = miniconf::serde_json_core::from_slice(value)?.0;
+                        return Ok(());
+                    }
+                };
 
-                *self = miniconf::serde_json_core::from_slice(value)?.0;
-                Ok(())
+                match variant {
+                    #(#set_variant_arms ,)*
+                    // Only variants containing a value can be addressed.
+                    _ => Err(miniconf::Error::PathTooLong)
+                }
             }
 
             fn string_get(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>, value: &mut [u8]) -> Result<usize, miniconf::Error> {
-                if topic_parts.peek().is_some() {
-                    // We don't support enums that can contain other values
-                    return Err(miniconf::Error::PathTooLong)
-                }
+                let variant = match topic_parts.next() {
+                    Some(variant) => variant,
+                    None => {
+                        return miniconf::serde_json_core::to_slice(self, value)
+                            .map_err(|_| miniconf::Error::SerializationFailed);
+                    }
+                };
 
-                miniconf::serde_json_core::to_slice(self, value).map_err(|_| miniconf::Error::SerializationFailed)
+                match variant {
+                    #(#get_variant_arms ,)*
+                    // Only variants containing a value can be addressed.
+                    _ => Err(miniconf::Error::PathTooLong)
+                }
             }
 
-            fn get_metadata(&self) -> miniconf::MiniconfMetadata {
-                // Atomic structs have no children and a single index.
-                miniconf::MiniconfMetadata {
-                    max_topic_size: 0,
-                    max_depth: 1,
+            fn string_value_len(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<usize, miniconf::Error> {
+                let variant = match topic_parts.next() {
+                    Some(variant) => variant,
+                    None => return miniconf::json_len(self),
+                };
+
+                match variant {
+                    #(#value_len_variant_arms ,)*
+                    // Only variants containing a value can be addressed.
+                    _ => Err(miniconf::Error::PathTooLong)
                 }
             }
 
-            fn recurse_paths<const TS: usize>(&self, index: &mut [usize], topic: &mut miniconf::heapless::String<TS>) -> Option<()> {
-                if index.len() == 0 {
-                    // Note: During expected execution paths using `into_iter()`, the size of the
-                    // index stack is checked in advance to make sure this condition doesn't occur.
-                    // However, it's possible to happen if the user manually calls `recurse_paths`.
-                    unreachable!("Index stack too small");
+            fn string_variants(&self, mut topic_parts: core::iter::Peekable<core::str::Split<char>>) -> Result<&'static [&'static str], miniconf::Error> {
+                match topic_parts.next() {
+                    None => Ok(&[#(#variant_names),*]),
+                    Some(variant) if topic_parts.peek().is_none() => match variant {
+                        // The data of a variant is updated atomically and is not an enum itself.
+                        #(#variants_arms => Err(miniconf::Error::NotAnEnum),)*
+                        _ => Err(miniconf::Error::PathTooLong),
+                    },
+                    Some(_) => Err(miniconf::Error::PathTooLong),
                 }
+            }
 
-                let i = index[0];
-                index[0] += 1;
+            #iteration
+        }
+    };
 
-                if i == 0 {
-                    Some(())
-                } else {
-                    None
+    TokenStream::from(expanded)
+}
+
+/// Generate the implementation of path iteration for nodes without any members.
+fn leaf_iteration() -> proc_macro2::TokenStream {
+    quote! {
+        fn get_metadata(&self) -> miniconf::MiniconfMetadata {
+            // Leaves have no children and a single index.
+            miniconf::MiniconfMetadata {
+                max_topic_size: 0,
+                max_depth: 1,
+                path_count: 1,
+            }
+        }
+
+        fn recurse_paths(&self, index: &mut [usize], topic: &mut impl miniconf::iter::TopicBuffer) -> Option<()> {
+            if index.is_empty() {
+                // Note: During expected execution paths using `into_iter()`, the size of the
+                // index stack is checked in advance to make sure this condition doesn't occur.
+                // However, it's possible to happen if the user manually calls `recurse_paths`.
+                unreachable!("Index stack too small");
+            }
+
+            let i = index[0];
+            index[0] += 1;
+
+            if i == 0 {
+                Some(())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Find the value of a `#[serde(...)]` attribute naming the type or a variant.
+///
+/// # Args
+/// * `attrs` - The attributes to search.
+/// * `key` - The name of the attribute, e.g. `rename`.
+///
+/// # Returns
+/// The value given as `key = "..."` or as `key(deserialize = "...")`, if any.
+fn serde_name(attrs: &[syn::Attribute], key: &str) -> Option<String> {
+    serde_lit(attrs, key).map(|name| name.value())
+}
+
+/// Find the literal of a serde attribute of the form `key = "..."`, e.g. to report errors at it.
+///
+/// # Args
+/// * `attrs` - The attributes to search.
+/// * `key` - The name of the attribute, e.g. `rename_all`.
+///
+/// # Returns
+/// The literal given as `key = "..."` or as `key(deserialize = "...")`, if any.
+fn serde_lit(attrs: &[syn::Attribute], key: &str) -> Option<syn::LitStr> {
+    serde_attributes(attrs).find_map(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+            path,
+            lit: syn::Lit::Str(name),
+            ..
+        })) if path.is_ident(key) => Some(name),
+        syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident(key) => {
+            list.nested.into_iter().find_map(|meta| match meta {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(name),
+                    ..
+                })) if path.is_ident("deserialize") => Some(name),
+                _ => None,
+            })
+        }
+        _ => None,
+    })
+}
+
+/// Check whether a variant is skipped during deserialization by serde.
+fn serde_skipped(attrs: &[syn::Attribute]) -> bool {
+    serde_attributes(attrs).any(|meta| {
+        matches!(meta, syn::NestedMeta::Meta(syn::Meta::Path(path))
+            if path.is_ident("skip") || path.is_ident("skip_deserializing"))
+    })
+}
+
+/// Collect the contents of all `#[serde(...)]` attributes.
+fn serde_attributes(attrs: &[syn::Attribute]) -> impl Iterator<Item = syn::NestedMeta> + '_ {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested.into_iter()),
+            _ => None,
+        })
+        .flatten()
+}
+
+/// A serde `rename_all` rule.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    None,
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Parse the `rename_all` rule of a type.
+    ///
+    /// # Args
+    /// * `attrs` - The attributes of the type.
+    ///
+    /// # Returns
+    /// The rule, or an error spanning the rule if serde does not support it.
+    fn from_attributes(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let rule = match serde_lit(attrs, "rename_all") {
+            Some(rule) => rule,
+            None => return Ok(RenameRule::None),
+        };
+
+        match rule.value().as_str() {
+            "lowercase" => Ok(RenameRule::Lower),
+            "UPPERCASE" => Ok(RenameRule::Upper),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "camelCase" => Ok(RenameRule::Camel),
+            "snake_case" => Ok(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "kebab-case" => Ok(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameRule::ScreamingKebab),
+            other => Err(syn::Error::new_spanned(
+                &rule,
+                format!("Unsupported serde rename rule `{}`", other),
+            )),
+        }
+    }
+
+    /// Apply the rule to the name of a variant.
+    ///
+    /// # Args
+    /// * `name` - The name of the variant, which is expected to be in PascalCase.
+    ///
+    /// # Returns
+    /// The name of the variant as used by serde.
+    fn apply_to_variant(self, name: &str) -> String {
+        let separated = |separator: char| {
+            let mut renamed = String::new();
+            for (i, c) in name.char_indices() {
+                if i > 0 && c.is_uppercase() {
+                    renamed.push(separator);
                 }
+                renamed.push(c.to_ascii_lowercase());
             }
+            renamed
+        };
+
+        match self {
+            RenameRule::None | RenameRule::Pascal => name.to_string(),
+            RenameRule::Lower => name.to_ascii_lowercase(),
+            RenameRule::Upper => name.to_ascii_uppercase(),
+            RenameRule::Camel => lowercase_first(name),
+            RenameRule::Snake => separated('_'),
+            RenameRule::ScreamingSnake => separated('_').to_ascii_uppercase(),
+            RenameRule::Kebab => separated('-'),
+            RenameRule::ScreamingKebab => separated('-').to_ascii_uppercase(),
         }
-    };
+    }
 
-    TokenStream::from(expanded)
+    /// Apply the rule to the name of a field.
+    ///
+    /// # Args
+    /// * `name` - The name of the field, which is expected to be in snake_case.
+    ///
+    /// # Returns
+    /// The name of the field as used by serde.
+    fn apply_to_field(self, name: &str) -> String {
+        let pascal = || {
+            name.split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars.next().map_or_else(String::new, |first| {
+                        first.to_ascii_uppercase().to_string() + chars.as_str()
+                    })
+                })
+                .collect::<String>()
+        };
+
+        match self {
+            RenameRule::None | RenameRule::Lower | RenameRule::Snake => name.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => name.to_ascii_uppercase(),
+            RenameRule::Pascal => pascal(),
+            RenameRule::Camel => lowercase_first(&pascal()),
+            RenameRule::Kebab => name.replace('_', "-"),
+            RenameRule::ScreamingKebab => name.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+/// Convert the first character of a name to lowercase.
+fn lowercase_first(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_ascii_lowercase().to_string() + chars.as_str()
+    })
+}
+
+/// Include the implementation of path iteration unless it is compiled out by the `set-only`
+/// feature.
+fn iteration(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if cfg!(feature = "set-only") {
+        proc_macro2::TokenStream::new()
+    } else {
+        tokens
+    }
 }
@@ -21,7 +21,7 @@ async fn mqtt_client() {
     let mut mqtt: Minimq<_, _, 256, 1> = Minimq::new(
         "127.0.0.1".parse().unwrap(),
         "tester",
-        Stack::default(),
+        Stack,
         StandardClock::default(),
     )
     .unwrap();
@@ -76,7 +76,7 @@ async fn main() {
     tokio::task::spawn(async move { mqtt_client().await });
 
     let mut client: MqttClient<Settings, Stack, StandardClock, 256> = MqttClient::new(
-        Stack::default(),
+        Stack,
         "",
         "sample/prefix",
         "127.0.0.1".parse().unwrap(),
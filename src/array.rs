@@ -1,6 +1,25 @@
-use super::{Error, Miniconf, MiniconfMetadata};
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+use super::{Attributes, Error, LeafValue, Miniconf};
 
-use core::fmt::Write;
+/// Parse an array index from a path part.
+///
+/// # Note
+/// Only canonical base-10 representations are accepted, i.e. no signs, whitespace or leading
+/// zeros.
+pub(crate) fn parse_index(part: &str) -> Result<usize, Error> {
+    let canonical = match part.as_bytes() {
+        [] => false,
+        [b'0', _, ..] => false,
+        digits => digits.iter().all(u8::is_ascii_digit),
+    };
+
+    if !canonical {
+        return Err(Error::BadIndex);
+    }
+
+    part.parse().or(Err(Error::BadIndex))
+}
 
 impl<T: Miniconf, const N: usize> Miniconf for [T; N] {
     fn string_set(
@@ -10,13 +29,11 @@ impl<T: Miniconf, const N: usize> Miniconf for [T; N] {
     ) -> Result<(), Error> {
         let next = topic_parts.next();
         if next.is_none() {
-            return Err(Error::PathTooShort);
+            return Err(Error::ExpectedLeaf);
         }
 
         // Parse what should be the index value
-        let i: usize = serde_json_core::from_str(next.unwrap())
-            .or(Err(Error::BadIndex))?
-            .0;
+        let i = parse_index(next.unwrap())?;
 
         if i >= self.len() {
             return Err(Error::BadIndex);
@@ -38,9 +55,7 @@ impl<T: Miniconf, const N: usize> Miniconf for [T; N] {
         }
 
         // Parse what should be the index value
-        let i: usize = serde_json_core::from_str(next.unwrap())
-            .or(Err(Error::BadIndex))?
-            .0;
+        let i = parse_index(next.unwrap())?;
 
         if i >= self.len() {
             return Err(Error::BadIndex);
@@ -49,7 +64,132 @@ impl<T: Miniconf, const N: usize> Miniconf for [T; N] {
         self[i].string_get(topic_parts, value)
     }
 
+    fn string_value_len(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        // Parse what should be the index value
+        let i = parse_index(next.unwrap())?;
+
+        if i >= self.len() {
+            return Err(Error::BadIndex);
+        }
+
+        self[i].string_value_len(topic_parts)
+    }
+
+    fn string_attributes(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Attributes, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        // Parse what should be the index value
+        let i = parse_index(next.unwrap())?;
+
+        if i >= self.len() {
+            return Err(Error::BadIndex);
+        }
+
+        self[i].string_attributes(topic_parts)
+    }
+
+    fn string_type_name(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static str, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        // Parse what should be the index value
+        let i = parse_index(next.unwrap())?;
+
+        if i >= self.len() {
+            return Err(Error::BadIndex);
+        }
+
+        self[i].string_type_name(topic_parts)
+    }
+
+    fn string_variants(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static [&'static str], Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        // Parse what should be the index value
+        let i = parse_index(next.unwrap())?;
+
+        if i >= self.len() {
+            return Err(Error::BadIndex);
+        }
+
+        self[i].string_variants(topic_parts)
+    }
+
+    fn string_leaf_value(
+        &mut self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        // Parse what should be the index value
+        let i = parse_index(next.unwrap())?;
+
+        if i >= self.len() {
+            return Err(Error::BadIndex);
+        }
+
+        self[i].string_leaf_value(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn string_metadata(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<MiniconfMetadata, Error> {
+        let next = match topic_parts.next() {
+            Some(next) => next,
+            None => return Ok(self.get_metadata()),
+        };
+
+        // Parse what should be the index value
+        let i = parse_index(next)?;
+
+        if i >= self.len() {
+            return Err(Error::BadIndex);
+        }
+
+        self[i].string_metadata(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
     fn get_metadata(&self) -> MiniconfMetadata {
+        // Empty arrays have no members, but still require an index for iteration.
+        if N == 0 {
+            return MiniconfMetadata {
+                max_topic_size: 0,
+                max_depth: 1,
+                path_count: 0,
+            };
+        }
+
         // First, figure out how many digits the maximum index requires when printing.
         let mut index = N - 1;
         let mut num_digits = 0;
@@ -61,6 +201,9 @@ impl<T: Miniconf, const N: usize> Miniconf for [T; N] {
 
         let metadata = self[0].get_metadata();
 
+        // Elements may contain a different number of paths (e.g. optional members).
+        let path_count = self.iter().map(|item| item.get_metadata().path_count).sum();
+
         // If the sub-members have topic size, we also need to include an additional character for
         // the path separator. This is ommitted if the sub-members have no topic (e.g. fundamental
         // types, enums).
@@ -68,23 +211,22 @@ impl<T: Miniconf, const N: usize> Miniconf for [T; N] {
             MiniconfMetadata {
                 max_topic_size: metadata.max_topic_size + num_digits + 1,
                 max_depth: metadata.max_depth + 1,
+                path_count,
             }
         } else {
             MiniconfMetadata {
                 max_topic_size: num_digits,
                 max_depth: metadata.max_depth + 1,
+                path_count,
             }
         }
     }
 
-    fn recurse_paths<const TS: usize>(
-        &self,
-        index: &mut [usize],
-        topic: &mut heapless::String<TS>,
-    ) -> Option<()> {
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl TopicBuffer) -> Option<()> {
         let original_length = topic.len();
 
-        if index.len() == 0 {
+        if index.is_empty() {
             // Note: During expected execution paths using `into_iter()`, the size of the
             // index stack is checked in advance to make sure this condition doesn't occur.
             // However, it's possible to happen if the user manually calls `recurse_paths`.
@@ -93,20 +235,17 @@ impl<T: Miniconf, const N: usize> Miniconf for [T; N] {
 
         while index[0] < N {
             // Add the array index to the topic name.
-            if topic.len() > 0 {
-                if topic.push('/').is_err() {
-                    // Note: During expected execution paths using `into_iter()`, the size of the
-                    // topic buffer is checked in advance to make sure this condition doesn't occur.
-                    // However, it's possible to happen if the user manually calls `recurse_paths`.
-                    unreachable!("Topic buffer too short");
-                }
-            }
+            let written = (topic.is_empty() || topic.write_char('/').is_ok())
+                && write!(topic, "{}", index[0]).is_ok();
 
-            if write!(topic, "{}", index[0]).is_err() {
+            if !written {
                 // Note: During expected execution paths using `into_iter()`, the size of the
                 // topic buffer is checked in advance to make sure this condition doesn't occur.
                 // However, it's possible to happen if the user manually calls `recurse_paths`.
-                unreachable!("Topic buffer too short");
+                // The remaining paths of this array are dropped instead of panicking.
+                log::error!("Topic buffer too short for array index {}", index[0]);
+                topic.truncate(original_length);
+                return None;
             }
 
             if self[index[0]]
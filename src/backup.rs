@@ -0,0 +1,144 @@
+//! Backup and restore of complete settings instances.
+//!
+//! # Format
+//! A backup is a sequence of records, one for each settings path. Each record consists of the
+//! path followed by its serialized value, each prefixed with its length in bytes as a
+//! little-endian `u16`. The records are followed by the CRC-32 (IEEE 802.3) of all records as a
+//! little-endian `u32`.
+use super::{Error, Miniconf};
+use core::convert::TryFrom;
+
+/// Errors that occur when restoring settings from a backup.
+#[derive(Debug, PartialEq)]
+pub enum BackupError<'a> {
+    /// The backup data is not a valid sequence of records.
+    Malformed,
+
+    /// The checksum of the backup does not match its contents.
+    ///
+    /// This typically indicates that the stored backup was corrupted.
+    Corrupt,
+
+    /// The value of a path in the backup could not be applied to the settings.
+    ///
+    /// This typically indicates that the settings structure changed since the backup was taken.
+    Apply {
+        /// The path that failed to apply.
+        path: &'a str,
+
+        /// The reason that the path failed to apply.
+        error: Error,
+    },
+}
+
+/// Compute the CRC-32 (IEEE 802.3) of the provided data.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+/// Write a length-prefixed field into the backup.
+///
+/// # Args
+/// * `data` - The backup buffer.
+/// * `len` - The current length of the backup. Updated with the written data.
+/// * `field` - A closure that serializes the field into the provided buffer.
+fn write_field(
+    data: &mut [u8],
+    len: &mut usize,
+    field: impl FnOnce(&mut [u8]) -> Result<usize, Error>,
+) -> Result<(), Error> {
+    let start = *len + 2;
+    let buffer = data.get_mut(start..).ok_or(Error::SerializationFailed)?;
+    let size = field(buffer)?;
+    let size_bytes = u16::try_from(size)
+        .map_err(|_| Error::SerializationFailed)?
+        .to_le_bytes();
+
+    data[*len..start].copy_from_slice(&size_bytes);
+    *len = start + size;
+    Ok(())
+}
+
+/// Read a length-prefixed field from the backup.
+///
+/// # Args
+/// * `data` - The remaining backup data. Advanced past the field.
+///
+/// # Returns
+/// The contents of the field.
+fn read_field<'a>(data: &mut &'a [u8]) -> Result<&'a [u8], BackupError<'a>> {
+    if data.len() < 2 {
+        return Err(BackupError::Malformed);
+    }
+
+    let (size, remainder) = data.split_at(2);
+    let size = u16::from_le_bytes([size[0], size[1]]) as usize;
+    if remainder.len() < size {
+        return Err(BackupError::Malformed);
+    }
+
+    let (field, remainder) = remainder.split_at(size);
+    *data = remainder;
+    Ok(field)
+}
+
+pub(crate) fn serialize<Settings: Miniconf + ?Sized>(
+    settings: &Settings,
+    data: &mut [u8],
+) -> Result<usize, Error> {
+    let mut len = 0;
+    let mut state = [0; 16];
+    for path in settings.into_iter::<128>(&mut state)? {
+        write_field(data, &mut len, |buffer| {
+            buffer
+                .get_mut(..path.len())
+                .ok_or(Error::SerializationFailed)?
+                .copy_from_slice(path.as_bytes());
+            Ok(path.len())
+        })?;
+        write_field(data, &mut len, |buffer| settings.get(&path, buffer))?;
+    }
+
+    let crc = crc32(&data[..len]).to_le_bytes();
+    data.get_mut(len..len + crc.len())
+        .ok_or(Error::SerializationFailed)?
+        .copy_from_slice(&crc);
+
+    Ok(len + crc.len())
+}
+
+pub(crate) fn restore<Settings: Miniconf + Default>(
+    data: &[u8],
+) -> Result<Settings, BackupError<'_>> {
+    if data.len() < 4 {
+        return Err(BackupError::Malformed);
+    }
+
+    let (mut data, crc) = data.split_at(data.len() - 4);
+    if crc32(data).to_le_bytes() != crc {
+        return Err(BackupError::Corrupt);
+    }
+
+    let mut settings = Settings::default();
+
+    while !data.is_empty() {
+        let path =
+            core::str::from_utf8(read_field(&mut data)?).map_err(|_| BackupError::Malformed)?;
+        let value = read_field(&mut data)?;
+
+        settings
+            .set(path, value)
+            .map_err(|error| BackupError::Apply { path, error })?;
+    }
+
+    Ok(settings)
+}
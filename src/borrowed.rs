@@ -0,0 +1,77 @@
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+use super::{Attributes, Error, LeafValue, Miniconf};
+
+/// Mutably borrowed settings are transparent, i.e. they occupy the same paths as the settings they
+/// borrow. This allows settings owned elsewhere to be served, e.g. as one of several
+/// [crate::Domains].
+impl<T: Miniconf> Miniconf for &mut T {
+    fn string_set(
+        &mut self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        (**self).string_set(topic_parts, value)
+    }
+
+    fn string_get(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &mut [u8],
+    ) -> Result<usize, Error> {
+        (**self).string_get(topic_parts, value)
+    }
+
+    fn string_value_len(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        (**self).string_value_len(topic_parts)
+    }
+
+    fn string_attributes(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Attributes, Error> {
+        (**self).string_attributes(topic_parts)
+    }
+
+    fn string_type_name(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static str, Error> {
+        (**self).string_type_name(topic_parts)
+    }
+
+    fn string_variants(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static [&'static str], Error> {
+        (**self).string_variants(topic_parts)
+    }
+
+    fn string_leaf_value(
+        &mut self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        (**self).string_leaf_value(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn string_metadata(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<MiniconfMetadata, Error> {
+        (**self).string_metadata(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn get_metadata(&self) -> MiniconfMetadata {
+        (**self).get_metadata()
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl TopicBuffer) -> Option<()> {
+        (**self).recurse_paths(index, topic)
+    }
+}
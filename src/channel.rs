@@ -0,0 +1,155 @@
+//! Settings access over a line-based byte channel.
+//!
+//! # Protocol
+//! Every request is a single line terminated by `\n`:
+//! * `GET <path>` - Retrieve a setting. Answered with `OK <value>`.
+//! * `SET <path> <value>` - Update a setting. Answered with `OK`.
+//! * `LIST` - Retrieve all settings paths. Answered with one line per path followed by `OK`.
+//!
+//! Failed requests are answered with `ERR <description>`.
+use super::Miniconf;
+use embedded_io::{Read, Write, WriteFmtError};
+use heapless::Vec;
+
+// The maximum recursive depth of a settings structure.
+const MAX_RECURSION_DEPTH: usize = 16;
+
+/// Settings interface over a byte channel, e.g. a UART or USB-CDC serial port.
+///
+/// # Template Arguments
+/// * `LINE_SIZE` - The maximum size of request lines and serialized settings values.
+pub struct SettingsChannel<Settings, Io, const LINE_SIZE: usize = 256>
+where
+    Settings: Miniconf,
+    Io: Read + Write,
+{
+    io: Io,
+    settings: Settings,
+    line: Vec<u8, LINE_SIZE>,
+    overflow: bool,
+}
+
+impl<Settings, Io, const LINE_SIZE: usize> SettingsChannel<Settings, Io, LINE_SIZE>
+where
+    Settings: Miniconf,
+    Io: Read + Write,
+{
+    /// Construct a new settings channel.
+    ///
+    /// # Args
+    /// * `io` - The byte channel to receive requests from and send responses to.
+    /// * `settings` - The initial settings values.
+    pub fn new(io: Io, settings: Settings) -> Self {
+        Self {
+            io,
+            settings,
+            line: Vec::new(),
+            overflow: false,
+        }
+    }
+
+    /// Receive data from the channel and handle all complete requests.
+    ///
+    /// # Note
+    /// If reads from the channel are blocking, this blocks until data is available.
+    ///
+    /// # Returns
+    /// True if the settings changed. False otherwise.
+    pub fn update(&mut self) -> Result<bool, Io::Error> {
+        let mut buffer = [0; 64];
+        let len = self.io.read(&mut buffer)?;
+
+        let mut updated = false;
+        for &byte in &buffer[..len] {
+            if byte != b'\n' {
+                // Requests that exceed the line size are discarded up to the end of the line.
+                if self.line.push(byte).is_err() {
+                    self.overflow = true;
+                }
+
+                continue;
+            }
+
+            let line = core::mem::take(&mut self.line);
+            if core::mem::take(&mut self.overflow) {
+                self.io.write_all(b"ERR Request too long\n")?;
+                continue;
+            }
+
+            updated |= self.handle_request(&line)?;
+        }
+
+        Ok(updated)
+    }
+
+    /// Get the current settings.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Release the channel and the settings.
+    pub fn free(self) -> (Io, Settings) {
+        (self.io, self.settings)
+    }
+
+    fn handle_request(&mut self, line: &[u8]) -> Result<bool, Io::Error> {
+        let request = match core::str::from_utf8(line) {
+            Ok(request) => request.trim_end_matches('\r'),
+            Err(_) => {
+                self.io.write_all(b"ERR Invalid UTF-8\n")?;
+                return Ok(false);
+            }
+        };
+
+        let (command, args) = request.split_once(' ').unwrap_or((request, ""));
+        match command {
+            "GET" => {
+                let mut value = [0; LINE_SIZE];
+                match self.settings.get(args, &mut value) {
+                    Ok(len) => {
+                        self.io.write_all(b"OK ")?;
+                        self.io.write_all(&value[..len])?;
+                        self.io.write_all(b"\n")?;
+                    }
+                    Err(err) => respond(&mut self.io, format_args!("ERR {:?}\n", err))?,
+                }
+            }
+            "SET" => {
+                let (path, value) = args.split_once(' ').unwrap_or((args, ""));
+                match self.settings.set(path, value.as_bytes()) {
+                    Ok(_) => {
+                        self.io.write_all(b"OK\n")?;
+                        return Ok(true);
+                    }
+                    Err(err) => respond(&mut self.io, format_args!("ERR {:?}\n", err))?,
+                }
+            }
+            "LIST" => {
+                let mut state = [0; MAX_RECURSION_DEPTH];
+                match self.settings.into_iter::<LINE_SIZE>(&mut state) {
+                    Ok(paths) => {
+                        for path in paths {
+                            self.io.write_all(path.as_bytes())?;
+                            self.io.write_all(b"\n")?;
+                        }
+
+                        self.io.write_all(b"OK\n")?;
+                    }
+                    Err(err) => respond(&mut self.io, format_args!("ERR {:?}\n", err))?,
+                }
+            }
+            _ => self.io.write_all(b"ERR Unknown command\n")?,
+        }
+
+        Ok(false)
+    }
+}
+
+/// Write a formatted response to the channel.
+fn respond<Io: Write>(io: &mut Io, args: core::fmt::Arguments) -> Result<(), Io::Error> {
+    match io.write_fmt(args) {
+        Err(WriteFmtError::Other(err)) => Err(err),
+        // Note: Formatting the debug representation of errors cannot fail.
+        _ => Ok(()),
+    }
+}
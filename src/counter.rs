@@ -0,0 +1,345 @@
+//! Determination of the length of serialized values without serializing them into a buffer.
+use super::Error;
+use serde::ser::{self, Impossible, Serialize};
+
+/// An error that occurred while counting.
+#[derive(Debug)]
+struct CountError;
+
+impl core::fmt::Display for CountError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Serialization failed")
+    }
+}
+
+impl ser::StdError for CountError {}
+
+impl ser::Error for CountError {
+    fn custom<T: core::fmt::Display>(_msg: T) -> Self {
+        CountError
+    }
+}
+
+/// A serializer that counts the bytes `serde_json_core` would serialize a value into.
+#[derive(Default)]
+struct Counter {
+    len: usize,
+}
+
+impl Counter {
+    fn add(&mut self, len: usize) -> Result<(), CountError> {
+        self.len = self.len.checked_add(len).ok_or(CountError)?;
+        Ok(())
+    }
+
+    /// Count a number, which `serde_json_core` formats into at most 24 bytes.
+    fn number<T: Serialize>(&mut self, value: T) -> Result<(), CountError> {
+        let len = serde_json_core::to_slice(&value, &mut [0; 24]).map_err(|_| CountError)?;
+        self.add(len)
+    }
+
+    /// Count a string including its quotes and escape sequences.
+    fn string(&mut self, value: &str) -> Result<(), CountError> {
+        let escaped: usize = value
+            .chars()
+            .map(|c| match c {
+                '\\' | '"' | '\u{0008}' | '\u{0009}' | '\u{000A}' | '\u{000C}' | '\u{000D}' => 2,
+                '\u{0000}'..='\u{001F}' => 6,
+                c => c.len_utf8(),
+            })
+            .sum();
+        self.add(escaped + 2)
+    }
+}
+
+/// Counts the members of sequences, maps and structs.
+struct Members<'a> {
+    counter: &'a mut Counter,
+    first: bool,
+    close: usize,
+}
+
+impl<'a> Members<'a> {
+    fn separate(&mut self) -> Result<(), CountError> {
+        if !self.first {
+            self.counter.add(1)?;
+        }
+
+        self.first = false;
+        Ok(())
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), CountError> {
+        // Keys of struct fields are written verbatim with quotes and a colon.
+        self.separate()?;
+        self.counter.add(key.len() + 3)?;
+        value.serialize(&mut *self.counter)
+    }
+
+    fn end(self) -> Result<(), CountError> {
+        self.counter.add(self.close)
+    }
+}
+
+impl<'a> ser::SerializeSeq for Members<'a> {
+    type Ok = ();
+    type Error = CountError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CountError> {
+        self.separate()?;
+        value.serialize(&mut *self.counter)
+    }
+
+    fn end(self) -> Result<(), CountError> {
+        Members::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTuple for Members<'a> {
+    type Ok = ();
+    type Error = CountError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CountError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), CountError> {
+        Members::end(self)
+    }
+}
+
+impl<'a> ser::SerializeMap for Members<'a> {
+    type Ok = ();
+    type Error = CountError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), CountError> {
+        self.separate()?;
+        key.serialize(&mut *self.counter)?;
+        self.counter.add(1)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CountError> {
+        value.serialize(&mut *self.counter)
+    }
+
+    fn end(self) -> Result<(), CountError> {
+        Members::end(self)
+    }
+}
+
+impl<'a> ser::SerializeStruct for Members<'a> {
+    type Ok = ();
+    type Error = CountError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), CountError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), CountError> {
+        Members::end(self)
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Members<'a> {
+    type Ok = ();
+    type Error = CountError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), CountError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), CountError> {
+        Members::end(self)
+    }
+}
+
+macro_rules! number {
+    ($($method:ident: $ty:ty),+) => {
+        $(
+        fn $method(self, value: $ty) -> Result<(), CountError> {
+            self.number(value)
+        }
+        )+
+    };
+}
+
+impl<'a> ser::Serializer for &'a mut Counter {
+    type Ok = ();
+    type Error = CountError;
+    type SerializeSeq = Members<'a>;
+    type SerializeTuple = Members<'a>;
+    type SerializeTupleStruct = Impossible<(), CountError>;
+    type SerializeTupleVariant = Impossible<(), CountError>;
+    type SerializeMap = Members<'a>;
+    type SerializeStruct = Members<'a>;
+    type SerializeStructVariant = Members<'a>;
+
+    number!(
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_f32: f32,
+        serialize_f64: f64
+    );
+
+    fn serialize_bool(self, value: bool) -> Result<(), CountError> {
+        self.add(if value { 4 } else { 5 })
+    }
+
+    fn serialize_char(self, _value: char) -> Result<(), CountError> {
+        // Not supported by `serde_json_core`.
+        Err(CountError)
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), CountError> {
+        self.string(value)
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), CountError> {
+        // Bytes are written verbatim.
+        self.add(value.len())
+    }
+
+    fn serialize_none(self) -> Result<(), CountError> {
+        self.add(4)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), CountError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), CountError> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), CountError> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), CountError> {
+        self.string(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), CountError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), CountError> {
+        // Serialized as an object with a single member named by the variant.
+        self.add(1)?;
+        let mut members = Members {
+            counter: self,
+            first: true,
+            close: 1,
+        };
+        members.field(variant, value)?;
+        members.end()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Members<'a>, CountError> {
+        self.add(1)?;
+        Ok(Members {
+            counter: self,
+            first: true,
+            close: 1,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Members<'a>, CountError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, CountError> {
+        // Not supported by `serde_json_core`.
+        Err(CountError)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, CountError> {
+        // Not supported by `serde_json_core`.
+        Err(CountError)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Members<'a>, CountError> {
+        self.serialize_seq(len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Members<'a>, CountError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Members<'a>, CountError> {
+        // Serialized as an object with a single member named by the variant, containing an object
+        // of the fields.
+        self.add(variant.len() + 5)?;
+        Ok(Members {
+            counter: self,
+            first: true,
+            close: 2,
+        })
+    }
+
+    fn collect_str<T: core::fmt::Display + ?Sized>(self, _value: &T) -> Result<(), CountError> {
+        // Not supported by `serde_json_core`.
+        Err(CountError)
+    }
+}
+
+/// Determine the length of a value serialized using `serde_json_core`.
+///
+/// # Args
+/// * `value` - The value to measure.
+///
+/// # Returns
+/// The number of bytes the serialized value occupies.
+#[doc(hidden)]
+pub fn json_len<T: Serialize + ?Sized>(value: &T) -> Result<usize, Error> {
+    let mut counter = Counter::default();
+    value
+        .serialize(&mut counter)
+        .map_err(|_| Error::SerializationFailed)?;
+    Ok(counter.len)
+}
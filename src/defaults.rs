@@ -0,0 +1,33 @@
+use super::{diff::serialize_value, iter::PathIterator, Error, IterError, Miniconf};
+use heapless::{String, Vec};
+
+/// Iterate the paths of the default settings along with their serialized values.
+///
+/// # Note
+/// The settings are constructed using [Default], so no live instance is required, e.g. to generate
+/// a template of the factory configuration. Paths that are absent in the default settings (e.g. an
+/// `Option` that is `None`) are not yielded.
+///
+/// # Template Arguments
+/// * `TS` - The maximum number of bytes to encode a settings path into.
+/// * `VS` - The maximum number of bytes to serialize a settings value into.
+/// * `DEPTH` - The maximum recursive depth of the settings.
+///
+/// # Returns
+/// An iterator over the `(path, value)` pairs of the default settings. Values that do not fit into
+/// `VS` bytes are yielded as errors. An error is returned instead if the paths do not fit within
+/// `TS` and `DEPTH`.
+pub fn default_paths<
+    Settings: Miniconf + Default,
+    const TS: usize,
+    const VS: usize,
+    const DEPTH: usize,
+>() -> Result<impl Iterator<Item = Result<(String<TS>, Vec<u8, VS>), Error>>, IterError> {
+    let settings = Settings::default();
+    let mut paths = PathIterator::<TS, DEPTH>::new(&settings)?;
+
+    Ok(core::iter::from_fn(move || {
+        let path = paths.next(&settings)?;
+        Some(serialize_value::<_, VS>(&settings, &path).map(|value| (path, value)))
+    }))
+}
@@ -0,0 +1,133 @@
+use super::{iter::PathIterator, Error, IterError, Miniconf};
+use heapless::{String, Vec};
+
+struct DiffIter<
+    'a,
+    Settings: Miniconf + ?Sized,
+    const TS: usize,
+    const VS: usize,
+    const DEPTH: usize,
+> {
+    current: &'a Settings,
+    desired: &'a Settings,
+    paths: PathIterator<TS, DEPTH>,
+}
+
+impl<'a, Settings: Miniconf + ?Sized, const TS: usize, const VS: usize, const DEPTH: usize> Iterator
+    for DiffIter<'a, Settings, TS, VS, DEPTH>
+{
+    type Item = Result<(String<TS>, Vec<u8, VS>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let path = self.paths.next(self.desired)?;
+            let desired = match serialize_value::<_, VS>(self.desired, &path) {
+                Ok(desired) => desired,
+                Err(error) => return Some(Err(error)),
+            };
+
+            // Any path that cannot be read from the current settings (e.g. an `Option` that is
+            // `None`) is considered to differ.
+            let unchanged = matches!(
+                serialize_value::<_, VS>(self.current, &path),
+                Ok(current) if current == desired
+            );
+
+            if !unchanged {
+                return Some(Ok((path, desired)));
+            }
+        }
+    }
+}
+
+/// Serialize the value of a settings path into an owned buffer.
+///
+/// # Returns
+/// The serialized value, or [Error::SerializationFailed] if it does not fit into `VS` bytes.
+pub(crate) fn serialize_value<Settings: Miniconf + ?Sized, const VS: usize>(
+    settings: &Settings,
+    path: &str,
+) -> Result<Vec<u8, VS>, Error> {
+    let mut value = Vec::new();
+    // Note(unwrap): The vector is resized to exactly its capacity.
+    value.resize_default(VS).unwrap();
+
+    let len = settings.get(path, &mut value)?;
+    value.truncate(len);
+    Ok(value)
+}
+
+/// Compute the updates required to reconcile two settings instances.
+///
+/// # Note
+/// Leaf values are compared in their serialized form. Only paths whose values differ are yielded.
+///
+/// # Template Arguments
+/// * `TS` - The maximum number of bytes to encode a settings path into.
+/// * `VS` - The maximum number of bytes to serialize a settings value into.
+/// * `DEPTH` - The maximum recursive depth of the settings.
+///
+/// # Args
+/// * `current` - The current settings.
+/// * `desired` - The desired settings.
+///
+/// # Returns
+/// An iterator over `(path, value)` pairs that must be applied to `current` to obtain `desired`.
+/// Values that do not fit into `VS` bytes are yielded as errors. An error is returned instead if
+/// the paths do not fit within `TS` and `DEPTH`.
+pub fn diff<
+    'a,
+    Settings: Miniconf + ?Sized,
+    const TS: usize,
+    const VS: usize,
+    const DEPTH: usize,
+>(
+    current: &'a Settings,
+    desired: &'a Settings,
+) -> Result<impl Iterator<Item = Result<(String<TS>, Vec<u8, VS>), Error>> + 'a, IterError> {
+    Ok(DiffIter::<'a, Settings, TS, VS, DEPTH> {
+        current,
+        desired,
+        paths: PathIterator::new(desired)?,
+    })
+}
+
+/// Check whether all settings paths of an instance are present with identical values in another.
+///
+/// # Returns
+/// True if every path of `settings` can be read from `other` with an identical serialized value.
+pub(crate) fn contained<
+    Settings: Miniconf + ?Sized,
+    const TS: usize,
+    const VS: usize,
+    const DEPTH: usize,
+>(
+    settings: &Settings,
+    other: &Settings,
+) -> Result<bool, Error> {
+    let mut paths = PathIterator::<TS, DEPTH>::new(settings)?;
+    let (mut value, mut other_value) = ([0; VS], [0; VS]);
+
+    while let Some(path) = paths.next(settings) {
+        // Values of different sizes differ without being serialized. Values that cannot be
+        // measured, e.g. those serialized by a module, are only compared by their contents.
+        if let (Ok(len), Ok(other_len)) = (settings.value_len(&path), other.value_len(&path)) {
+            if len != other_len {
+                return Ok(false);
+            }
+        }
+
+        let len = settings.get(&path, &mut value)?;
+
+        // Paths that cannot be read from the other instance (e.g. an `Option` that is `None`)
+        // differ.
+        if !matches!(
+            other.get(&path, &mut other_value),
+            Ok(other_len) if other_value[..other_len] == value[..len]
+        ) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,254 @@
+#[cfg(not(feature = "set-only"))]
+use super::{
+    iter::TopicBuffer,
+    tuple::{add_member_metadata, recurse_members},
+    MiniconfMetadata,
+};
+use super::{Attributes, Error, LeafValue, Miniconf};
+
+/// Independent settings structures served side by side under distinct names.
+///
+/// # Note
+/// The first path segment selects the domain by name, e.g. with domains named `net` and `dsp`,
+/// `net/address` and `dsp/gain` address settings of the respective structures. Domains are
+/// provided as a tuple of two to four settings structures and iterated in the order of the
+/// tuple, so republication publishes all paths of one domain before those of the next. Domains
+/// may also be mutable references to settings owned elsewhere.
+///
+/// # Example
+/// ```rust
+/// # use miniconf::{Domains, Miniconf};
+/// #[derive(Miniconf, Default)]
+/// struct Network {
+///     port: u16,
+/// }
+///
+/// #[derive(Miniconf, Default)]
+/// struct Dsp {
+///     gain: f32,
+/// }
+///
+/// let domains = (Network::default(), Dsp::default());
+/// let mut settings = Domains::new(&["net", "dsp"], domains).unwrap();
+/// settings.set("net/port", b"1883").unwrap();
+/// assert_eq!(settings.0.port, 1883);
+///
+/// // Settings owned elsewhere are served by borrowing them.
+/// let mut dsp = Dsp::default();
+/// let mut settings = Domains::new(&["net", "dsp"], (Network::default(), &mut dsp)).unwrap();
+/// settings.set("dsp/gain", b"0.5").unwrap();
+/// assert_eq!(dsp.gain, 0.5);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Domains<T, const N: usize> {
+    names: &'static [&'static str; N],
+    domains: T,
+}
+
+/// An error in the names of [Domains].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DomainsError {
+    /// The name is empty or contains a `/`.
+    InvalidName(&'static str),
+
+    /// The name is used by more than one domain.
+    DuplicateName(&'static str),
+}
+
+impl<T, const N: usize> Domains<T, N> {
+    /// Construct the settings domains.
+    ///
+    /// # Args
+    /// * `names` - The name of each domain. Names must be unique, non-empty and may not contain
+    ///   `/`.
+    /// * `domains` - A tuple of the settings of each domain.
+    ///
+    /// # Returns
+    /// The domains, or an error naming the first name that cannot be used.
+    pub fn new(names: &'static [&'static str; N], domains: T) -> Result<Self, DomainsError> {
+        for (i, name) in names.iter().enumerate() {
+            if name.is_empty() || name.contains('/') {
+                return Err(DomainsError::InvalidName(name));
+            }
+
+            if names[..i].contains(name) {
+                return Err(DomainsError::DuplicateName(name));
+            }
+        }
+
+        Ok(Self { names, domains })
+    }
+
+    /// Get the names of the domains.
+    pub fn names(&self) -> &'static [&'static str; N] {
+        self.names
+    }
+
+    /// Release the settings of the domains.
+    pub fn into_inner(self) -> T {
+        self.domains
+    }
+
+    /// Find the index of a domain from its name.
+    fn index(&self, part: &str) -> Result<usize, Error> {
+        self.names
+            .iter()
+            .position(|name| *name == part)
+            .ok_or(Error::PathNotFound)
+    }
+}
+
+impl<T, const N: usize> core::ops::Deref for Domains<T, N> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.domains
+    }
+}
+
+impl<T, const N: usize> core::ops::DerefMut for Domains<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.domains
+    }
+}
+
+macro_rules! impl_domains {
+    ($n:literal; $($i:tt: $t:ident),+) => {
+        impl<$($t: Miniconf),+> Miniconf for Domains<($($t,)+), $n> {
+            fn string_set(
+                &mut self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+                value: &[u8],
+            ) -> Result<(), Error> {
+                let i = self.index(topic_parts.next().ok_or(Error::ExpectedLeaf)?)?;
+                match i {
+                    $($i => self.domains.$i.string_set(topic_parts, value),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn string_get(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+                value: &mut [u8],
+            ) -> Result<usize, Error> {
+                let i = self.index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.domains.$i.string_get(topic_parts, value),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn string_value_len(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<usize, Error> {
+                let i = self.index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.domains.$i.string_value_len(topic_parts),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn string_attributes(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<Attributes, Error> {
+                let i = self.index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.domains.$i.string_attributes(topic_parts),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn string_type_name(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<&'static str, Error> {
+                let i = self.index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.domains.$i.string_type_name(topic_parts),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn string_variants(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<&'static [&'static str], Error> {
+                let i = self.index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.domains.$i.string_variants(topic_parts),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn string_leaf_value(
+                &mut self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<Option<&mut dyn LeafValue>, Error> {
+                let i = self.index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.domains.$i.string_leaf_value(topic_parts),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            #[cfg(not(feature = "set-only"))]
+            fn string_metadata(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<MiniconfMetadata, Error> {
+                let i = match topic_parts.next() {
+                    Some(next) => self.index(next)?,
+                    None => return Ok(self.get_metadata()),
+                };
+
+                match i {
+                    $($i => self.domains.$i.string_metadata(topic_parts),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            #[cfg(not(feature = "set-only"))]
+            fn get_metadata(&self) -> MiniconfMetadata {
+                let mut maximum_sizes = MiniconfMetadata::default();
+
+                $(
+                    add_member_metadata(
+                        &mut maximum_sizes,
+                        self.names[$i].len(),
+                        self.domains.$i.get_metadata(),
+                    );
+                )+
+
+                // We need an additional index depth for the domain.
+                maximum_sizes.max_depth += 1;
+
+                maximum_sizes
+            }
+
+            #[cfg(not(feature = "set-only"))]
+            fn recurse_paths(
+                &self,
+                index: &mut [usize],
+                topic: &mut impl TopicBuffer,
+            ) -> Option<()> {
+                recurse_members(
+                    index,
+                    topic,
+                    $n,
+                    |i, topic| topic.write_str(self.names[i]),
+                    |i, index, topic| match i {
+                        $($i => self.domains.$i.recurse_paths(index, topic),)+
+                        _ => unreachable!(),
+                    },
+                )
+            }
+        }
+    };
+}
+
+impl_domains!(2; 0: A, 1: B);
+impl_domains!(3; 0: A, 1: B, 2: C);
+impl_domains!(4; 0: A, 1: B, 2: C, 3: D);
@@ -0,0 +1,147 @@
+//! Settings of the fixed-point types of the `fixed` crate.
+//!
+//! # Note
+//! Fixed-point values are transmitted as JSON numbers in their exact decimal representation,
+//! e.g. `1.5` for an `I16F16` of one and a half, not as their raw integer representation.
+//! Values with more fractional digits than representable are rounded to the nearest
+//! representable value. Values outside of the range of the type and numbers in exponent
+//! notation are rejected.
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+use super::{Error, Miniconf};
+use fixed::types::extra::{LeEqU128, LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+use fixed::{
+    FixedI128, FixedI16, FixedI32, FixedI64, FixedI8, FixedU128, FixedU16, FixedU32, FixedU64,
+    FixedU8,
+};
+
+/// A formatting destination writing into a byte buffer.
+struct Cursor<'a> {
+    data: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> core::fmt::Write for Cursor<'a> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.data
+            .get_mut(self.len..self.len + s.len())
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+/// A formatting destination that only counts the bytes written.
+#[derive(Default)]
+struct Length(usize);
+
+impl core::fmt::Write for Length {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+macro_rules! impl_fixed {
+    ($($ty:ident: $bound:ident),+) => {
+        $(
+        impl<Frac: $bound> Miniconf for $ty<Frac> {
+            fn string_set(
+                &mut self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+                value: &[u8],
+            ) -> Result<(), Error> {
+                if topic_parts.peek().is_some() {
+                    return Err(Error::PathTooLong);
+                }
+
+                // Note: Out-of-range values are rejected like those of integer settings.
+                let invalid = || Error::Deserialization(serde_json_core::de::Error::InvalidNumber);
+                let number = core::str::from_utf8(value).map_err(|_| invalid())?.trim();
+                *self = number.parse().map_err(|_| invalid())?;
+                Ok(())
+            }
+
+            fn string_get(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+                value: &mut [u8],
+            ) -> Result<usize, Error> {
+                use core::fmt::Write;
+
+                if topic_parts.peek().is_some() {
+                    return Err(Error::PathTooLong);
+                }
+
+                let mut cursor = Cursor { data: value, len: 0 };
+                write!(cursor, "{}", self).map_err(|_| Error::SerializationFailed)?;
+                Ok(cursor.len)
+            }
+
+            fn string_value_len(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<usize, Error> {
+                use core::fmt::Write;
+
+                if topic_parts.peek().is_some() {
+                    return Err(Error::PathTooLong);
+                }
+
+                let mut length = Length::default();
+                write!(length, "{}", self).map_err(|_| Error::SerializationFailed)?;
+                Ok(length.0)
+            }
+
+            #[cfg(not(feature = "set-only"))]
+            fn get_metadata(&self) -> MiniconfMetadata {
+                MiniconfMetadata {
+                    // No topic length is needed, as there are no sub-members.
+                    max_topic_size: 0,
+                    // One index is required for the current element.
+                    max_depth: 1,
+                    path_count: 1,
+                }
+            }
+
+            #[cfg(not(feature = "set-only"))]
+            fn recurse_paths(
+                &self,
+                index: &mut [usize],
+                _topic: &mut impl TopicBuffer,
+            ) -> Option<()> {
+                if index.is_empty() {
+                    // Note: During expected execution paths using `into_iter()`, the size of the
+                    // index stack is checked in advance to make sure this condition doesn't occur.
+                    // However, it's possible to happen if the user manually calls `recurse_paths`.
+                    unreachable!("Index stack too small");
+                }
+
+                let i = index[0];
+                index[0] += 1;
+                index[1..].iter_mut().for_each(|x| *x = 0);
+
+                if i == 0 {
+                    Some(())
+                } else {
+                    None
+                }
+            }
+        }
+        )+
+    };
+}
+
+impl_fixed!(
+    FixedI8: LeEqU8,
+    FixedI16: LeEqU16,
+    FixedI32: LeEqU32,
+    FixedI64: LeEqU64,
+    FixedI128: LeEqU128,
+    FixedU8: LeEqU8,
+    FixedU16: LeEqU16,
+    FixedU32: LeEqU32,
+    FixedU64: LeEqU64,
+    FixedU128: LeEqU128
+);
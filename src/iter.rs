@@ -1,6 +1,62 @@
-use super::Miniconf;
+use super::{IterError, Miniconf};
 use heapless::String;
 
+/// A buffer that settings paths can be written into during iteration.
+pub trait TopicBuffer: core::fmt::Write {
+    /// Get the current length of the path in bytes.
+    fn len(&self) -> usize;
+
+    /// Check if the path is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Shorten the path to the provided length in bytes.
+    fn truncate(&mut self, len: usize);
+}
+
+impl<const N: usize> TopicBuffer for String<N> {
+    fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        String::truncate(self, len)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TopicBuffer for alloc::string::String {
+    fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        alloc::string::String::truncate(self, len)
+    }
+}
+
+/// A topic buffer that only tracks the length of the path written into it.
+#[derive(Default)]
+pub(crate) struct PathLength(pub(crate) usize);
+
+impl core::fmt::Write for PathLength {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+impl TopicBuffer for PathLength {
+    fn len(&self) -> usize {
+        self.0
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.0 = self.0.min(len);
+    }
+}
+
 pub struct MiniconfIter<'a, Settings: Miniconf + ?Sized, const TS: usize> {
     pub(crate) settings: &'a Settings,
     pub(crate) state: &'a mut [usize],
@@ -14,7 +70,7 @@ impl<'a, Settings: Miniconf + ?Sized, const TS: usize> Iterator for MiniconfIter
 
         if self
             .settings
-            .recurse_paths(&mut self.state, &mut topic_buffer)
+            .recurse_paths(self.state, &mut topic_buffer)
             .is_some()
         {
             Some(topic_buffer)
@@ -23,3 +79,72 @@ impl<'a, Settings: Miniconf + ?Sized, const TS: usize> Iterator for MiniconfIter
         }
     }
 }
+
+/// An iterator over settings paths that owns its iteration state.
+///
+/// # Note
+/// The settings are only borrowed for each call to [PathIterator::next], so the iterator can be
+/// stored independently of the settings and interleaved with other settings accesses.
+///
+/// # Template Arguments
+/// * `TS` - The maximum number of bytes to encode a settings path into.
+/// * `DEPTH` - The maximum recursive depth of the settings.
+pub struct PathIterator<const TS: usize, const DEPTH: usize> {
+    state: [usize; DEPTH],
+}
+
+impl<const TS: usize, const DEPTH: usize> PathIterator<TS, DEPTH> {
+    /// Create an iterator over the paths of the provided settings.
+    ///
+    /// # Args
+    /// * `settings` - The settings to iterate over. Used to check the template arguments.
+    pub fn new<Settings: Miniconf + ?Sized>(settings: &Settings) -> Result<Self, IterError> {
+        let metadata = settings.get_metadata();
+
+        if TS < metadata.max_topic_size {
+            return Err(IterError::InsufficientTopicLength);
+        }
+
+        if DEPTH < metadata.max_depth {
+            return Err(IterError::InsufficientStateDepth {
+                needed: metadata.max_depth,
+            });
+        }
+
+        Ok(Self { state: [0; DEPTH] })
+    }
+
+    /// Get the next settings path.
+    ///
+    /// # Args
+    /// * `settings` - The settings to iterate over. Must be the same settings in every call.
+    ///
+    /// # Returns
+    /// The next path, or None if all paths were iterated.
+    pub fn next<Settings: Miniconf + ?Sized>(&mut self, settings: &Settings) -> Option<String<TS>> {
+        let mut topic_buffer: String<TS> = String::new();
+
+        settings
+            .recurse_paths(&mut self.state, &mut topic_buffer)
+            .map(|_| topic_buffer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub struct MiniconfAllocIter<'a, Settings: Miniconf + ?Sized> {
+    pub(crate) settings: &'a Settings,
+    pub(crate) state: alloc::vec::Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Settings: Miniconf + ?Sized> Iterator for MiniconfAllocIter<'a, Settings> {
+    type Item = alloc::string::String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut topic_buffer = alloc::string::String::new();
+
+        self.settings
+            .recurse_paths(&mut self.state, &mut topic_buffer)
+            .map(|_| topic_buffer)
+    }
+}
@@ -0,0 +1,543 @@
+//! Serialization and application of settings as nested JSON objects.
+//!
+//! # Note
+//! Every member of the object is mapped to the settings path formed by joining the keys leading
+//! to it. Objects and arrays are applied member by member if the settings have members at their
+//! path, with array elements addressed by index. Otherwise, they are applied as a whole.
+//! Serialized objects address array elements by index keys, unless they are deserialized into a
+//! serde type, which expects JSON arrays.
+use super::Error;
+#[cfg(not(feature = "set-only"))]
+use super::Miniconf;
+#[cfg(not(feature = "set-only"))]
+use heapless::String;
+
+/// Errors that occur when applying a JSON object to settings.
+#[cfg(not(feature = "set-only"))]
+#[derive(Debug, PartialEq)]
+pub enum ApplyError {
+    /// The data is not a valid JSON object.
+    Malformed,
+
+    /// A value in the object could not be applied to the settings.
+    Apply {
+        /// The path that failed to apply.
+        path: String<128>,
+
+        /// The reason that the path failed to apply.
+        error: Error,
+    },
+}
+
+/// Reasons for failing to apply a JSON object.
+///
+/// # Note
+/// On failure to apply a value, the path buffer is left at the failing path.
+enum Failure {
+    Malformed,
+    #[cfg(not(feature = "set-only"))]
+    Apply(Error),
+}
+
+/// Advance past any whitespace.
+fn skip_whitespace(data: &[u8], pos: &mut usize) {
+    while matches!(data.get(*pos), Some(b' ' | b'\t' | b'\r' | b'\n')) {
+        *pos += 1;
+    }
+}
+
+/// Consume an expected byte.
+fn expect(data: &[u8], pos: &mut usize, byte: u8) -> Result<(), Failure> {
+    if data.get(*pos) != Some(&byte) {
+        return Err(Failure::Malformed);
+    }
+
+    *pos += 1;
+    Ok(())
+}
+
+/// Consume a string.
+///
+/// # Returns
+/// The raw contents of the string without the surrounding quotes.
+fn parse_string<'a>(data: &'a [u8], pos: &mut usize) -> Result<&'a str, Failure> {
+    expect(data, pos, b'"')?;
+    let start = *pos;
+
+    loop {
+        match data.get(*pos) {
+            Some(b'"') => break,
+            Some(b'\\') => *pos += 2,
+            Some(_) => *pos += 1,
+            None => return Err(Failure::Malformed),
+        }
+    }
+
+    let contents = data.get(start..*pos).ok_or(Failure::Malformed)?;
+    *pos += 1;
+    core::str::from_utf8(contents).map_err(|_| Failure::Malformed)
+}
+
+/// Consume a complete value of any type.
+fn skip_value(data: &[u8], pos: &mut usize) -> Result<(), Failure> {
+    match data.get(*pos) {
+        Some(b'"') => parse_string(data, pos).map(|_| ()),
+        Some(b'{' | b'[') => {
+            let mut depth = 0usize;
+            loop {
+                match data.get(*pos) {
+                    Some(b'"') => {
+                        parse_string(data, pos)?;
+                        continue;
+                    }
+                    Some(b'{' | b'[') => depth += 1,
+                    Some(b'}' | b']') => {
+                        depth -= 1;
+                        if depth == 0 {
+                            *pos += 1;
+                            return Ok(());
+                        }
+                    }
+                    Some(_) => {}
+                    None => return Err(Failure::Malformed),
+                }
+
+                *pos += 1;
+            }
+        }
+        _ => {
+            let start = *pos;
+            while !matches!(
+                data.get(*pos),
+                None | Some(b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n')
+            ) {
+                *pos += 1;
+            }
+
+            if *pos == start {
+                return Err(Failure::Malformed);
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Append a path segment, applying the provided closure with the extended path.
+#[cfg(not(feature = "set-only"))]
+fn with_segment(
+    path: &mut String<128>,
+    segment: impl core::fmt::Display,
+    f: impl FnOnce(&mut String<128>) -> Result<(), Failure>,
+) -> Result<(), Failure> {
+    use core::fmt::Write;
+
+    let len = path.len();
+    let separator = if len > 0 { "/" } else { "" };
+    if write!(path, "{}{}", separator, segment).is_err() {
+        path.truncate(len);
+        return Err(Failure::Apply(Error::PathTooLong));
+    }
+
+    f(path)?;
+    path.truncate(len);
+    Ok(())
+}
+
+/// Apply the members of an object or array individually.
+#[cfg(not(feature = "set-only"))]
+fn apply_members<Settings: Miniconf + ?Sized>(
+    settings: &mut Settings,
+    data: &[u8],
+    pos: &mut usize,
+    path: &mut String<128>,
+) -> Result<(), Failure> {
+    let is_object = data.get(*pos) == Some(&b'{');
+    let end = if is_object { b'}' } else { b']' };
+    *pos += 1;
+
+    skip_whitespace(data, pos);
+    if data.get(*pos) == Some(&end) {
+        *pos += 1;
+        return Ok(());
+    }
+
+    let mut index = 0usize;
+    loop {
+        skip_whitespace(data, pos);
+        if is_object {
+            let key = parse_string(data, pos)?;
+            skip_whitespace(data, pos);
+            expect(data, pos, b':')?;
+            skip_whitespace(data, pos);
+            with_segment(path, key, |path| apply_value(settings, data, pos, path))?;
+        } else {
+            with_segment(path, index, |path| apply_value(settings, data, pos, path))?;
+            index += 1;
+        }
+
+        skip_whitespace(data, pos);
+        match data.get(*pos) {
+            Some(b',') => *pos += 1,
+            Some(&byte) if byte == end => {
+                *pos += 1;
+                return Ok(());
+            }
+            _ => return Err(Failure::Malformed),
+        }
+    }
+}
+
+/// Apply a single value at the provided path.
+#[cfg(not(feature = "set-only"))]
+fn apply_value<Settings: Miniconf + ?Sized>(
+    settings: &mut Settings,
+    data: &[u8],
+    pos: &mut usize,
+    path: &mut String<128>,
+) -> Result<(), Failure> {
+    // Nodes with members are applied member by member, so failures are reported at the path of
+    // the failing member.
+    let composite = matches!(data.get(*pos), Some(b'{' | b'['))
+        && matches!(settings.subtree_metadata(path), Ok(metadata) if metadata.max_topic_size > 0);
+
+    if composite {
+        return apply_members(settings, data, pos, path);
+    }
+
+    let start = *pos;
+    skip_value(data, pos)?;
+    settings
+        .set(path, &data[start..*pos])
+        .map_err(Failure::Apply)
+}
+
+#[cfg(not(feature = "set-only"))]
+fn apply_object<Settings: Miniconf + ?Sized>(
+    settings: &mut Settings,
+    data: &[u8],
+    path: &mut String<128>,
+) -> Result<(), Failure> {
+    let mut pos = 0;
+
+    skip_whitespace(data, &mut pos);
+    if data.get(pos) != Some(&b'{') {
+        return Err(Failure::Malformed);
+    }
+
+    apply_members(settings, data, &mut pos, path)?;
+
+    skip_whitespace(data, &mut pos);
+    if pos != data.len() {
+        return Err(Failure::Malformed);
+    }
+
+    Ok(())
+}
+
+// Note: The error reports the failing path by value, so its size is intentional.
+#[allow(clippy::result_large_err)]
+#[cfg(not(feature = "set-only"))]
+pub(crate) fn apply<Settings: Miniconf + ?Sized>(
+    settings: &mut Settings,
+    data: &[u8],
+) -> Result<(), ApplyError> {
+    let mut path = String::new();
+
+    apply_object(settings, data, &mut path).map_err(|failure| match failure {
+        Failure::Malformed => ApplyError::Malformed,
+        Failure::Apply(error) => ApplyError::Apply { path, error },
+    })
+}
+
+/// Apply a JSON object to the members of a settings node.
+///
+/// # Note
+/// Members are applied in order, so members preceding a member that fails to apply remain
+/// updated.
+///
+/// # Returns
+/// [Error::ExpectedLeaf] if the value is not an object. Otherwise, the first error of any member.
+#[doc(hidden)]
+#[cfg(not(feature = "set-only"))]
+pub fn set_members<Settings: Miniconf + ?Sized>(
+    settings: &mut Settings,
+    value: &[u8],
+) -> Result<(), Error> {
+    let mut pos = 0;
+    skip_whitespace(value, &mut pos);
+    if value.get(pos) != Some(&b'{') {
+        return Err(Error::ExpectedLeaf);
+    }
+
+    let mut path = String::new();
+    apply_object(settings, value, &mut path).map_err(|failure| match failure {
+        Failure::Malformed => Error::Deserialization(serde_json_core::de::Error::CustomError),
+        Failure::Apply(error) => error,
+    })
+}
+
+/// Find the first key of an object that is not one of the provided field names.
+fn find_unknown_field<'a>(data: &'a [u8], fields: &[&str]) -> Result<Option<&'a str>, Failure> {
+    let mut pos = 0;
+
+    skip_whitespace(data, &mut pos);
+    expect(data, &mut pos, b'{')?;
+    skip_whitespace(data, &mut pos);
+    if data.get(pos) == Some(&b'}') {
+        return Ok(None);
+    }
+
+    loop {
+        skip_whitespace(data, &mut pos);
+        let key = parse_string(data, &mut pos)?;
+        if !fields.contains(&key) {
+            return Ok(Some(key));
+        }
+
+        skip_whitespace(data, &mut pos);
+        expect(data, &mut pos, b':')?;
+        skip_whitespace(data, &mut pos);
+        skip_value(data, &mut pos)?;
+        skip_whitespace(data, &mut pos);
+
+        match data.get(pos) {
+            Some(b',') => pos += 1,
+            _ => return Ok(None),
+        }
+    }
+}
+
+/// Reject objects containing keys other than the provided field names.
+///
+/// # Note
+/// Values that are not valid objects are left to the deserializer to reject.
+#[doc(hidden)]
+pub fn deny_unknown_fields(value: &[u8], fields: &[&str]) -> Result<(), Error> {
+    match find_unknown_field(value, fields) {
+        Ok(Some(_)) => Err(Error::UnknownField),
+        _ => Ok(()),
+    }
+}
+
+/// Patch the fields of an atomic struct with the members of a partial JSON object.
+///
+/// # Note
+/// Keys that are not fields of the struct are rejected, like values of structs denying unknown
+/// fields.
+///
+/// # Args
+/// * `value` - The partial JSON object.
+/// * `patch` - A closure deserializing the value of the named field, returning false if there is
+///   no such field.
+#[doc(hidden)]
+pub fn patch_members(
+    value: &[u8],
+    mut patch: impl FnMut(&str, &[u8]) -> Result<bool, Error>,
+) -> Result<(), Error> {
+    let malformed = |_| Error::Deserialization(serde_json_core::de::Error::CustomError);
+    let mut pos = 0;
+
+    skip_whitespace(value, &mut pos);
+    expect(value, &mut pos, b'{').map_err(malformed)?;
+    skip_whitespace(value, &mut pos);
+    if value.get(pos) == Some(&b'}') {
+        return Ok(());
+    }
+
+    loop {
+        skip_whitespace(value, &mut pos);
+        let key = parse_string(value, &mut pos).map_err(malformed)?;
+        skip_whitespace(value, &mut pos);
+        expect(value, &mut pos, b':').map_err(malformed)?;
+        skip_whitespace(value, &mut pos);
+
+        let start = pos;
+        skip_value(value, &mut pos).map_err(malformed)?;
+        if !patch(key, &value[start..pos])? {
+            return Err(Error::UnknownField);
+        }
+
+        skip_whitespace(value, &mut pos);
+        match value.get(pos) {
+            Some(b',') => pos += 1,
+            Some(b'}') => return Ok(()),
+            _ => return Err(malformed(Failure::Malformed)),
+        }
+    }
+}
+
+/// Append raw data to the serialized object.
+#[cfg(not(feature = "set-only"))]
+fn write(data: &mut [u8], len: &mut usize, value: &[u8]) -> Result<(), Error> {
+    data.get_mut(*len..*len + value.len())
+        .ok_or(Error::SerializationFailed)?
+        .copy_from_slice(value);
+    *len += value.len();
+    Ok(())
+}
+
+/// Append an object key.
+#[cfg(not(feature = "set-only"))]
+fn write_key(data: &mut [u8], len: &mut usize, key: &str) -> Result<(), Error> {
+    write(data, len, b"\"")?;
+    write(data, len, key.as_bytes())?;
+    write(data, len, b"\":")
+}
+
+/// Append the opening bracket of an object or array.
+#[cfg(not(feature = "set-only"))]
+fn open(data: &mut [u8], len: &mut usize, array: bool) -> Result<(), Error> {
+    write(data, len, if array { b"[" } else { b"{" })
+}
+
+/// Append the closing bracket of an object or array.
+#[cfg(not(feature = "set-only"))]
+fn close(data: &mut [u8], len: &mut usize, array: bool) -> Result<(), Error> {
+    write(data, len, if array { b"]" } else { b"}" })
+}
+
+/// Serialize settings as a nested JSON object.
+///
+/// # Args
+/// * `settings` - The settings to serialize.
+/// * `paths` - The paths of the settings in iteration order.
+/// * `data` - The location to serialize the object into.
+/// * `arrays` - Specifies whether members keyed by indices are serialized as JSON arrays instead
+///   of objects keyed by the index.
+///
+/// # Returns
+/// The number of bytes used in the `data` buffer for the object.
+#[cfg(not(feature = "set-only"))]
+pub(crate) fn serialize<Settings: Miniconf + ?Sized, P: AsRef<str>>(
+    settings: &Settings,
+    paths: impl Iterator<Item = P>,
+    data: &mut [u8],
+    arrays: bool,
+) -> Result<usize, Error> {
+    let mut len = 0;
+    let mut previous: Option<P> = None;
+
+    // All members of a container are keyed alike, so a container is an array if the key of any
+    // of its members is an index.
+    let is_index = |key: &str| arrays && !key.is_empty() && key.bytes().all(|b| b.is_ascii_digit());
+
+    for path in paths {
+        let current = path.as_ref();
+
+        // Paths are iterated depth-first, so the object of the previous path only needs to be
+        // closed down to the node shared with the current path.
+        let mut common = 0;
+        match &previous {
+            None => {
+                // Note(unwrap): Split always yields at least one segment.
+                open(data, &mut len, is_index(current.split('/').next().unwrap()))?;
+            }
+            Some(previous) => {
+                let previous = previous.as_ref();
+                common = current
+                    .split('/')
+                    .zip(previous.split('/'))
+                    .take_while(|(current, previous)| current == previous)
+                    .count();
+
+                let depth = previous.split('/').count();
+                for key in previous.rsplit('/').take(depth.saturating_sub(common + 1)) {
+                    close(data, &mut len, is_index(key))?;
+                }
+
+                write(data, &mut len, b",")?;
+            }
+        }
+
+        let mut segments = current.split('/').skip(common).peekable();
+        while let Some(key) = segments.next() {
+            if !is_index(key) {
+                write_key(data, &mut len, key)?;
+            }
+
+            if let Some(member) = segments.peek() {
+                open(data, &mut len, is_index(member))?;
+            }
+        }
+
+        let value = data.get_mut(len..).ok_or(Error::SerializationFailed)?;
+        len += settings.get(current, value)?;
+        previous = Some(path);
+    }
+
+    match previous {
+        None => write(data, &mut len, b"{}")?,
+        Some(previous) => {
+            for key in previous.as_ref().rsplit('/') {
+                close(data, &mut len, is_index(key))?;
+            }
+        }
+    }
+
+    Ok(len)
+}
+
+/// The maximum size of values that cannot be measured without serializing them.
+#[cfg(all(feature = "alloc", not(feature = "set-only")))]
+const MAX_SCRATCH_SIZE: usize = 1 << 16;
+
+/// Determine the size of the serialized value of a setting.
+///
+/// # Note
+/// Values serialized by a module cannot be measured, so they are serialized into the scratch
+/// buffer instead, which grows as needed.
+///
+/// # Args
+/// * `settings` - The settings containing the value.
+/// * `path` - The path of the setting.
+/// * `scratch` - The buffer to serialize values into that cannot be measured.
+///
+/// # Returns
+/// The number of bytes that the serialized value occupies.
+#[cfg(all(feature = "alloc", not(feature = "set-only")))]
+fn value_len<Settings: Miniconf + ?Sized>(
+    settings: &Settings,
+    path: &str,
+    scratch: &mut alloc::vec::Vec<u8>,
+) -> Result<usize, Error> {
+    if let Ok(len) = settings.value_len(path) {
+        return Ok(len);
+    }
+
+    loop {
+        match settings.get(path, scratch) {
+            Err(Error::SerializationFailed) if scratch.len() < MAX_SCRATCH_SIZE => {
+                scratch.resize((2 * scratch.len()).max(64), 0);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Deserialize settings into an equivalent serde type.
+///
+/// # Args
+/// * `settings` - The settings to deserialize.
+///
+/// # Returns
+/// The deserialized value.
+#[cfg(all(feature = "alloc", not(feature = "set-only")))]
+pub(crate) fn deserialize_into<T, Settings>(settings: &Settings) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+    Settings: Miniconf + ?Sized,
+{
+    // Every key and bracket of the object belongs to a segment of a path. Each segment requires
+    // at most its key with quotes, a colon, a comma and a pair of brackets.
+    let mut size = 2;
+    let mut scratch = alloc::vec::Vec::new();
+    for path in settings.iter_paths() {
+        size +=
+            path.len() + 6 * path.split('/').count() + value_len(settings, &path, &mut scratch)?;
+    }
+
+    let mut data = alloc::vec![0; size];
+    let len = serialize(settings, settings.iter_paths(), &mut data, true)?;
+    Ok(serde_json_core::from_slice(&data[..len])?.0)
+}
@@ -0,0 +1,96 @@
+/// Typed access to the value of a settings leaf.
+///
+/// # Note
+/// Leaves of the types `bool`, `u8`, `u16`, `u32`, `u64`, `usize`, `i8`, `i16`, `i32`, `i64`,
+/// `f32`, `f64` and [crate::LenientBool] are reachable through [crate::Miniconf::map_leaves].
+/// Each of them provides access to its value through the accessor of its type, while all other
+/// accessors return None. Leaves of other types, e.g. strings, enums, atomic structs or fields
+/// using `#[miniconf(with = "...")]`, are not reachable.
+pub trait LeafValue {
+    /// Access the value of a `bool` leaf.
+    fn as_bool_mut(&mut self) -> Option<&mut bool> {
+        None
+    }
+
+    /// Access the value of a `u8` leaf.
+    fn as_u8_mut(&mut self) -> Option<&mut u8> {
+        None
+    }
+
+    /// Access the value of a `u16` leaf.
+    fn as_u16_mut(&mut self) -> Option<&mut u16> {
+        None
+    }
+
+    /// Access the value of a `u32` leaf.
+    fn as_u32_mut(&mut self) -> Option<&mut u32> {
+        None
+    }
+
+    /// Access the value of a `u64` leaf.
+    fn as_u64_mut(&mut self) -> Option<&mut u64> {
+        None
+    }
+
+    /// Access the value of a `usize` leaf.
+    fn as_usize_mut(&mut self) -> Option<&mut usize> {
+        None
+    }
+
+    /// Access the value of an `i8` leaf.
+    fn as_i8_mut(&mut self) -> Option<&mut i8> {
+        None
+    }
+
+    /// Access the value of an `i16` leaf.
+    fn as_i16_mut(&mut self) -> Option<&mut i16> {
+        None
+    }
+
+    /// Access the value of an `i32` leaf.
+    fn as_i32_mut(&mut self) -> Option<&mut i32> {
+        None
+    }
+
+    /// Access the value of an `i64` leaf.
+    fn as_i64_mut(&mut self) -> Option<&mut i64> {
+        None
+    }
+
+    /// Access the value of an `f32` leaf.
+    fn as_f32_mut(&mut self) -> Option<&mut f32> {
+        None
+    }
+
+    /// Access the value of an `f64` leaf.
+    fn as_f64_mut(&mut self) -> Option<&mut f64> {
+        None
+    }
+}
+
+macro_rules! impl_leaf_value {
+    ($($ty:ty: $accessor:ident),+) => {
+        $(
+        impl LeafValue for $ty {
+            fn $accessor(&mut self) -> Option<&mut $ty> {
+                Some(self)
+            }
+        }
+        )+
+    };
+}
+
+impl_leaf_value!(
+    bool: as_bool_mut,
+    u8: as_u8_mut,
+    u16: as_u16_mut,
+    u32: as_u32_mut,
+    u64: as_u64_mut,
+    usize: as_usize_mut,
+    i8: as_i8_mut,
+    i16: as_i16_mut,
+    i32: as_i32_mut,
+    i64: as_i64_mut,
+    f32: as_f32_mut,
+    f64: as_f64_mut
+);
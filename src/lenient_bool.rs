@@ -0,0 +1,104 @@
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+use super::{Error, LeafValue, Miniconf};
+
+// The spellings accepted for true and false values, compared case-insensitively.
+const TRUE: [&str; 4] = ["true", "1", "on", "yes"];
+const FALSE: [&str; 4] = ["false", "0", "off", "no"];
+
+/// A boolean setting accepting common human spellings.
+///
+/// # Note
+/// In addition to `true` and `false`, the values `1`/`0`, `on`/`off` and `yes`/`no` are accepted
+/// case-insensitively, optionally as JSON strings. The value is always retrieved as `true` or
+/// `false`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LenientBool(pub bool);
+
+impl From<bool> for LenientBool {
+    fn from(value: bool) -> Self {
+        Self(value)
+    }
+}
+
+impl From<LenientBool> for bool {
+    fn from(value: LenientBool) -> Self {
+        value.0
+    }
+}
+
+impl core::ops::Deref for LenientBool {
+    type Target = bool;
+
+    fn deref(&self) -> &bool {
+        &self.0
+    }
+}
+
+impl Miniconf for LenientBool {
+    fn string_set(
+        &mut self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        if topic_parts.peek().is_some() {
+            return Err(Error::PathTooLong);
+        }
+
+        let invalid = || Error::Deserialization(serde_json_core::de::Error::InvalidType);
+        let token = core::str::from_utf8(value).map_err(|_| invalid())?.trim();
+        let token = token
+            .strip_prefix('"')
+            .and_then(|token| token.strip_suffix('"'))
+            .unwrap_or(token);
+
+        let matches = |spellings: &[&str]| {
+            spellings
+                .iter()
+                .any(|spelling| spelling.eq_ignore_ascii_case(token))
+        };
+
+        if matches(&TRUE) {
+            self.0 = true;
+        } else if matches(&FALSE) {
+            self.0 = false;
+        } else {
+            return Err(invalid());
+        }
+
+        Ok(())
+    }
+
+    fn string_get(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &mut [u8],
+    ) -> Result<usize, Error> {
+        self.0.string_get(topic_parts, value)
+    }
+
+    fn string_value_len(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        self.0.string_value_len(topic_parts)
+    }
+
+    fn string_leaf_value(
+        &mut self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        // The value is accessed as the underlying `bool`.
+        self.0.string_leaf_value(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn get_metadata(&self) -> MiniconfMetadata {
+        self.0.get_metadata()
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl TopicBuffer) -> Option<()> {
+        self.0.recurse_paths(index, topic)
+    }
+}
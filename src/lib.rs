@@ -52,6 +52,24 @@
 //! Miniconf supports an MQTT-based client for configuring and managing run-time settings via MQTT.
 //! To enable this feature, enable the `mqtt-client` feature.
 //!
+//! With the `fixed` feature, the fixed-point types of the [fixed](https://docs.rs/fixed) crate
+//! (e.g. `I16F16`) can be used as settings. They are transmitted as decimal JSON numbers rather
+//! than as their raw integer representation.
+//!
+//! With the `std` feature, settings shared behind a `std::sync::Mutex` can be accessed through the
+//! mutex, which is locked for the duration of each operation.
+//!
+//! ### Set-only deployments
+//!
+//! On targets where code size is critical and settings are only ever accessed by known paths,
+//! the `set-only` feature compiles out path iteration and metadata, including all functionality
+//! built on them (e.g. settings comparison or JSON objects). Only setting and retrieving
+//! individual paths remains available. The feature cannot be combined with the `mqtt-client`,
+//! `channel` or `backup` features, which require path iteration:
+//! ```sh
+//! cargo build --no-default-features --features set-only
+//! ```
+//!
 //! ### Path iteration
 //!
 //! Miniconf also allows iteration over all settings paths:
@@ -79,22 +97,114 @@
 //!
 //! While Miniconf is platform agnostic, there is an [MQTT-based client](MqttClient) provided to
 //! manage settings via the [MQTT protocol](https://mqtt.org).
+//! With the `channel` feature, settings can alternatively be managed using line-based requests
+//! over any byte channel (e.g. a UART) with `SettingsChannel`.
 //!
 //! ## Limitations
 //!
 //! Minconf cannot be used with some of Rust's more complex types. Some unsupported types:
-//! * Complex enums
-//! * Tuples
+//! * Enums with struct variants or variants containing multiple values
+//! * Tuples of more than twelve elements. Elements of smaller tuples are addressed by their index,
+//!   e.g. `0/gain`.
+//!
+//! All fields of a derived struct must implement [Miniconf], e.g. by deriving it, unless they are
+//! accessed using `#[miniconf(with = "...")]`. Compilation otherwise fails with an error pointing
+//! at the offending field:
+//! ```compile_fail
+//! use miniconf::Miniconf;
+//!
+//! struct Calibration(f32);
+//!
+//! #[derive(Miniconf)]
+//! struct Settings {
+//!     // error: `Calibration` does not implement `Miniconf`
+//!     calibration: Calibration,
+//! }
+//! ```
+
+#[cfg(all(
+    feature = "set-only",
+    any(feature = "mqtt-client", feature = "channel", feature = "backup")
+))]
+compile_error!("The `set-only` feature cannot be combined with features requiring path iteration");
 
 #[cfg(feature = "mqtt-client")]
 mod mqtt_client;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 mod array;
+#[cfg(feature = "backup")]
+mod backup;
+mod borrowed;
+#[cfg(feature = "alloc")]
+mod boxed;
+#[cfg(feature = "channel")]
+mod channel;
+mod counter;
+#[cfg(not(feature = "set-only"))]
+mod defaults;
+#[cfg(not(feature = "set-only"))]
+mod diff;
+mod domains;
+#[cfg(feature = "fixed")]
+mod fixed_point;
+#[cfg(not(feature = "set-only"))]
 pub mod iter;
+mod json;
+mod leaf_value;
+mod lenient_bool;
+#[cfg(not(feature = "set-only"))]
+mod lines;
+#[cfg(feature = "std")]
+mod mutex;
+mod named_array;
 mod option;
+mod optional;
+mod select;
+mod tuple;
+#[cfg(not(feature = "set-only"))]
+mod visit;
+
+#[cfg(not(feature = "set-only"))]
+pub use defaults::default_paths;
+#[cfg(not(feature = "set-only"))]
+pub use diff::diff;
+pub use domains::{Domains, DomainsError};
+#[cfg(not(feature = "set-only"))]
+pub use json::ApplyError;
+pub use leaf_value::LeafValue;
+pub use lenient_bool::LenientBool;
+pub use named_array::NamedArray;
+pub use optional::Optional;
+#[cfg(not(feature = "set-only"))]
+pub use visit::{LeafMut, LeafRef};
+
+#[cfg(feature = "backup")]
+pub use backup::BackupError;
+
+#[cfg(feature = "channel")]
+pub use channel::SettingsChannel;
+
+#[cfg(feature = "channel")]
+pub use embedded_io;
 
 #[cfg(feature = "mqtt-client")]
-pub use mqtt_client::MqttClient;
+pub use mqtt_client::{
+    ActivationError, CollectError, ConstructionError, Format, MqttClient, MqttErrorKind, MqttStats,
+    PauseBehavior, PollOutcome, TopicMapper,
+};
+
+#[cfg(all(feature = "mqtt-client", feature = "alloc"))]
+pub use mqtt_client::SettingsReader;
+
+#[cfg(feature = "mqtt-client")]
+#[doc(hidden)]
+pub use mqtt_client::{assert_buffer_sizes, BufferSizeOptions};
 
 #[cfg(feature = "mqtt-client")]
 pub use minimq;
@@ -102,6 +212,22 @@ pub use minimq;
 #[cfg(feature = "mqtt-client")]
 pub use minimq::embedded_time;
 
+#[doc(hidden)]
+pub use log;
+
+#[doc(hidden)]
+pub use json::{deny_unknown_fields, patch_members};
+
+#[doc(hidden)]
+pub use counter::json_len;
+
+#[doc(hidden)]
+pub use select::{get_member, member_len};
+
+#[cfg(not(feature = "set-only"))]
+#[doc(hidden)]
+pub use json::set_members;
+
 #[doc(hidden)]
 pub use serde::{
     de::{Deserialize, DeserializeOwned},
@@ -152,13 +278,68 @@ pub enum Error {
     ///
     /// Check array indices to ensure that bounds for all paths are respected.
     BadIndex,
+
+    /// The provided path refers to a non-atomic structure or array, which cannot be updated as
+    /// a whole.
+    ///
+    /// Extend the path to refer to the individual members of the node.
+    ExpectedLeaf,
+
+    /// The provided path refers to the data of an enum variant that is not currently active.
+    ///
+    /// Set the variant before retrieving its data.
+    InactiveVariant,
+
+    /// The value provided for configuration lies outside of the inclusive bounds of the setting.
+    ///
+    /// Check that the value lies within the reported bounds.
+    OutOfRange {
+        /// The lower bound of the setting, if any.
+        min: Option<&'static str>,
+
+        /// The upper bound of the setting, if any.
+        max: Option<&'static str>,
+    },
+
+    /// The value provided for configuration is NaN or infinite, but the setting must be finite.
+    ///
+    /// Provide a finite value.
+    NotFinite,
+
+    /// The value provided for configuration is not one of the values allowed for the setting.
+    ///
+    /// Provide one of the allowed values.
+    NotAllowed {
+        /// The values allowed for the setting.
+        allowed: &'static [&'static str],
+    },
+
+    /// The setting is not an enumeration.
+    ///
+    /// Only the variants of settings deriving `Miniconf` for an enum can be listed.
+    NotAnEnum,
+
+    /// The lock of the settings was poisoned by a thread panicking while holding it.
+    ///
+    /// The settings may be inconsistent. Recover the lock of the settings before accessing them.
+    Poisoned,
+
+    /// The value provided for configuration of a struct contains a field that the struct does not
+    /// have.
+    ///
+    /// Check the names of the fields, which are those used by serde, e.g. after renaming.
+    UnknownField,
 }
 
 /// Errors that occur during iteration over topic paths.
-#[derive(Debug)]
+#[cfg(not(feature = "set-only"))]
+#[derive(Debug, PartialEq)]
 pub enum IterError {
     /// The provided state vector is not long enough.
-    InsufficientStateDepth,
+    InsufficientStateDepth {
+        /// The length of the state vector required by the settings.
+        needed: usize,
+    },
 
     /// The provided topic length is not long enough.
     InsufficientTopicLength,
@@ -174,10 +355,27 @@ impl From<Error> for u8 {
             Error::Deserialization(_) => 5,
             Error::BadIndex => 6,
             Error::SerializationFailed => 7,
+            Error::ExpectedLeaf => 8,
+            Error::InactiveVariant => 9,
+            Error::OutOfRange { .. } => 10,
+            Error::NotFinite => 11,
+            Error::NotAllowed { .. } => 12,
+            Error::NotAnEnum => 13,
+            Error::Poisoned => 14,
+            Error::UnknownField => 15,
         }
     }
 }
 
+#[cfg(not(feature = "set-only"))]
+impl From<IterError> for Error {
+    // Note: Iteration only fails if the paths of the settings exceed the supported length or
+    // depth.
+    fn from(_err: IterError) -> Error {
+        Error::PathTooLong
+    }
+}
+
 impl From<serde_json_core::de::Error> for Error {
     fn from(err: serde_json_core::de::Error) -> Error {
         Error::Deserialization(err)
@@ -192,8 +390,52 @@ pub struct MiniconfMetadata {
 
     /// The maximum recursive depth of the structure.
     pub max_depth: usize,
+
+    /// The number of settings paths in the structure.
+    pub path_count: usize,
+}
+
+/// Descriptive attributes of a settings path.
+///
+/// # Note
+/// Attributes are specified on struct fields using `#[miniconf(unit = "...", description = "...")]`.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Attributes {
+    /// The unit of the setting.
+    pub unit: Option<&'static str>,
+
+    /// A description of the setting.
+    pub description: Option<&'static str>,
+}
+
+/// The effect of a settings update on the stored value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    /// The stored value differs from the previous value.
+    Changed,
+
+    /// The stored value is identical to the previous value.
+    Unchanged,
 }
 
+/// Split a settings path into its segments.
+///
+/// # Note
+/// An empty path addresses the settings as a whole, e.g. a top-level enum, and has no segments.
+fn split_path(path: &str) -> core::iter::Peekable<core::str::Split<'_, char>> {
+    let mut parts = path.split('/').peekable();
+    if path.is_empty() {
+        parts.next();
+    }
+
+    parts
+}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not implement `Miniconf`",
+    label = "field type must implement `Miniconf`",
+    note = "derive `Miniconf` or `MiniconfAtomic` for `{Self}`, or use `#[miniconf(with = \"...\")]` on the field"
+)]
 pub trait Miniconf {
     /// Update settings directly from a string path and data.
     ///
@@ -204,7 +446,66 @@ pub trait Miniconf {
     /// # Returns
     /// The result of the configuration operation.
     fn set(&mut self, path: &str, data: &[u8]) -> Result<(), Error> {
-        self.string_set(path.split('/').peekable(), data)
+        self.string_set(split_path(path), data)
+    }
+
+    /// Update a setting and report whether its value changed.
+    ///
+    /// # Note
+    /// The values before and after the update are compared in their serialized form. Both values
+    /// are serialized into the `scratch` buffer. If they do not fit, the update is not applied and
+    /// fails with [Error::SerializationFailed].
+    ///
+    /// # Args
+    /// * `path` - The path to update within `settings`.
+    /// * `data` - The serialized data making up the contents of the configured value.
+    /// * `scratch` - The location to serialize the previous and updated values into.
+    ///
+    /// # Returns
+    /// Whether the update changed the stored value.
+    fn set_tracked(
+        &mut self,
+        path: &str,
+        data: &[u8],
+        scratch: &mut [u8],
+    ) -> Result<Change, Error> {
+        let previous_len = self.get(path, scratch)?;
+        let (previous, current) = scratch.split_at_mut(previous_len);
+
+        self.set(path, data)?;
+
+        let len = match self.get(path, current) {
+            Ok(len) => len,
+            Err(err) => {
+                // The update cannot be compared, so the previous value is restored.
+                self.set(path, previous)?;
+                return Err(err);
+            }
+        };
+
+        Ok(if previous[..] == current[..len] {
+            Change::Unchanged
+        } else {
+            Change::Changed
+        })
+    }
+
+    /// Update a setting and retrieve the resulting serialized value.
+    ///
+    /// # Note
+    /// The retrieved value reflects any conversion or normalization applied when storing the
+    /// value. If the update fails, the value is not retrieved.
+    ///
+    /// # Args
+    /// * `path` - The path to update within `settings`.
+    /// * `data` - The serialized data making up the contents of the configured value.
+    /// * `out` - The location to serialize the stored value into.
+    ///
+    /// # Returns
+    /// The number of bytes used in the `out` buffer for serialization.
+    fn set_and_get(&mut self, path: &str, data: &[u8], out: &mut [u8]) -> Result<usize, Error> {
+        self.string_set(split_path(path), data)?;
+        self.string_get(split_path(path), out)
     }
 
     /// Retrieve a serialized settings value from a string path.
@@ -216,7 +517,543 @@ pub trait Miniconf {
     /// # Returns
     /// The number of bytes used in the `data` buffer for serialization.
     fn get(&self, path: &str, data: &mut [u8]) -> Result<usize, Error> {
-        self.string_get(path.split('/').peekable(), data)
+        self.string_get(split_path(path), data)
+    }
+
+    /// Determine the size of the serialized value of a setting.
+    ///
+    /// # Note
+    /// This can be used to predict the size of messages before publishing a value. The value is
+    /// only measured, so values of any size are supported. Fields serialized by a module using
+    /// `#[miniconf(with = "...")]` cannot be measured and fail with [Error::SerializationFailed].
+    ///
+    /// # Args
+    /// * `path` - The path of the setting.
+    ///
+    /// # Returns
+    /// The number of bytes that the serialized value occupies.
+    fn value_len(&self, path: &str) -> Result<usize, Error> {
+        self.string_value_len(split_path(path))
+    }
+
+    /// Retrieve all serialized settings values below a wildcard path.
+    ///
+    /// # Note
+    /// The path must end in a `*` or `#` wildcard, which matches all settings paths below the
+    /// preceding path (e.g. `adc/0/*`). A lone wildcard matches all settings. Settings with paths
+    /// longer than 128 bytes or more than 16 levels deep fail with [Error::PathTooLong].
+    ///
+    /// # Args
+    /// * `path` - The wildcard path to retrieve.
+    /// * `data` - The location to serialize the data into.
+    ///
+    /// # Returns
+    /// The number of bytes used in the `data` buffer for serialization. The values are serialized
+    /// as a JSON object keyed by the path of each setting relative to the wildcard.
+    #[cfg(not(feature = "set-only"))]
+    fn get_wildcard(&self, path: &str, data: &mut [u8]) -> Result<usize, Error> {
+        let prefix = path
+            .strip_suffix(|c| c == '*' || c == '#')
+            .filter(|prefix| prefix.is_empty() || prefix.ends_with('/'))
+            .ok_or(Error::PathNotFound)?;
+
+        let push = |data: &mut [u8], len: &mut usize, bytes: &[u8]| -> Result<(), Error> {
+            let end = *len + bytes.len();
+            data.get_mut(*len..end)
+                .ok_or(Error::SerializationFailed)?
+                .copy_from_slice(bytes);
+            *len = end;
+            Ok(())
+        };
+
+        let mut len = 0;
+        let mut found = false;
+        let mut state = [0; 16];
+        for topic in self.into_iter::<128>(&mut state)? {
+            let relative = match topic.strip_prefix(prefix) {
+                Some(relative) => relative,
+                None => continue,
+            };
+
+            push(data, &mut len, if found { b",\"" } else { b"{\"" })?;
+            push(data, &mut len, relative.as_bytes())?;
+            push(data, &mut len, b"\":")?;
+            found = true;
+
+            len += self.get(&topic, &mut data[len..])?;
+        }
+
+        if !found {
+            return Err(Error::PathNotFound);
+        }
+
+        push(data, &mut len, b"}")?;
+        Ok(len)
+    }
+
+    /// Update a setting only if its current value matches an expected value.
+    ///
+    /// # Note
+    /// The current value is compared in its serialized form, so `expected` must match the value
+    /// as returned by [Miniconf::get]. The current value is serialized into the `scratch` buffer.
+    /// If it does not fit, the update fails with [Error::SerializationFailed].
+    ///
+    /// # Args
+    /// * `path` - The path to update within `settings`.
+    /// * `expected` - The serialized value that the setting is expected to have.
+    /// * `new` - The serialized data to apply if the current value matches.
+    /// * `scratch` - The location to serialize the current value into.
+    ///
+    /// # Returns
+    /// True if the value was applied. False if the current value did not match.
+    fn set_if(
+        &mut self,
+        path: &str,
+        expected: &[u8],
+        new: &[u8],
+        scratch: &mut [u8],
+    ) -> Result<bool, Error> {
+        let len = self.get(path, scratch)?;
+
+        if &scratch[..len] != expected {
+            return Ok(false);
+        }
+
+        self.set(path, new)?;
+        Ok(true)
+    }
+
+    /// Compare two settings instances for equality.
+    ///
+    /// # Note
+    /// Leaf values are compared in their serialized form, so `PartialEq` is not required and the
+    /// semantics match the values transmitted for each path. The sizes of values are compared
+    /// before their contents where they can be measured, so only values of identical size must
+    /// fit into `VS` bytes.
+    ///
+    /// # Template Arguments
+    /// * `TS` - The maximum number of bytes to encode a settings path into.
+    /// * `VS` - The maximum number of bytes to serialize a settings value into.
+    /// * `DEPTH` - The maximum recursive depth of the settings.
+    ///
+    /// # Args
+    /// * `other` - The settings to compare against.
+    ///
+    /// # Returns
+    /// True if all settings paths are present in both instances with identical values.
+    /// [Error::PathTooLong] if the paths do not fit within `TS` and `DEPTH`, or
+    /// [Error::SerializationFailed] if a compared value does not fit into `VS` bytes.
+    #[cfg(not(feature = "set-only"))]
+    fn settings_eq<const TS: usize, const VS: usize, const DEPTH: usize>(
+        &self,
+        other: &Self,
+    ) -> Result<bool, Error> {
+        // Paths that are only present in one of the instances (e.g. an `Option` that is `None` in
+        // the other) are only detected when iterating that instance.
+        Ok(diff::contained::<Self, TS, VS, DEPTH>(self, other)?
+            && diff::contained::<Self, TS, VS, DEPTH>(other, self)?)
+    }
+
+    /// Serialize all settings into a backup.
+    ///
+    /// # Note
+    /// Paths of up to 128 bytes and 16 levels deep are supported. The backup ends with a checksum
+    /// of its contents, so that corrupted backups are detected when restoring them.
+    ///
+    /// # Args
+    /// * `data` - The location to serialize the backup into.
+    ///
+    /// # Returns
+    /// The number of bytes used in the `data` buffer for the backup, or [Error::PathTooLong] if
+    /// the settings exceed the supported paths.
+    #[cfg(feature = "backup")]
+    fn serialize_all(&self, data: &mut [u8]) -> Result<usize, Error> {
+        backup::serialize(self, data)
+    }
+
+    /// Construct settings from a backup.
+    ///
+    /// # Note
+    /// The settings are constructed from their default values, and every path in the backup is
+    /// then applied in order. The checksum of the backup is verified before any path is applied.
+    ///
+    /// # Args
+    /// * `data` - The backup generated by [Miniconf::serialize_all].
+    ///
+    /// # Returns
+    /// The restored settings, or an error naming the first path that failed to apply.
+    /// [BackupError::Corrupt] if the backup does not match its checksum.
+    #[cfg(feature = "backup")]
+    fn from_backup(data: &[u8]) -> Result<Self, BackupError<'_>>
+    where
+        Self: Default + Sized,
+    {
+        backup::restore(data)
+    }
+
+    /// Visit all settings leaves.
+    ///
+    /// # Note
+    /// Paths are generated into a buffer on the stack, so no allocation is required.
+    ///
+    /// # Template Arguments
+    /// * `TS` - The maximum number of bytes to encode a settings path into.
+    /// * `DEPTH` - The maximum recursive depth of the settings.
+    ///
+    /// # Args
+    /// * `f` - A closure called with the path of every leaf and a handle to retrieve its value.
+    ///
+    /// # Returns
+    /// An error if the template arguments are too small for the settings, in which case no leaf is
+    /// visited.
+    #[cfg(not(feature = "set-only"))]
+    fn visit_leaves<const TS: usize, const DEPTH: usize>(
+        &self,
+        mut f: impl FnMut(&str, LeafRef<'_, Self>),
+    ) -> Result<(), IterError> {
+        let mut paths = iter::PathIterator::<TS, DEPTH>::new(self)?;
+        while let Some(path) = paths.next(self) {
+            f(
+                &path,
+                LeafRef {
+                    settings: self,
+                    path: &path,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Visit all settings leaves with the ability to update them.
+    ///
+    /// # Note
+    /// Paths are generated into a buffer on the stack, so no allocation is required.
+    ///
+    /// # Template Arguments
+    /// * `TS` - The maximum number of bytes to encode a settings path into.
+    /// * `DEPTH` - The maximum recursive depth of the settings.
+    ///
+    /// # Args
+    /// * `f` - A closure called with the path of every leaf and a handle to retrieve and update
+    ///   its value.
+    ///
+    /// # Returns
+    /// An error if the template arguments are too small for the settings, in which case no leaf is
+    /// visited.
+    #[cfg(not(feature = "set-only"))]
+    fn visit_leaves_mut<const TS: usize, const DEPTH: usize>(
+        &mut self,
+        mut f: impl FnMut(&str, LeafMut<'_, Self>),
+    ) -> Result<(), IterError> {
+        let mut paths = iter::PathIterator::<TS, DEPTH>::new(self)?;
+        while let Some(path) = paths.next(self) {
+            f(
+                &path,
+                LeafMut {
+                    settings: self,
+                    path: &path,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Transform the values of all settings leaves in place.
+    ///
+    /// # Note
+    /// Values are accessed directly instead of being serialized, so transformations are lossless.
+    /// Only leaves reachable through [LeafValue] are visited. Values are not validated, e.g.
+    /// against bounds declared with `#[miniconf(min = ..., max = ...)]`.
+    ///
+    /// # Template Arguments
+    /// * `TS` - The maximum number of bytes to encode a settings path into.
+    /// * `DEPTH` - The maximum recursive depth of the settings.
+    ///
+    /// # Args
+    /// * `f` - A closure called with the path of every reachable leaf and typed access to its
+    ///   value.
+    ///
+    /// # Returns
+    /// [Error::PathTooLong] if the template arguments are too small for the settings, in which
+    /// case no leaf is visited.
+    #[cfg(not(feature = "set-only"))]
+    fn map_leaves<const TS: usize, const DEPTH: usize>(
+        &mut self,
+        mut f: impl FnMut(&str, &mut dyn LeafValue),
+    ) -> Result<(), Error> {
+        let mut paths = iter::PathIterator::<TS, DEPTH>::new(self)?;
+        while let Some(path) = paths.next(self) {
+            if let Some(leaf) = self.string_leaf_value(split_path(&path))? {
+                f(&path, leaf);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Determine the length of the longest path of the current settings.
+    ///
+    /// # Note
+    /// In contrast to [MiniconfMetadata::max_topic_size], only the paths that are present at
+    /// run-time are considered, e.g. no paths within absent `Option`s. This helps to diagnose
+    /// sizing problems of settings with varying contents. Paths are only measured, so they may
+    /// have any length.
+    ///
+    /// # Template Arguments
+    /// * `DEPTH` - The maximum recursive depth of the settings.
+    ///
+    /// # Returns
+    /// The length of the longest path in bytes, or zero if there are no paths.
+    #[cfg(not(feature = "set-only"))]
+    fn longest_path<const DEPTH: usize>(&self) -> Result<usize, IterError> {
+        let metadata = self.get_metadata();
+        if DEPTH < metadata.max_depth {
+            return Err(IterError::InsufficientStateDepth {
+                needed: metadata.max_depth,
+            });
+        }
+
+        let mut state = [0; DEPTH];
+        let mut longest = 0;
+        let mut path = iter::PathLength::default();
+        while self.recurse_paths(&mut state, &mut path).is_some() {
+            longest = longest.max(path.0);
+            path.0 = 0;
+        }
+
+        Ok(longest)
+    }
+
+    /// Compute a hash of the structure of the settings.
+    ///
+    /// # Note
+    /// The hash covers all settings paths present at run-time along with a schema version, so it
+    /// changes when settings are added, removed or renamed. Changes of the types of settings are
+    /// not detected, so the version should be incremented whenever the interpretation of a
+    /// setting changes. It can be compared to detect settings intended for a different firmware
+    /// version. Only paths and the version are hashed, so the hash is stable across compiler
+    /// versions.
+    ///
+    /// # Template Arguments
+    /// * `TS` - The maximum number of bytes to encode a settings path into.
+    /// * `DEPTH` - The maximum recursive depth of the settings.
+    ///
+    /// # Args
+    /// * `version` - The version of the settings schema, maintained by the application.
+    ///
+    /// # Returns
+    /// The 32-bit FNV-1a hash of the version and paths.
+    #[cfg(not(feature = "set-only"))]
+    fn schema_hash<const TS: usize, const DEPTH: usize>(
+        &self,
+        version: u32,
+    ) -> Result<u32, IterError> {
+        let mut hash: u32 = 0x811c_9dc5;
+        let mut update = |bytes: &[u8]| {
+            for byte in bytes {
+                hash = (hash ^ *byte as u32).wrapping_mul(0x0100_0193);
+            }
+        };
+
+        update(&version.to_le_bytes());
+
+        let mut paths = iter::PathIterator::<TS, DEPTH>::new(self)?;
+        while let Some(path) = paths.next(self) {
+            // Each path is terminated, so that consecutive paths cannot be confused.
+            update(path.as_bytes());
+            update(&[0]);
+        }
+
+        Ok(hash)
+    }
+
+    /// Retrieve the descriptive attributes of a settings path.
+    ///
+    /// # Note
+    /// Combined with iteration over all settings paths, this can be used to generate a schema of
+    /// the settings structure.
+    ///
+    /// # Args
+    /// * `path` - The path to retrieve attributes for.
+    ///
+    /// # Returns
+    /// The attributes of the path.
+    fn get_attributes(&self, path: &str) -> Result<Attributes, Error> {
+        self.string_attributes(split_path(path))
+    }
+
+    /// Retrieve the name of the type of a setting.
+    ///
+    /// # Note
+    /// The name is provided by [core::any::type_name] and intended for diagnostics only. Its exact
+    /// contents are not guaranteed to be stable.
+    ///
+    /// # Args
+    /// * `path` - The path of the setting.
+    ///
+    /// # Returns
+    /// The name of the type of the setting, e.g. `f32`.
+    fn type_name(&self, path: &str) -> Result<&'static str, Error> {
+        self.string_type_name(split_path(path))
+    }
+
+    /// List the names of the variants of an enum setting.
+    ///
+    /// # Note
+    /// The names are those used on the wire, i.e. they respect `#[serde(rename)]` and
+    /// `#[serde(rename_all)]` attributes of the enum. Variants that are skipped during
+    /// deserialization are not listed.
+    ///
+    /// # Args
+    /// * `path` - The path of the setting.
+    ///
+    /// # Returns
+    /// The names of the variants of the setting, or [Error::NotAnEnum] if it is not an enum.
+    fn variants(&self, path: &str) -> Result<&'static [&'static str], Error> {
+        self.string_variants(split_path(path))
+    }
+
+    /// Serialize all settings into a nested JSON object.
+    ///
+    /// # Note
+    /// Array elements are serialized as object members keyed by their index, e.g.
+    /// `{"channels":{"0":{"gain":1.0}}}`. The object can be applied again using
+    /// [Miniconf::apply_json_object].
+    ///
+    /// # Template Arguments
+    /// * `TS` - The maximum number of bytes to encode a settings path into.
+    /// * `DEPTH` - The maximum recursive depth of the settings.
+    ///
+    /// # Args
+    /// * `data` - The location to serialize the object into.
+    ///
+    /// # Returns
+    /// The number of bytes used in the `data` buffer for the object, or [Error::PathTooLong] if
+    /// the paths do not fit within the template arguments.
+    #[cfg(not(feature = "set-only"))]
+    fn serialize_json_object<const TS: usize, const DEPTH: usize>(
+        &self,
+        data: &mut [u8],
+    ) -> Result<usize, Error> {
+        let mut paths = iter::PathIterator::<TS, DEPTH>::new(self)?;
+        json::serialize(self, core::iter::from_fn(|| paths.next(self)), data, false)
+    }
+
+    /// Read the settings into an equivalent serde type.
+    ///
+    /// # Note
+    /// The settings are serialized into a nested JSON object, which is then deserialized into the
+    /// provided type. Unlike [Miniconf::serialize_json_object], members keyed by indices, e.g.
+    /// array elements, are serialized as JSON arrays, so that they deserialize into arrays and
+    /// tuples. Absent optional settings are omitted. Escape sequences in strings are not
+    /// unescaped.
+    ///
+    /// # Template Arguments
+    /// * `T` - The serde type mirroring the structure of the settings.
+    ///
+    /// # Returns
+    /// The settings as `T`, or [Error::Deserialization] if they do not match its structure.
+    #[cfg(all(feature = "alloc", not(feature = "set-only")))]
+    fn to_serde_value<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        json::deserialize_into(self)
+    }
+
+    /// Apply a nested JSON object to the settings.
+    ///
+    /// # Note
+    /// Each member of the object is applied at the path formed by the keys leading to it, e.g.
+    /// `{"channels": [{"gain": 1.0}]}` updates `channels/0/gain`. Members are applied in order,
+    /// so the settings are partially updated if a member fails to apply. Paths of up to 128 bytes
+    /// are supported.
+    ///
+    /// # Args
+    /// * `obj` - The serialized JSON object, e.g. factory defaults embedded with `include_bytes!`.
+    ///
+    /// # Returns
+    /// An error naming the first path that failed to apply.
+    #[cfg(not(feature = "set-only"))]
+    #[allow(clippy::result_large_err)]
+    fn apply_json_object(&mut self, obj: &[u8]) -> Result<(), ApplyError> {
+        json::apply(self, obj)
+    }
+
+    /// Validate a nested JSON object against the settings without applying it.
+    ///
+    /// # Note
+    /// The object is applied to a copy of the settings as in [Miniconf::apply_json_object],
+    /// including all bounds and other validation of the settings. The settings themselves are
+    /// left untouched, so an object can be validated entirely before it is committed.
+    ///
+    /// # Args
+    /// * `obj` - The serialized JSON object, e.g. an uploaded configuration file.
+    ///
+    /// # Returns
+    /// An error naming the first path that failed to apply.
+    #[cfg(not(feature = "set-only"))]
+    #[allow(clippy::result_large_err)]
+    fn validate_json_object(&self, obj: &[u8]) -> Result<(), ApplyError>
+    where
+        Self: Clone,
+    {
+        self.clone().apply_json_object(obj)
+    }
+
+    /// Export all settings as line-delimited path-value pairs.
+    ///
+    /// # Note
+    /// Every setting is written as a line of the form `path\tvalue`, where the value is
+    /// serialized as JSON, e.g. `channels/0/gain\t1.5`. The lines can be applied again using
+    /// [Miniconf::import_lines]. Paths of up to 128 bytes and 16 levels deep and values of up to
+    /// 256 bytes are supported.
+    ///
+    /// # Args
+    /// * `w` - The destination to write the lines into.
+    ///
+    /// # Returns
+    /// [Error::PathTooLong] if the settings exceed the supported paths, in which case nothing is
+    /// written.
+    #[cfg(not(feature = "set-only"))]
+    fn export_lines<W: core::fmt::Write>(&self, w: &mut W) -> Result<(), Error> {
+        lines::export(self, w)
+    }
+
+    /// Apply line-delimited path-value pairs to the settings.
+    ///
+    /// # Note
+    /// Lines are applied in order, so the settings are partially updated if a line fails to
+    /// apply. Empty lines are ignored.
+    ///
+    /// # Args
+    /// * `data` - The lines, e.g. as produced by [Miniconf::export_lines].
+    ///
+    /// # Returns
+    /// An error naming the first path that failed to apply.
+    #[cfg(not(feature = "set-only"))]
+    #[allow(clippy::result_large_err)]
+    fn import_lines(&mut self, data: &str) -> Result<(), ApplyError> {
+        lines::import(self, data)
+    }
+
+    /// Retrieve metadata about the subtree of the settings below a path.
+    ///
+    /// # Note
+    /// This can be used to size buffers for accessing only part of the settings. The metadata of
+    /// a leaf has no topic size.
+    ///
+    /// # Args
+    /// * `base` - The path of the root of the subtree. An empty path refers to all settings.
+    ///
+    /// # Returns
+    /// The metadata of the subtree, with topic sizes relative to the base path.
+    #[cfg(not(feature = "set-only"))]
+    fn subtree_metadata(&self, base: &str) -> Result<MiniconfMetadata, Error> {
+        if base.is_empty() {
+            return Ok(self.get_metadata());
+        }
+
+        self.string_metadata(base.split('/').peekable())
     }
 
     /// Create an iterator to read all possible settings paths.
@@ -230,6 +1067,12 @@ pub trait Miniconf {
     ///
     /// # Args
     /// * `state` - A state vector to record iteration state in.
+    ///
+    /// # Returns
+    /// The iterator, or an error if the topic size or the state vector are too small for the
+    /// settings. [IterError::InsufficientStateDepth] reports the state vector length required.
+    #[cfg(not(feature = "set-only"))]
+    #[allow(clippy::wrong_self_convention)]
     fn into_iter<'a, const TS: usize>(
         &'a self,
         state: &'a mut [usize],
@@ -241,7 +1084,9 @@ pub trait Miniconf {
         }
 
         if state.len() < metadata.max_depth {
-            return Err(IterError::InsufficientStateDepth);
+            return Err(IterError::InsufficientStateDepth {
+                needed: metadata.max_depth,
+            });
         }
 
         Ok(iter::MiniconfIter {
@@ -265,6 +1110,7 @@ pub trait Miniconf {
     ///
     /// # Args
     /// * `state` - A state vector to record iteration state in.
+    #[cfg(not(feature = "set-only"))]
     fn unchecked_into_iter<'a, const TS: usize>(
         &'a self,
         state: &'a mut [usize],
@@ -275,31 +1121,202 @@ pub trait Miniconf {
         }
     }
 
+    /// Call a function with all possible settings paths, reusing a single path buffer.
+    ///
+    /// # Note
+    /// Each path is written into the provided buffer, which is cleared before every path. In
+    /// contrast to [Miniconf::into_iter], no path buffer is constructed per path.
+    ///
+    /// # Note
+    /// The state vector can be used to resume iteration from a previous point in time. The data
+    /// should be zero-initialized if starting iteration for the first time.
+    ///
+    /// # Args
+    /// * `state` - A state vector to record iteration state in.
+    /// * `topic` - The buffer to write each path into.
+    /// * `f` - The function to call with each path.
+    #[cfg(not(feature = "set-only"))]
+    fn for_each_path<const TS: usize>(
+        &self,
+        state: &mut [usize],
+        topic: &mut heapless::String<TS>,
+        mut f: impl FnMut(&str),
+    ) -> Result<(), IterError> {
+        let metadata = self.get_metadata();
+
+        if TS < metadata.max_topic_size {
+            return Err(IterError::InsufficientTopicLength);
+        }
+
+        if state.len() < metadata.max_depth {
+            return Err(IterError::InsufficientStateDepth {
+                needed: metadata.max_depth,
+            });
+        }
+
+        loop {
+            topic.clear();
+            if self.recurse_paths(state, topic).is_none() {
+                return Ok(());
+            }
+
+            f(topic);
+        }
+    }
+
+    /// Create an iterator to read all possible settings paths into growable strings.
+    ///
+    /// # Note
+    /// Both the iteration state and the paths are allocated dynamically, so no capacity needs to
+    /// be provided.
+    #[cfg(all(feature = "alloc", not(feature = "set-only")))]
+    fn iter_paths(&self) -> iter::MiniconfAllocIter<'_, Self> {
+        iter::MiniconfAllocIter {
+            settings: self,
+            state: alloc::vec![0; self.get_metadata().max_depth],
+        }
+    }
+
+    /// Collect all possible settings paths in lexicographic order.
+    ///
+    /// # Note
+    /// This is a convenience for host tools. Iteration itself always yields paths in declaration
+    /// order. Paths are compared as strings, so e.g. `a/10` sorts before `a/2`.
+    #[cfg(all(feature = "alloc", not(feature = "set-only")))]
+    fn sorted_paths(&self) -> alloc::vec::Vec<alloc::string::String> {
+        let mut paths: alloc::vec::Vec<_> = self.iter_paths().collect();
+        paths.sort_unstable();
+        paths
+    }
+
+    /// Update a setting from the remaining parts of its path.
+    ///
+    /// # Note
+    /// Implementations consume at most one path part before recursing into a member, and leaves
+    /// reject any remaining parts with [Error::PathTooLong]. The recursion depth is therefore
+    /// bounded by the depth of the settings type, regardless of the length of the path.
     fn string_set(
         &mut self,
         topic_parts: core::iter::Peekable<core::str::Split<char>>,
         value: &[u8],
     ) -> Result<(), Error>;
 
+    /// Serialize a setting from the remaining parts of its path.
+    ///
+    /// # Note
+    /// As for [Miniconf::string_set], the recursion depth is bounded by the depth of the settings
+    /// type.
     fn string_get(
         &self,
         topic_parts: core::iter::Peekable<core::str::Split<char>>,
         value: &mut [u8],
     ) -> Result<usize, Error>;
 
+    /// Determine the size of the serialized value of a setting from the remaining parts of its
+    /// path.
+    ///
+    /// # Note
+    /// The size is that of the value serialized by [Miniconf::string_get]. The default
+    /// implementation cannot measure any value and fails with [Error::SerializationFailed], so
+    /// that existing implementations remain valid.
+    fn string_value_len(
+        &self,
+        _topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        Err(Error::SerializationFailed)
+    }
+
+    /// Retrieve the descriptive attributes of a settings path.
+    ///
+    /// # Note
+    /// The default implementation is suitable for types without any members.
+    fn string_attributes(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Attributes, Error> {
+        if topic_parts.peek().is_some() {
+            return Err(Error::PathTooLong);
+        }
+
+        Ok(Attributes::default())
+    }
+
+    /// Retrieve the name of the type of a setting from the remaining parts of its path.
+    ///
+    /// # Note
+    /// The default implementation is suitable for types without any members.
+    fn string_type_name(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static str, Error> {
+        if topic_parts.peek().is_some() {
+            return Err(Error::PathTooLong);
+        }
+
+        Ok(core::any::type_name::<Self>())
+    }
+
+    /// Retrieve the names of the variants of an enum setting from the remaining parts of its path.
+    ///
+    /// # Note
+    /// The default implementation is suitable for types without any members that are not enums.
+    fn string_variants(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static [&'static str], Error> {
+        if topic_parts.peek().is_some() {
+            return Err(Error::PathTooLong);
+        }
+
+        Err(Error::NotAnEnum)
+    }
+
+    /// Retrieve typed access to the value of a leaf from the remaining parts of its path.
+    ///
+    /// # Note
+    /// The default implementation is suitable for types without any members that are not
+    /// reachable through [LeafValue].
+    ///
+    /// # Returns
+    /// The value of the leaf, or None if the leaf is not reachable through [LeafValue].
+    fn string_leaf_value(
+        &mut self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        if topic_parts.peek().is_some() {
+            return Err(Error::PathTooLong);
+        }
+
+        Ok(None)
+    }
+
+    /// Retrieve metadata about the subtree of the settings below the remaining parts of a path.
+    ///
+    /// # Note
+    /// The default implementation is suitable for types without any members.
+    #[cfg(not(feature = "set-only"))]
+    fn string_metadata(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<MiniconfMetadata, Error> {
+        if topic_parts.peek().is_some() {
+            return Err(Error::PathTooLong);
+        }
+
+        Ok(self.get_metadata())
+    }
+
     /// Get metadata about the settings structure.
+    #[cfg(not(feature = "set-only"))]
     fn get_metadata(&self) -> MiniconfMetadata;
 
-    fn recurse_paths<const TS: usize>(
-        &self,
-        index: &mut [usize],
-        topic: &mut heapless::String<TS>,
-    ) -> Option<()>;
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl iter::TopicBuffer) -> Option<()>;
 }
 
 macro_rules! impl_single {
-    ($x:ty) => {
-        impl Miniconf for $x {
+    (<$(const $n:ident: usize)?> $x:ty $(, $leaf:ident)?) => {
+        impl<$(const $n: usize)?> Miniconf for $x {
             fn string_set(
                 &mut self,
                 mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
@@ -324,23 +1341,50 @@ macro_rules! impl_single {
                 serde_json_core::to_slice(self, value).map_err(|_| Error::SerializationFailed)
             }
 
+            fn string_value_len(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<usize, Error> {
+                if topic_parts.peek().is_some() {
+                    return Err(Error::PathTooLong);
+                }
+
+                json_len(self)
+            }
+
+            $(
+            fn string_leaf_value(
+                &mut self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<Option<&mut dyn $leaf>, Error> {
+                if topic_parts.peek().is_some() {
+                    return Err(Error::PathTooLong);
+                }
+
+                Ok(Some(self))
+            }
+            )?
+
+            #[cfg(not(feature = "set-only"))]
             fn get_metadata(&self) -> MiniconfMetadata {
                 MiniconfMetadata {
                     // No topic length is needed, as there are no sub-members.
                     max_topic_size: 0,
                     // One index is required for the current element.
                     max_depth: 1,
+                    path_count: 1,
                 }
             }
 
             // This implementation is the base case for primitives where it will
             // yield once for self, then return None on subsequent calls.
-            fn recurse_paths<const TS: usize>(
+            #[cfg(not(feature = "set-only"))]
+            fn recurse_paths(
                 &self,
                 index: &mut [usize],
-                _topic: &mut heapless::String<TS>,
+                _topic: &mut impl iter::TopicBuffer,
             ) -> Option<()> {
-                if index.len() == 0 {
+                if index.is_empty() {
                     // Note: During expected execution paths using `into_iter()`, the size of the
                     // index stack is checked in advance to make sure this condition doesn't occur.
                     // However, it's possible to happen if the user manually calls `recurse_paths`.
@@ -359,6 +1403,10 @@ macro_rules! impl_single {
             }
         }
     };
+    // Primitive types provide typed access to their values.
+    ($x:ty) => {
+        impl_single!(<> $x, LeafValue);
+    };
 }
 
 // Implement trait for the primitive types
@@ -377,3 +1425,6 @@ impl_single!(f64);
 
 impl_single!(usize);
 impl_single!(bool);
+
+// Strings are transmitted as JSON strings.
+impl_single!(<const N: usize> heapless::String<N>);
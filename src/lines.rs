@@ -0,0 +1,55 @@
+//! Export and import of settings as line-delimited path-value pairs.
+//!
+//! # Format
+//! Every settings path is written on its own line, followed by a tab and the serialized JSON
+//! value of the setting, e.g. `channels/0/gain\t1.5`. Since values are JSON, they never contain
+//! tabs or line breaks themselves.
+use super::{ApplyError, Error, Miniconf};
+use heapless::String;
+
+// The maximum size of a serialized value in an export.
+const MAX_VALUE_SIZE: usize = 256;
+
+pub(crate) fn export<Settings: Miniconf + ?Sized, W: core::fmt::Write>(
+    settings: &Settings,
+    w: &mut W,
+) -> Result<(), Error> {
+    let mut state = [0; 16];
+    let mut value = [0; MAX_VALUE_SIZE];
+
+    for path in settings.into_iter::<128>(&mut state)? {
+        let len = settings.get(&path, &mut value)?;
+        let value = core::str::from_utf8(&value[..len]).map_err(|_| Error::SerializationFailed)?;
+        writeln!(w, "{}\t{}", path, value).map_err(|_| Error::SerializationFailed)?;
+    }
+
+    Ok(())
+}
+
+// Note: The error reports the failing path by value, so its size is intentional.
+#[allow(clippy::result_large_err)]
+pub(crate) fn import<Settings: Miniconf + ?Sized>(
+    settings: &mut Settings,
+    data: &str,
+) -> Result<(), ApplyError> {
+    for line in data.lines().filter(|line| !line.is_empty()) {
+        let (path, value) = line.split_once('\t').ok_or(ApplyError::Malformed)?;
+
+        settings.set(path, value.as_bytes()).map_err(|error| {
+            // Note: Paths exceeding the buffer are truncated in the error.
+            let mut failed = String::new();
+            for c in path.chars() {
+                if failed.push(c).is_err() {
+                    break;
+                }
+            }
+
+            ApplyError::Apply {
+                path: failed,
+                error,
+            }
+        })?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,570 @@
+//! Transcoding between JSON and CBOR representations of settings values.
+//!
+//! # Design
+//! Settings are (de)serialized as JSON by [crate::Miniconf]. Since both JSON and CBOR are
+//! self-describing, values can be converted between the two formats without knowledge of the
+//! underlying types. Maps and arrays are always encoded with definite lengths.
+//!
+//! Values are received from remote peers, so the nesting of maps, arrays and tags is limited to
+//! bound the recursion while transcoding.
+use core::{convert::TryFrom, fmt::Write};
+
+/// Errors that occur while transcoding.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The input data is not valid or cannot be represented in the output format.
+    Invalid,
+
+    /// The output buffer is too small.
+    BufferFull,
+}
+
+// CBOR major types.
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+// The additional information indicating an indefinite length item.
+const INDEFINITE: u8 = 31;
+
+// The maximum nesting depth of maps, arrays and tags.
+const MAX_NESTING_DEPTH: usize = 16;
+
+/// A cursor for writing into a fixed-size buffer.
+struct Cursor<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn push(&mut self, data: &[u8]) -> Result<(), Error> {
+        let end = self.pos.checked_add(data.len()).ok_or(Error::BufferFull)?;
+        self.buf
+            .get_mut(self.pos..end)
+            .ok_or(Error::BufferFull)?
+            .copy_from_slice(data);
+        self.pos = end;
+        Ok(())
+    }
+
+    /// Encode a CBOR item header.
+    fn head(&mut self, major: u8, value: u64) -> Result<(), Error> {
+        let mut head = [0; 9];
+        let len = encode_head(&mut head, major, value);
+        self.push(&head[..len])
+    }
+
+    /// Insert a CBOR item header at a previous position, moving any subsequent data back.
+    fn insert_head(&mut self, pos: usize, major: u8, value: u64) -> Result<(), Error> {
+        let mut head = [0; 9];
+        let len = encode_head(&mut head, major, value);
+        if self.pos + len > self.buf.len() {
+            return Err(Error::BufferFull);
+        }
+
+        self.buf.copy_within(pos..self.pos, pos + len);
+        self.buf[pos..pos + len].copy_from_slice(&head[..len]);
+        self.pos += len;
+        Ok(())
+    }
+}
+
+impl<'a> Write for Cursor<'a> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
+}
+
+/// Enter a nested map, array or tag.
+///
+/// # Returns
+/// The nesting depth within the item, or [Error::Invalid] if the maximum depth is exceeded.
+fn enter(depth: usize) -> Result<usize, Error> {
+    if depth < MAX_NESTING_DEPTH {
+        Ok(depth + 1)
+    } else {
+        Err(Error::Invalid)
+    }
+}
+
+fn encode_head(head: &mut [u8; 9], major: u8, value: u64) -> usize {
+    let major = major << 5;
+    if value < 24 {
+        head[0] = major | value as u8;
+        1
+    } else if value <= u8::MAX as u64 {
+        head[0] = major | 24;
+        head[1] = value as u8;
+        2
+    } else if value <= u16::MAX as u64 {
+        head[0] = major | 25;
+        head[1..3].copy_from_slice(&(value as u16).to_be_bytes());
+        3
+    } else if value <= u32::MAX as u64 {
+        head[0] = major | 26;
+        head[1..5].copy_from_slice(&(value as u32).to_be_bytes());
+        5
+    } else {
+        head[0] = major | 27;
+        head[1..9].copy_from_slice(&value.to_be_bytes());
+        9
+    }
+}
+
+/// Convert a JSON value into CBOR.
+///
+/// # Args
+/// * `json` - The JSON value to convert.
+/// * `cbor` - The buffer to encode the CBOR item into.
+///
+/// # Returns
+/// The number of bytes used in the `cbor` buffer.
+pub fn from_json(json: &[u8], cbor: &mut [u8]) -> Result<usize, Error> {
+    let mut parser = JsonParser {
+        json,
+        pos: 0,
+        depth: 0,
+    };
+    let mut cursor = Cursor { buf: cbor, pos: 0 };
+    parser.value(&mut cursor)?;
+
+    // Only trailing whitespace is permitted after the value.
+    if parser.peek().is_some() {
+        return Err(Error::Invalid);
+    }
+
+    Ok(cursor.pos)
+}
+
+struct JsonParser<'a> {
+    json: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    /// Get the next non-whitespace character without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(&c) = self.json.get(self.pos) {
+            if !c.is_ascii_whitespace() {
+                return Some(c);
+            }
+            self.pos += 1;
+        }
+
+        None
+    }
+
+    fn next(&mut self) -> Result<u8, Error> {
+        let c = *self.json.get(self.pos).ok_or(Error::Invalid)?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn expect(&mut self, literal: &[u8]) -> Result<(), Error> {
+        if self.json[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(Error::Invalid)
+        }
+    }
+
+    fn value(&mut self, cursor: &mut Cursor) -> Result<(), Error> {
+        match self.peek().ok_or(Error::Invalid)? {
+            b'{' => self.container(cursor, MAP, b'}'),
+            b'[' => self.container(cursor, ARRAY, b']'),
+            b'"' => self.string(cursor),
+            b't' => {
+                self.expect(b"true")?;
+                cursor.push(&[0xF5])
+            }
+            b'f' => {
+                self.expect(b"false")?;
+                cursor.push(&[0xF4])
+            }
+            b'n' => {
+                self.expect(b"null")?;
+                cursor.push(&[0xF6])
+            }
+            _ => self.number(cursor),
+        }
+    }
+
+    fn container(&mut self, cursor: &mut Cursor, major: u8, end: u8) -> Result<(), Error> {
+        self.depth = enter(self.depth)?;
+
+        // Skip the opening delimiter.
+        self.pos += 1;
+
+        let start = cursor.pos;
+        let mut count = 0;
+
+        if self.peek() == Some(end) {
+            self.pos += 1;
+        } else {
+            loop {
+                if major == MAP {
+                    if self.peek() != Some(b'"') {
+                        return Err(Error::Invalid);
+                    }
+                    self.string(cursor)?;
+
+                    if self.peek() != Some(b':') {
+                        return Err(Error::Invalid);
+                    }
+                    self.pos += 1;
+                }
+
+                self.value(cursor)?;
+                count += 1;
+
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(c) if c == end => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(Error::Invalid),
+                }
+            }
+        }
+
+        self.depth -= 1;
+
+        // The number of elements is only known once the container has been parsed.
+        cursor.insert_head(start, major, count)
+    }
+
+    fn string(&mut self, cursor: &mut Cursor) -> Result<(), Error> {
+        // Skip the opening quote.
+        self.pos += 1;
+
+        let start = cursor.pos;
+        loop {
+            match self.next()? {
+                b'"' => break,
+                b'\\' => {
+                    let c = match self.next()? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return Err(Error::Invalid),
+                    };
+
+                    let mut encoded = [0; 4];
+                    cursor.push(c.encode_utf8(&mut encoded).as_bytes())?;
+                }
+                c => cursor.push(&[c])?,
+            }
+        }
+
+        let len = cursor.pos - start;
+        cursor.insert_head(start, TEXT, len as u64)
+    }
+
+    fn hex4(&mut self) -> Result<u32, Error> {
+        let digits = self
+            .json
+            .get(self.pos..self.pos + 4)
+            .ok_or(Error::Invalid)?;
+        self.pos += 4;
+
+        let digits = core::str::from_utf8(digits).or(Err(Error::Invalid))?;
+        u32::from_str_radix(digits, 16).or(Err(Error::Invalid))
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let mut code = self.hex4()?;
+
+        // Characters outside of the basic multilingual plane are encoded as surrogate pairs.
+        if (0xD800..0xDC00).contains(&code) {
+            self.expect(b"\\u")?;
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(Error::Invalid);
+            }
+            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+        }
+
+        core::char::from_u32(code).ok_or(Error::Invalid)
+    }
+
+    fn number(&mut self, cursor: &mut Cursor) -> Result<(), Error> {
+        let start = self.pos;
+        while let Some(c) = self.json.get(self.pos) {
+            if !matches!(c, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        let number = core::str::from_utf8(&self.json[start..self.pos]).or(Err(Error::Invalid))?;
+        if number.is_empty() {
+            return Err(Error::Invalid);
+        }
+
+        if let Ok(value) = number.parse::<u64>() {
+            return cursor.head(UNSIGNED, value);
+        }
+
+        if let Ok(value) = number.parse::<i64>() {
+            // Note: CBOR negative integers are encoded as `-1 - n`.
+            return if value < 0 {
+                cursor.head(NEGATIVE, !(value as u64))
+            } else {
+                cursor.head(UNSIGNED, value as u64)
+            };
+        }
+
+        let value: f64 = number.parse().or(Err(Error::Invalid))?;
+
+        // Use single precision when it is sufficient to represent the value exactly.
+        if value as f32 as f64 == value {
+            cursor.push(&[0xFA])?;
+            cursor.push(&(value as f32).to_be_bytes())
+        } else {
+            cursor.push(&[0xFB])?;
+            cursor.push(&value.to_be_bytes())
+        }
+    }
+}
+
+/// Convert a CBOR item into JSON.
+///
+/// # Args
+/// * `cbor` - The CBOR item to convert.
+/// * `json` - The buffer to write the JSON value into.
+///
+/// # Returns
+/// The number of bytes used in the `json` buffer.
+pub fn to_json(cbor: &[u8], json: &mut [u8]) -> Result<usize, Error> {
+    let mut decoder = CborDecoder {
+        cbor,
+        pos: 0,
+        depth: 0,
+    };
+    let mut cursor = Cursor { buf: json, pos: 0 };
+    decoder.item(&mut cursor)?;
+
+    // The input must contain exactly one item.
+    if decoder.pos != cbor.len() {
+        return Err(Error::Invalid);
+    }
+
+    Ok(cursor.pos)
+}
+
+struct CborDecoder<'a> {
+    cbor: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> CborDecoder<'a> {
+    fn take(&mut self, len: u64) -> Result<&'a [u8], Error> {
+        // The length is provided by the peer, so it must not be trusted to fit into the input.
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| self.pos.checked_add(len))
+            .ok_or(Error::Invalid)?;
+        let data = self.cbor.get(self.pos..end).ok_or(Error::Invalid)?;
+        self.pos = end;
+        Ok(data)
+    }
+
+    /// Decode an item header into its major type, additional information and argument.
+    fn head(&mut self) -> Result<(u8, u8, u64), Error> {
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1F);
+
+        let argument = match info {
+            0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes([self.take(1)?[0], self.take(1)?[0]]) as u64,
+            26 => {
+                let mut data = [0; 4];
+                data.copy_from_slice(self.take(4)?);
+                u32::from_be_bytes(data) as u64
+            }
+            27 => {
+                let mut data = [0; 8];
+                data.copy_from_slice(self.take(8)?);
+                u64::from_be_bytes(data)
+            }
+            INDEFINITE => 0,
+            _ => return Err(Error::Invalid),
+        };
+
+        Ok((major, info, argument))
+    }
+
+    /// Determine if the next byte is the break marker of an indefinite length item.
+    fn is_break(&mut self) -> Result<bool, Error> {
+        if *self.cbor.get(self.pos).ok_or(Error::Invalid)? == 0xFF {
+            self.pos += 1;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn item(&mut self, cursor: &mut Cursor) -> Result<(), Error> {
+        let (major, info, argument) = self.head()?;
+
+        match major {
+            UNSIGNED => write!(cursor, "{}", argument).or(Err(Error::BufferFull)),
+            NEGATIVE => write!(cursor, "{}", -1 - argument as i128).or(Err(Error::BufferFull)),
+            TEXT => {
+                cursor.push(b"\"")?;
+                if info == INDEFINITE {
+                    while !self.is_break()? {
+                        let (major, info, len) = self.head()?;
+                        if major != TEXT || info == INDEFINITE {
+                            return Err(Error::Invalid);
+                        }
+                        self.text(cursor, len)?;
+                    }
+                } else {
+                    self.text(cursor, argument)?;
+                }
+                cursor.push(b"\"")
+            }
+            ARRAY | MAP => self.container(cursor, major, info, argument),
+            // Tags carry semantic information that is not representable in JSON, so only the
+            // tagged item is converted.
+            TAG => {
+                self.depth = enter(self.depth)?;
+                self.item(cursor)?;
+                self.depth -= 1;
+                Ok(())
+            }
+            SIMPLE => match info {
+                20 => cursor.push(b"false"),
+                21 => cursor.push(b"true"),
+                22 | 23 => cursor.push(b"null"),
+                25 => self.float(cursor, half_to_f32(argument as u16) as f64, true),
+                26 => self.float(cursor, f32::from_bits(argument as u32) as f64, true),
+                27 => self.float(cursor, f64::from_bits(argument), false),
+                _ => Err(Error::Invalid),
+            },
+            // Byte strings are not representable in JSON.
+            BYTES => Err(Error::Invalid),
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    fn container(
+        &mut self,
+        cursor: &mut Cursor,
+        major: u8,
+        info: u8,
+        argument: u64,
+    ) -> Result<(), Error> {
+        self.depth = enter(self.depth)?;
+
+        let (open, close) = if major == MAP {
+            (b"{", b"}")
+        } else {
+            (b"[", b"]")
+        };
+        cursor.push(open)?;
+
+        let mut index = 0;
+        loop {
+            let done = if info == INDEFINITE {
+                self.is_break()?
+            } else {
+                index == argument
+            };
+            if done {
+                break;
+            }
+
+            if index > 0 {
+                cursor.push(b",")?;
+            }
+
+            if major == MAP {
+                // JSON only supports string keys.
+                if self.cbor.get(self.pos).map(|initial| initial >> 5) != Some(TEXT) {
+                    return Err(Error::Invalid);
+                }
+                self.item(cursor)?;
+                cursor.push(b":")?;
+            }
+
+            self.item(cursor)?;
+            index += 1;
+        }
+
+        self.depth -= 1;
+        cursor.push(close)
+    }
+
+    fn text(&mut self, cursor: &mut Cursor, len: u64) -> Result<(), Error> {
+        let text = core::str::from_utf8(self.take(len)?).or(Err(Error::Invalid))?;
+
+        for c in text.chars() {
+            match c {
+                '"' => cursor.push(b"\\\"")?,
+                '\\' => cursor.push(b"\\\\")?,
+                c if (c as u32) < 0x20 => {
+                    write!(cursor, "\\u{:04x}", c as u32).or(Err(Error::BufferFull))?
+                }
+                c => {
+                    let mut encoded = [0; 4];
+                    cursor.push(c.encode_utf8(&mut encoded).as_bytes())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn float(&mut self, cursor: &mut Cursor, value: f64, single: bool) -> Result<(), Error> {
+        // JSON does not support non-finite numbers.
+        if !value.is_finite() {
+            return Err(Error::Invalid);
+        }
+
+        // Single precision values are printed with the shortest representation that is exact in
+        // single precision.
+        let result = if single {
+            write!(cursor, "{:?}", value as f32)
+        } else {
+            write!(cursor, "{:?}", value)
+        };
+
+        result.or(Err(Error::BufferFull))
+    }
+}
+
+/// Convert an IEEE 754 half-precision value into single precision.
+fn half_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1F) as u32;
+    let mantissa = (half & 0x3FF) as u32;
+
+    match exponent {
+        // Subnormal values are scaled by 2^-24.
+        0 => {
+            let magnitude = mantissa as f32 / 16_777_216.0;
+            f32::from_bits(sign | magnitude.to_bits())
+        }
+        // Infinity and NaN.
+        31 => f32::from_bits(sign | 0x7F80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 127 - 15) << 23) | (mantissa << 13)),
+    }
+}
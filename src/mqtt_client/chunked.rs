@@ -0,0 +1,139 @@
+use heapless::{String, Vec};
+use minimq::embedded_time::{self, duration::Extensions, Instant};
+
+// The time after receiving the latest chunk of a transfer after which the transfer is aborted.
+const CHUNK_TIMEOUT_SECONDS: u32 = 10;
+
+/// A request that is part of a chunked settings transfer.
+pub enum Request<'a> {
+    /// A fragment of the value of `path`, published to `<path>/chunk/<index>`.
+    Chunk { path: &'a str, index: usize },
+
+    /// A request to apply the transferred value of `path`, published to `<path>/commit`.
+    Commit { path: &'a str },
+}
+
+impl<'a> Request<'a> {
+    /// Parse a chunked transfer request from a settings path.
+    ///
+    /// # Returns
+    /// The request, or None if the path is not part of a chunked transfer.
+    pub fn parse(path: &'a str) -> Option<Self> {
+        if let Some(path) = path.strip_suffix("/commit") {
+            return Some(Request::Commit { path });
+        }
+
+        let (path, index) = path.rsplit_once('/')?;
+        let path = path.strip_suffix("/chunk")?;
+        let index = index.parse().ok()?;
+
+        Some(Request::Chunk { path, index })
+    }
+}
+
+/// Reassembles a settings value transferred in multiple chunks.
+///
+/// # Template Arguments
+/// * `MAX_TOPIC_LENGTH` - The maximum length of the settings path.
+/// * `SIZE` - The maximum size of the reassembled value.
+pub struct ChunkedTransfer<
+    C: embedded_time::Clock,
+    const MAX_TOPIC_LENGTH: usize,
+    const SIZE: usize,
+> {
+    clock: C,
+    path: String<MAX_TOPIC_LENGTH>,
+    data: Vec<u8, SIZE>,
+    next_index: usize,
+    deadline: Option<Instant<C>>,
+}
+
+impl<C: embedded_time::Clock, const MAX_TOPIC_LENGTH: usize, const SIZE: usize>
+    ChunkedTransfer<C, MAX_TOPIC_LENGTH, SIZE>
+{
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            path: String::new(),
+            data: Vec::new(),
+            next_index: 0,
+            deadline: None,
+        }
+    }
+
+    /// Parse a chunked transfer request from a settings path.
+    ///
+    /// # Returns
+    /// The request, or None if the path is not part of a chunked transfer or chunked transfers
+    /// are disabled.
+    pub fn parse<'a>(&self, path: &'a str) -> Option<Request<'a>> {
+        if SIZE == 0 {
+            return None;
+        }
+
+        Request::parse(path)
+    }
+
+    /// Abort the transfer in progress if no chunk was received before the timeout.
+    pub fn handle_timeout(&mut self) {
+        // Note(unwrap): The clock is also used by the MQTT client, which requires it to be valid.
+        let now = self.clock.try_now().unwrap();
+        if matches!(self.deadline, Some(deadline) if now > deadline) {
+            log::warn!("Chunked transfer of `{}` timed out", self.path);
+            self.abort();
+        }
+    }
+
+    /// Receive a chunk of a value.
+    ///
+    /// # Note
+    /// Chunk zero starts a new transfer, aborting any transfer in progress. All other chunks must
+    /// be received in order. Any error aborts the transfer.
+    ///
+    /// # Args
+    /// * `path` - The settings path of the value.
+    /// * `index` - The index of the chunk.
+    /// * `chunk` - The data of the chunk.
+    pub fn receive(&mut self, path: &str, index: usize, chunk: &[u8]) -> Result<(), &'static str> {
+        if index == 0 {
+            self.abort();
+            if self.path.push_str(path).is_err() {
+                return Err("Path too long");
+            }
+        } else if self.deadline.is_none() || self.path != path || self.next_index != index {
+            self.abort();
+            return Err("Chunk out of order");
+        }
+
+        if self.data.extend_from_slice(chunk).is_err() {
+            self.abort();
+            return Err("Value too large");
+        }
+
+        // Note(unwrap): The clock is also used by the MQTT client, which requires it to be valid.
+        let now = self.clock.try_now().unwrap();
+        self.next_index = index + 1;
+        self.deadline.replace(now + CHUNK_TIMEOUT_SECONDS.seconds());
+        Ok(())
+    }
+
+    /// Get the reassembled value of a path.
+    ///
+    /// # Args
+    /// * `path` - The settings path of the value.
+    pub fn value(&self, path: &str) -> Result<&[u8], &'static str> {
+        if self.deadline.is_none() || self.path != path {
+            return Err("No transfer in progress");
+        }
+
+        Ok(&self.data)
+    }
+
+    /// Abort the transfer in progress, if any.
+    pub fn abort(&mut self) {
+        self.path.clear();
+        self.data.clear();
+        self.next_index = 0;
+        self.deadline = None;
+    }
+}
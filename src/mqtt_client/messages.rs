@@ -1,11 +1,105 @@
+use super::cbor;
 use heapless::{String, Vec};
-use serde::Serialize;
+use serde::{Serialize, Serializer};
+
+/// The serialization format of settings values and responses transmitted over MQTT.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    /// Values are transmitted as JSON.
+    Json,
+
+    /// Values are transmitted as CBOR.
+    Cbor,
+}
+
+impl Format {
+    /// Convert a received value into JSON.
+    ///
+    /// # Args
+    /// * `value` - The received value.
+    /// * `buffer` - A buffer to store the converted value in, if necessary.
+    ///
+    /// # Returns
+    /// The JSON representation of the value.
+    pub fn decode<'a>(
+        &self,
+        value: &'a [u8],
+        buffer: &'a mut [u8],
+    ) -> Result<&'a [u8], cbor::Error> {
+        match self {
+            Format::Json => Ok(value),
+            Format::Cbor => {
+                let len = cbor::to_json(value, buffer)?;
+                Ok(&buffer[..len])
+            }
+        }
+    }
+
+    /// Convert a JSON value into the format for transmission.
+    ///
+    /// # Args
+    /// * `json` - The JSON value.
+    /// * `buffer` - A buffer to store the converted value in, if necessary.
+    ///
+    /// # Returns
+    /// The representation of the value in this format.
+    pub fn encode<'a>(
+        &self,
+        json: &'a [u8],
+        buffer: &'a mut [u8],
+    ) -> Result<&'a [u8], cbor::Error> {
+        match self {
+            Format::Json => Ok(json),
+            Format::Cbor => {
+                let len = cbor::from_json(json, buffer)?;
+                Ok(&buffer[..len])
+            }
+        }
+    }
+}
+
+// The maximum size of a serialized response. Messages and values carried by a `ValueResponse`
+// are at most 64 bytes each and may double in size when escaped.
+const MAX_RESPONSE_SIZE: usize = 384;
+
+// The maximum size of the serialized details of a handler error.
+const MAX_ERROR_DETAIL_SIZE: usize = 64;
+
+/// Serialized JSON embedded into a response as is.
+///
+/// # Note
+/// `serde_json_core` serializes bytes without any encoding, so the JSON is embedded verbatim.
+struct RawJson(Vec<u8, MAX_ERROR_DETAIL_SIZE>);
+
+impl Serialize for RawJson {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
 
 /// The payload of the MQTT response message to a settings update request.
 #[derive(Serialize)]
 pub struct SettingsResponse {
     code: u8,
     msg: String<64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RawJson>,
+}
+
+/// The structured payload published to the liveness topic of the device.
+#[derive(Serialize)]
+pub struct Liveness<'a> {
+    pub id: &'a str,
+    pub online: bool,
+}
+
+/// A response to a settings update request carrying the committed value of the setting.
+#[derive(Serialize)]
+pub struct ValueResponse<'a> {
+    code: u8,
+    msg: &'a str,
+    path: &'a str,
+    value: &'a str,
 }
 
 impl SettingsResponse {
@@ -13,35 +107,155 @@ impl SettingsResponse {
         Self {
             msg: String::from("OK"),
             code: 0,
+            error: None,
         }
     }
 
+    pub fn queued() -> Self {
+        Self {
+            msg: String::from("Queued"),
+            code: 0,
+            error: None,
+        }
+    }
+
+    pub fn chunk_received() -> Self {
+        Self {
+            msg: String::from("Chunk received"),
+            code: 0,
+            error: None,
+        }
+    }
+
+    pub fn busy() -> Self {
+        Self::error(String::from("Busy, retry"))
+    }
+
+    pub fn forbidden() -> Self {
+        Self::error(String::from("Forbidden"))
+    }
+
     pub fn error(msg: String<64>) -> Self {
-        Self { code: 255, msg }
+        Self {
+            code: 255,
+            msg,
+            error: None,
+        }
+    }
+
+    /// Attach the committed value of a setting to the response.
+    ///
+    /// # Args
+    /// * `path` - The path of the setting. Must not exceed 64 bytes.
+    /// * `value` - The serialized JSON value of the setting. Must not exceed 64 bytes.
+    pub fn with_value<'a>(&'a self, path: &'a str, value: &'a str) -> ValueResponse<'a> {
+        ValueResponse {
+            code: self.code,
+            msg: &self.msg,
+            path,
+            value,
+        }
+    }
+
+    /// Construct an error response from the debug representation of an error.
+    ///
+    /// # Note
+    /// Descriptions that do not fit within the response are truncated.
+    pub fn describe(err: impl core::fmt::Debug) -> Self {
+        Self::error(truncated(format_args!("{:?}", err)))
+    }
+
+    /// Describe the error reported by the response.
+    ///
+    /// # Returns
+    /// The serialized error details if provided, or the message otherwise. None if the response
+    /// does not report an error.
+    pub fn error_description(&self) -> Option<&str> {
+        if self.code == 0 {
+            return None;
+        }
+
+        // Note(unwrap): Error details are serialized JSON, which is valid UTF-8.
+        Some(match &self.error {
+            Some(error) => core::str::from_utf8(&error.0).unwrap(),
+            None => &self.msg,
+        })
+    }
+
+    /// Construct a human-readable description of the response to a request.
+    ///
+    /// # Args
+    /// * `path` - The settings path of the request.
+    pub fn audit(&self, path: &str) -> String<128> {
+        truncated(format_args!("{}: {} ({})", path, self.msg, self.code))
+    }
+}
+
+/// Errors returned by the handler of settings updates.
+pub trait HandlerError {
+    /// Construct the response to an update rejected with the error.
+    fn response(&self) -> SettingsResponse;
+}
+
+impl<E: AsRef<str>> HandlerError for E {
+    fn response(&self) -> SettingsResponse {
+        SettingsResponse::error(truncated(format_args!("{}", self.as_ref())))
     }
 }
 
-impl<T, E: AsRef<str>> From<Result<T, E>> for SettingsResponse {
+/// A handler error reported to the requestor as a JSON object.
+pub struct Structured<E>(pub E);
+
+impl<E: Serialize> HandlerError for Structured<E> {
+    fn response(&self) -> SettingsResponse {
+        let mut response = SettingsResponse::error(String::from("Rejected"));
+        match serde_json_core::to_vec(&self.0) {
+            Ok(json) => response.error = Some(RawJson(json)),
+            Err(_) => log::warn!(
+                "Handler error exceeds {} bytes, omitting details",
+                MAX_ERROR_DETAIL_SIZE
+            ),
+        }
+
+        response
+    }
+}
+
+impl<T, E: HandlerError> From<Result<T, E>> for SettingsResponse {
     fn from(result: Result<T, E>) -> Self {
         match result {
             Ok(_) => SettingsResponse::ok(),
+            Err(error) => error.response(),
+        }
+    }
+}
 
-            Err(error) => {
-                let mut msg = String::new();
-                if msg.push_str(error.as_ref()).is_err() {
-                    msg = String::from("Configuration Error");
-                }
+/// Format text into a string, truncating any text that does not fit.
+fn truncated<const N: usize>(args: core::fmt::Arguments) -> String<N> {
+    struct Truncating<'a, const N: usize>(&'a mut String<N>);
 
-                Self::error(msg)
+    impl<'a, const N: usize> core::fmt::Write for Truncating<'a, N> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            for c in s.chars() {
+                if self.0.push(c).is_err() {
+                    break;
+                }
             }
+
+            Ok(())
         }
     }
+
+    let mut string = String::new();
+    // Note(unwrap): Writing into a truncating string never fails.
+    core::fmt::write(&mut Truncating(&mut string), args).unwrap();
+    string
 }
 
 /// Represents a generic MQTT message.
 pub struct MqttMessage<'a> {
     pub topic: &'a str,
-    pub message: Vec<u8, 128>,
+    pub message: Vec<u8, MAX_RESPONSE_SIZE>,
     pub properties: Vec<minimq::Property<'a>, 1>,
 }
 
@@ -51,39 +265,66 @@ impl<'a> MqttMessage<'a> {
     /// # Args
     /// * `properties` - A list of properties associated with the inbound message.
     /// * `default_response` - The default response topic for the message
-    /// * `msg` - The response associated with the message. Must fit within 128 bytes.
+    /// * `msg` - The response associated with the message. Must fit within 384 bytes.
+    /// * `format` - The format to encode the response in.
     pub fn new<'b: 'a>(
         properties: &[minimq::Property<'a>],
         default_response: &'b str,
         msg: &impl Serialize,
+        format: Format,
     ) -> Self {
-        // Extract the MQTT response topic.
-        let topic = properties
-            .iter()
-            .find_map(|prop| {
-                if let minimq::Property::ResponseTopic(topic) = prop {
-                    Some(topic)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(&default_response);
-
-        // Associate any provided correlation data with the response.
-        let mut correlation_data: Vec<minimq::Property<'a>, 1> = Vec::new();
-        if let Some(data) = properties
-            .iter()
-            .find(|prop| matches!(prop, minimq::Property::CorrelationData(_)))
-        {
-            // Note(unwrap): Unwrap can not fail, as we only ever push one value.
-            correlation_data.push(*data).unwrap();
-        }
+        let (topic, properties) = response_properties(properties, default_response);
+
+        // Note(unwrap): All SettingsResponse and ValueResponse objects are guaranteed to fit in the
+        // vector.
+        let json: Vec<u8, MAX_RESPONSE_SIZE> = serde_json_core::to_vec(msg).unwrap();
+
+        // Note(unwrap): Only the error details of responses may contain floating point values.
+        // The details are small enough that their CBOR representation fits in any case.
+        let mut buffer = [0; MAX_RESPONSE_SIZE];
+        let message = Vec::from_slice(format.encode(&json, &mut buffer).unwrap()).unwrap();
 
         Self {
             topic,
-            // Note(unwrap): All SettingsResponse objects are guaranteed to fit in the vector.
-            message: serde_json_core::to_vec(msg).unwrap(),
-            properties: correlation_data,
+            message,
+            properties,
         }
     }
 }
+
+/// Determine the response topic and properties for a reply to an incoming message.
+///
+/// # Args
+/// * `properties` - A list of properties associated with the inbound message.
+/// * `default_response` - The default response topic for the message
+///
+/// # Returns
+/// The topic to publish the response to and the properties to associate with the response.
+pub fn response_properties<'a, 'b: 'a>(
+    properties: &[minimq::Property<'a>],
+    default_response: &'b str,
+) -> (&'a str, Vec<minimq::Property<'a>, 1>) {
+    // Extract the MQTT response topic.
+    let topic = properties
+        .iter()
+        .find_map(|prop| {
+            if let minimq::Property::ResponseTopic(topic) = prop {
+                Some(topic)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(&default_response);
+
+    // Associate any provided correlation data with the response.
+    let mut correlation_data: Vec<minimq::Property<'a>, 1> = Vec::new();
+    if let Some(data) = properties
+        .iter()
+        .find(|prop| matches!(prop, minimq::Property::CorrelationData(_)))
+    {
+        // Note(unwrap): Unwrap can not fail, as we only ever push one value.
+        correlation_data.push(*data).unwrap();
+    }
+
+    (topic, correlation_data)
+}
@@ -1,3 +1,14 @@
+mod cbor;
+mod chunked;
 mod messages;
+#[allow(clippy::module_inception)]
 mod mqtt_client;
-pub use mqtt_client::MqttClient;
+#[cfg(feature = "alloc")]
+mod reader;
+pub use messages::Format;
+pub use mqtt_client::{
+    assert_buffer_sizes, ActivationError, BufferSizeOptions, CollectError, ConstructionError,
+    MqttClient, MqttErrorKind, MqttStats, PauseBehavior, PollOutcome, TopicMapper,
+};
+#[cfg(feature = "alloc")]
+pub use reader::SettingsReader;
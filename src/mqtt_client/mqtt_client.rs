@@ -9,6 +9,40 @@
 /// With an MQTT client prefix of `dt/sinara/stabilizer` and a settings path of `adc/0/gain`, the
 /// full MQTT path would be `dt/sinara/stabilizer/settings/adc/0/gain`.
 ///
+/// The `settings` segment can be replaced using [MqttClient::settings_root], e.g. to place the
+/// settings paths behind `<prefix>/cfg/`. The `/` separating the settings paths from the
+/// settings prefix can be replaced using [MqttClient::settings_separator].
+///
+/// Independent settings structures can be served by a single client using [crate::Domains], which
+/// places each structure below its own name, e.g. `<prefix>/settings/net/...` and
+/// `<prefix>/settings/dsp/...`.
+///
+/// Settings may also be an enum of mutually-exclusive modes. The active variant is then set and
+/// republished at `<prefix>/settings` itself, while the data of a variant is addressed by its
+/// name, e.g. `<prefix>/settings/Fixed`.
+///
+/// Publishing an empty message to a settings path ending in a `*` wildcard (e.g.
+/// `dt/sinara/stabilizer/settings/adc/0/*`) requests all settings values below that path. They are
+/// published to the response topic as an object keyed by the path relative to the wildcard.
+///
+/// Publishing an empty message to `<prefix>/get/<path>` requests the values of all settings below
+/// the path, or of the setting at the path itself. `<prefix>/get` (or `<prefix>/get/#`) requests
+/// all settings. Each value is published to the response topic as a JSON object of the form
+/// `{"path":"adc/0/gain","value":1.0}`, followed by the response to the request once all values
+/// are published. Values are published as the connection permits across multiple updates.
+///
+/// Settings may alternatively be republished as a single retained JSON object to
+/// `<prefix>/settings/snapshot`, see [MqttClient::republish_snapshot].
+///
+/// Responses to settings requests are published to the response topic provided by the request,
+/// or to `<prefix>/response` otherwise.
+///
+/// Settings topics can be translated to the topics used on the broker with a [TopicMapper], see
+/// [MqttClient::topic_mapper].
+///
+/// The hash of the settings structure can be published to `<prefix>/schema` and checked against
+/// the hash expected by the management plane, see [MqttClient::schema_check].
+///
 /// # Limitations
 /// The MQTT client logs failures to subscribe to the settings topic, but does not re-attempt to
 /// connect to it when errors occur.
@@ -16,20 +50,37 @@
 /// Responses to settings updates are sent without quality-of-service guarantees, so there's no
 /// guarantee that the requestee will be informed that settings have been applied.
 ///
-/// The library only supports serialized settings up to 256 bytes currently.
-use serde_json_core::heapless::String;
+/// Serialized settings values must fit within a single MQTT message of `MESSAGE_SIZE` bytes,
+/// including the topic, unless they are transferred in chunks.
+///
+/// ## Chunked Transfers
+/// Values larger than a single MQTT message can be transferred in multiple chunks if the
+/// `MAX_CHUNKED_SIZE` template argument is non-zero. Each chunk of the value is published to
+/// `<prefix>/settings/<path>/chunk/<n>`, starting with `n = 0` and incrementing sequentially.
+/// Publishing to `<prefix>/settings/<path>/commit` then applies the concatenated chunks as the
+/// value of the setting.
+///
+/// Chunk zero starts a new transfer and aborts any transfer in progress. A chunk received out of
+/// order or exceeding `MAX_CHUNKED_SIZE` aborts the transfer, as does not receiving a chunk for
+/// 10 seconds. Only a single transfer may be in progress at any time. While chunked transfers are
+/// enabled, settings named `commit` or `chunk` cannot be addressed.
+use serde_json_core::heapless::{String, Vec};
 
 use minimq::embedded_nal::{IpAddr, TcpClientStack};
 
-use super::messages::{MqttMessage, SettingsResponse};
+use super::chunked::{ChunkedTransfer, Request};
+use super::messages::{
+    response_properties, Format, HandlerError, Liveness, MqttMessage, SettingsResponse, Structured,
+};
+#[cfg(feature = "alloc")]
+use super::reader::{Publisher, SettingsReader};
 use crate::Miniconf;
 use log::info;
 use minimq::{embedded_time, QoS, Retain};
+use serde::Serialize;
 
 use core::fmt::Write;
-
-// The maximum topic length of any settings path.
-const MAX_TOPIC_LENGTH: usize = 128;
+use core::str::FromStr;
 
 // The keepalive interval to use for MQTT in seconds.
 const KEEPALIVE_INTERVAL_SECONDS: u16 = 60;
@@ -41,8 +92,28 @@ const MAX_RECURSION_DEPTH: usize = 8;
 // republished.
 const REPUBLISH_TIMEOUT_SECONDS: u32 = 2;
 
+// The maximum size of paths and values included in responses to committed updates.
+const MAX_RESPONSE_VALUE_SIZE: usize = 64;
+
+// The maximum number of distinct settings paths that can be queued while the client is paused.
+const MAX_PENDING_UPDATES: usize = 4;
+
+// The maximum number of distinct settings paths whose changes are tracked for the session.
+const MAX_TRACKED_PATHS: usize = 32;
+
+// The maximum size of structured payloads of the liveness topic.
+const MAX_LIVENESS_SIZE: usize = 128;
+
+// The maximum size of an MQTT publish packet without its topic, properties and payload: The fixed
+// header with the largest remaining length, the topic length and the property length.
+const PUBLISH_OVERHEAD: usize = 1 + 4 + 2 + 1;
+
 mod sm {
-    use minimq::embedded_time::{self, duration::Extensions, Instant};
+    use minimq::embedded_time::{
+        self,
+        duration::{Extensions, Seconds},
+        Instant,
+    };
     use smlang::statemachine;
 
     statemachine! {
@@ -50,21 +121,34 @@ mod sm {
             *Initial + Connected = ConnectedToBroker,
             ConnectedToBroker + IndicatedLife = PendingSubscribe,
 
+            // Subscriptions are only in place once the broker has acknowledged them.
+            PendingSubscribe + SubscriptionsRequested = PendingSubscriptionAck,
+
             // After initial subscriptions, we start a timeout to republish all settings.
-            PendingSubscribe + Subscribed / start_republish_timeout = PendingRepublish,
+            PendingSubscriptionAck + Subscribed / start_republish_timeout = PendingRepublish,
+
+            // If the broker preserved a persistent session, the subscription and previously
+            // published settings are still in place.
+            PendingSubscribe + SessionResumed / start_periodic_timeout = Active,
+
+            // Republishing on connection may be disabled, in which case settings are only
+            // republished on request.
+            PendingSubscriptionAck + SubscribedWithoutRepublish / start_periodic_timeout = Active,
 
             // Settings republish can be completed any time after subscription.
             PendingRepublish + StartRepublish / start_republish = RepublishingSettings,
             RepublishingSettings + StartRepublish / start_republish = RepublishingSettings,
             Active + StartRepublish / start_republish = RepublishingSettings,
 
-            // After republishing settings, we are in an idle "active" state.
-            RepublishingSettings + RepublishComplete = Active,
+            // After republishing settings, we are in an idle "active" state until the next
+            // periodic republish, if any.
+            RepublishingSettings + RepublishComplete / start_periodic_timeout = Active,
 
             // All states transition back to `initial` on reset.
             Initial + Reset = Initial,
             ConnectedToBroker + Reset = Initial,
             PendingSubscribe + Reset = Initial,
+            PendingSubscriptionAck + Reset = Initial,
             PendingRepublish + Reset = Initial,
             RepublishingSettings + Reset = Initial,
             Active + Reset = Initial,
@@ -74,7 +158,9 @@ mod sm {
     pub struct Context<C: embedded_time::Clock> {
         clock: C,
         timeout: Option<Instant<C>>,
+        pub periodic_interval: Option<Seconds<u32>>,
         pub republish_state: [usize; super::MAX_RECURSION_DEPTH],
+        pub republish_count: usize,
     }
 
     impl<C: embedded_time::Clock> Context<C> {
@@ -82,10 +168,16 @@ mod sm {
             Self {
                 clock,
                 timeout: None,
+                periodic_interval: None,
                 republish_state: [0; super::MAX_RECURSION_DEPTH],
+                republish_count: 0,
             }
         }
 
+        pub fn now(&self) -> Instant<C> {
+            self.clock.try_now().unwrap()
+        }
+
         pub fn republish_has_timed_out(&self) -> bool {
             if let Some(timeout) = self.timeout {
                 self.clock.try_now().unwrap() > timeout
@@ -102,16 +194,520 @@ mod sm {
             );
         }
 
+        fn start_periodic_timeout(&mut self) {
+            self.timeout = self
+                .periodic_interval
+                .map(|interval| self.clock.try_now().unwrap() + interval);
+        }
+
         fn start_republish(&mut self) {
+            self.timeout = None;
             self.republish_state = [0; super::MAX_RECURSION_DEPTH];
+            self.republish_count = 0;
+        }
+    }
+}
+
+/// Specifies how settings updates are handled while the client is paused.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PauseBehavior {
+    /// The latest value received for each path is queued and applied once the client is resumed.
+    Queue,
+
+    /// Updates are rejected with a busy response and must be retried by the requestor.
+    Reject,
+}
+
+/// Translation between the settings topics of the client and the topics used on the broker.
+///
+/// # Note
+/// Settings topics are mapped when subscribing and republishing settings. Topics received from
+/// the broker are mapped back before the settings path is extracted. Other topics of the client,
+/// e.g. responses, are not mapped.
+///
+/// # Template Arguments
+/// * `N` - The maximum length of any MQTT topic, i.e. `MAX_TOPIC_LENGTH` of the client.
+pub trait TopicMapper<const N: usize> {
+    /// Translate a settings topic of the client into the topic used on the broker.
+    ///
+    /// # Returns
+    /// The topic used on the broker, or None if the topic cannot be translated.
+    fn to_broker(&self, topic: &str) -> Option<String<N>>;
+
+    /// Translate a topic received from the broker into the settings topic of the client.
+    ///
+    /// # Returns
+    /// The settings topic, or None if the topic does not belong to the client.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_broker(&self, topic: &str) -> Option<String<N>>;
+}
+
+/// The outcome of servicing the settings interface.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PollOutcome {
+    /// No settings messages were received.
+    NoMessage,
+
+    /// Settings messages were handled, but the settings did not change.
+    Unchanged,
+
+    /// The settings changed.
+    Changed,
+}
+
+/// Errors that occur while collecting the paths of changed settings.
+#[derive(Debug)]
+pub enum CollectError<E> {
+    /// An error occurred in the MQTT client.
+    Mqtt(minimq::Error<E>),
+
+    /// The provided buffer could not hold the paths of all changed settings.
+    ///
+    /// # Note
+    /// All settings updates are still applied.
+    PathBufferFull,
+}
+
+impl<E> From<minimq::Error<E>> for CollectError<E> {
+    fn from(err: minimq::Error<E>) -> Self {
+        CollectError::Mqtt(err)
+    }
+}
+
+/// Errors that occur while waiting for the settings interface to become active.
+#[derive(Debug)]
+pub enum ActivationError<E> {
+    /// An error occurred in the MQTT client.
+    Mqtt(minimq::Error<E>),
+
+    /// The interface did not become active before the timeout elapsed.
+    Timeout,
+}
+
+impl<E> From<minimq::Error<E>> for ActivationError<E> {
+    fn from(err: minimq::Error<E>) -> Self {
+        ActivationError::Mqtt(err)
+    }
+}
+
+/// The kind of an error reported by the MQTT client.
+///
+/// # Note
+/// The variants correspond to those of `minimq::Error`, without any error details.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MqttErrorKind {
+    Network,
+    WriteFail,
+    NotReady,
+    Unsupported,
+    ProvidedClientIdTooLong,
+    Failed(u8),
+    Protocol(minimq::ProtocolError),
+    SessionReset,
+    Clock,
+}
+
+impl<E> From<&minimq::Error<E>> for MqttErrorKind {
+    fn from(err: &minimq::Error<E>) -> Self {
+        match err {
+            minimq::Error::Network(_) => MqttErrorKind::Network,
+            minimq::Error::WriteFail => MqttErrorKind::WriteFail,
+            minimq::Error::NotReady => MqttErrorKind::NotReady,
+            minimq::Error::Unsupported => MqttErrorKind::Unsupported,
+            minimq::Error::ProvidedClientIdTooLong => MqttErrorKind::ProvidedClientIdTooLong,
+            minimq::Error::Failed(code) => MqttErrorKind::Failed(*code),
+            minimq::Error::Protocol(err) => MqttErrorKind::Protocol(*err),
+            minimq::Error::SessionReset => MqttErrorKind::SessionReset,
+            minimq::Error::Clock(_) => MqttErrorKind::Clock,
+        }
+    }
+}
+
+/// Diagnostic statistics of the MQTT connection.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct MqttStats {
+    /// The number of times the connection to the broker was lost or the session was reset.
+    pub reconnects: u32,
+
+    /// The kind of the most recent error returned by the MQTT client, if any.
+    pub last_error_kind: Option<MqttErrorKind>,
+
+    /// The number of times all settings were republished.
+    pub republishes: u32,
+
+    /// The number of settings skipped during republication, e.g. because their value does not
+    /// fit within a single message.
+    pub dropped_settings: u32,
+}
+
+/// Errors that occur when constructing the MQTT client.
+#[derive(Debug)]
+pub enum ConstructionError<E> {
+    /// An error occurred in the MQTT client.
+    Mqtt(minimq::Error<E>),
+
+    /// The longest settings topic, including the device prefix, exceeds `MAX_TOPIC_LENGTH`.
+    TopicTooLong,
+
+    /// Messages of `MESSAGE_SIZE` cannot hold a publication to the longest settings topic.
+    MessageSizeTooSmall,
+
+    /// The settings are nested deeper than the client supports.
+    SettingsTooDeep {
+        /// The depth of the settings.
+        depth: usize,
+
+        /// The maximum depth supported by the client.
+        max: usize,
+    },
+
+    /// The device prefix contains MQTT wildcards (`+` or `#`) or null characters, which are not
+    /// allowed in the topics of the client.
+    InvalidPrefix,
+
+    /// The client ID is longer than 64 bytes or contains null characters, which are not allowed
+    /// in MQTT strings.
+    InvalidClientId,
+
+    /// The settings separator does not start with `/`.
+    InvalidSeparator,
+}
+
+impl<E> From<minimq::Error<E>> for ConstructionError<E> {
+    fn from(err: minimq::Error<E>) -> Self {
+        ConstructionError::Mqtt(err)
+    }
+}
+
+/// Optional configuration of an MQTT client checked by [crate::assert_buffer_sizes].
+#[doc(hidden)]
+pub struct BufferSizeOptions {
+    /// The settings root of the client, see [MqttClient::settings_root].
+    pub root: &'static str,
+
+    /// The settings separator of the client, see [MqttClient::settings_separator].
+    pub separator: &'static str,
+
+    /// The `MAX_VALUE_SIZE` of the client.
+    pub max_value_size: usize,
+}
+
+impl Default for BufferSizeOptions {
+    fn default() -> Self {
+        Self {
+            root: "settings",
+            separator: "/",
+            max_value_size: usize::MAX,
+        }
+    }
+}
+
+/// Assert that the buffers of an MQTT client can hold all settings topics and values.
+///
+/// # Note
+/// This is intended to be used in tests, see [crate::assert_buffer_sizes].
+///
+/// # Template Arguments
+/// * `MESSAGE_SIZE` - The `MESSAGE_SIZE` of the client.
+/// * `MAX_TOPIC_LENGTH` - The `MAX_TOPIC_LENGTH` of the client.
+///
+/// # Args
+/// * `settings` - The settings whose values are checked.
+/// * `prefix` - The MQTT device prefix of the client.
+/// * `options` - The optional configuration of the client.
+#[doc(hidden)]
+pub fn assert_buffer_sizes<
+    Settings: Miniconf + ?Sized,
+    const MESSAGE_SIZE: usize,
+    const MAX_TOPIC_LENGTH: usize,
+>(
+    settings: &Settings,
+    prefix: &str,
+    options: BufferSizeOptions,
+) {
+    // Settings topics are of the form `<prefix>/<root><separator><path>`, see [settings_topic].
+    let topic_length = |path_length: usize| {
+        let settings_prefix = prefix.len() + 1 + options.root.len();
+        if path_length == 0 {
+            settings_prefix
+        } else {
+            settings_prefix + options.separator.len() + path_length
+        }
+    };
+
+    // Paths within arrays may be longer at run-time than indicated by the metadata.
+    let longest_path = settings
+        .longest_path::<MAX_RECURSION_DEPTH>()
+        .unwrap_or_else(|_| {
+            panic!(
+                "Settings are nested deeper than the supported depth of {}",
+                MAX_RECURSION_DEPTH
+            )
+        })
+        .max(settings.get_metadata().max_topic_size);
+
+    assert!(
+        topic_length(longest_path) <= MAX_TOPIC_LENGTH,
+        "Settings topics require {} bytes, exceeding MAX_TOPIC_LENGTH of {}",
+        topic_length(longest_path),
+        MAX_TOPIC_LENGTH
+    );
+
+    // Note(unwrap): The paths and depth of the settings were checked to fit above.
+    let mut paths =
+        crate::iter::PathIterator::<MAX_TOPIC_LENGTH, MAX_RECURSION_DEPTH>::new(settings).unwrap();
+    let mut value = [0; MESSAGE_SIZE];
+
+    while let Some(path) = paths.next(settings) {
+        // Values that cannot be measured, e.g. of fields with custom serialization, are
+        // serialized instead.
+        let len = settings
+            .value_len(&path)
+            .or_else(|_| settings.get(&path, &mut value))
+            .unwrap_or_else(|err| {
+                panic!(
+                    "Value of `{}` cannot be serialized within MESSAGE_SIZE of {}: {:?}",
+                    path.as_str(),
+                    MESSAGE_SIZE,
+                    err
+                )
+            });
+
+        // Values exceeding `MAX_VALUE_SIZE` are skipped when republishing.
+        assert!(
+            len <= options.max_value_size,
+            "Value of `{}` requires {} bytes, exceeding MAX_VALUE_SIZE of {}",
+            path.as_str(),
+            len,
+            options.max_value_size
+        );
+
+        let required = topic_length(path.len()) + len + PUBLISH_OVERHEAD;
+        assert!(
+            required <= MESSAGE_SIZE,
+            "Publication of `{}` requires {} bytes, exceeding MESSAGE_SIZE of {}",
+            path.as_str(),
+            required,
+            MESSAGE_SIZE
+        );
+    }
+}
+
+/// Assert that the buffers of an MQTT client can hold all settings topics and values.
+///
+/// # Note
+/// This is intended to be used in tests to detect undersized buffers before running on a device.
+/// The maximum size of each value is not known in advance, so the values of `Default::default()`
+/// settings are checked. Settings whose serialized size depends on their value, e.g. strings,
+/// should be checked with representative values using the second form of the macro. Values are
+/// measured in their JSON form, so CBOR encoded values are not checked exactly.
+///
+/// # Args
+/// * `Settings` - The settings type, or a reference to representative settings.
+/// * `prefix` - The MQTT device prefix of the client.
+/// * `MESSAGE_SIZE` - The maximum size of MQTT messages of the client.
+/// * `MAX_TOPIC_LENGTH` - The maximum length of MQTT topics of the client. Defaults to 128.
+///
+/// Options of the client that deviate from their defaults follow a `;` as `name = value` pairs:
+/// * `root` - The settings root, see [MqttClient::settings_root]. Defaults to `"settings"`.
+/// * `separator` - The settings separator, see [MqttClient::settings_separator]. Defaults to
+///   `"/"`.
+/// * `max_value_size` - The `MAX_VALUE_SIZE` of the client. Defaults to `MESSAGE_SIZE`.
+///
+/// # Panics
+/// If a settings topic exceeds `MAX_TOPIC_LENGTH`, a value exceeds `MAX_VALUE_SIZE` or the
+/// publication of a setting exceeds `MESSAGE_SIZE`, with messages such as:
+/// ```text
+/// Settings topics require 140 bytes, exceeding MAX_TOPIC_LENGTH of 128
+/// Value of `gain` requires 4 bytes, exceeding MAX_VALUE_SIZE of 2
+/// Publication of `gain` requires 70 bytes, exceeding MESSAGE_SIZE of 64
+/// ```
+///
+/// # Example
+/// ```rust
+/// #[derive(miniconf::Miniconf, Default)]
+/// struct Settings {
+///     gain: f32,
+/// }
+///
+/// miniconf::assert_buffer_sizes!(Settings, "dt/sinara/device", 256);
+/// miniconf::assert_buffer_sizes!(&Settings { gain: -1.5 }, "dt/sinara/device", 256, 64);
+/// miniconf::assert_buffer_sizes!(Settings, "dt/sinara/device", 256; max_value_size = 16);
+/// ```
+#[macro_export]
+macro_rules! assert_buffer_sizes {
+    (&$settings:expr, $prefix:expr, $message_size:expr
+     $(; $($option:ident = $value:expr),+)?) => {
+        $crate::assert_buffer_sizes!(
+            &$settings, $prefix, $message_size, 128 $(; $($option = $value),+)?
+        )
+    };
+    (&$settings:expr, $prefix:expr, $message_size:expr, $max_topic_length:expr
+     $(; $($option:ident = $value:expr),+)?) => {
+        $crate::assert_buffer_sizes::<_, { $message_size }, { $max_topic_length }>(
+            &$settings,
+            $prefix,
+            $crate::BufferSizeOptions {
+                $($($option: $value,)+)?
+                ..::core::default::Default::default()
+            },
+        )
+    };
+    ($settings:ty, $prefix:expr, $message_size:expr $(; $($option:ident = $value:expr),+)?) => {
+        $crate::assert_buffer_sizes!(
+            $settings, $prefix, $message_size, 128 $(; $($option = $value),+)?
+        )
+    };
+    ($settings:ty, $prefix:expr, $message_size:expr, $max_topic_length:expr
+     $(; $($option:ident = $value:expr),+)?) => {
+        $crate::assert_buffer_sizes::<_, { $message_size }, { $max_topic_length }>(
+            &<$settings as ::core::default::Default>::default(),
+            $prefix,
+            $crate::BufferSizeOptions {
+                $($($option: $value,)+)?
+                ..::core::default::Default::default()
+            },
+        )
+    };
+}
+
+/// A function rewriting a settings value into a scratch buffer, see [MqttClient::pre_set].
+type PreSet = fn(&str, &[u8], &mut [u8]) -> Option<usize>;
+
+/// Tracks consecutive handler errors on a single settings path.
+struct HandlerErrors<const MAX_TOPIC_LENGTH: usize> {
+    threshold: Option<usize>,
+    path: String<MAX_TOPIC_LENGTH>,
+    count: usize,
+}
+
+impl<const MAX_TOPIC_LENGTH: usize> HandlerErrors<MAX_TOPIC_LENGTH> {
+    fn new() -> Self {
+        Self {
+            threshold: None,
+            path: String::new(),
+            count: 0,
+        }
+    }
+
+    /// Record the outcome of the handler for a settings path.
+    ///
+    /// # Note
+    /// Once the number of consecutive errors on the same path reaches the threshold, every
+    /// further error is logged as an error.
+    ///
+    /// # Args
+    /// * `path` - The settings path that was updated.
+    /// * `error` - The error returned by the handler, if any.
+    fn record(&mut self, path: &str, error: Option<&str>) {
+        let error = match error {
+            Some(error) => error,
+            None => {
+                self.count = 0;
+                return;
+            }
+        };
+
+        if self.path != path {
+            self.path.clear();
+            self.count = 0;
+
+            // Paths that do not fit cannot be tracked, so their errors are never escalated.
+            if self.path.push_str(path).is_err() {
+                return;
+            }
+        }
+
+        self.count += 1;
+
+        if matches!(self.threshold, Some(threshold) if self.count >= threshold) {
+            log::error!(
+                "Settings update of `{}` rejected {} times in a row: {}",
+                path,
+                self.count,
+                error
+            );
+        }
+    }
+}
+
+/// A request to retrieve the values of all settings below a path.
+struct GetRequest<const MAX_TOPIC_LENGTH: usize> {
+    path: String<MAX_TOPIC_LENGTH>,
+    response_topic: String<MAX_TOPIC_LENGTH>,
+    state: [usize; MAX_RECURSION_DEPTH],
+}
+
+/// Tracks the settings paths changed since the client was constructed.
+///
+/// # Note
+/// Paths are tracked by name. If more distinct paths change than can be tracked, all settings
+/// paths are considered changed.
+struct ChangedPaths<const MAX_TOPIC_LENGTH: usize> {
+    changed: Vec<String<MAX_TOPIC_LENGTH>, MAX_TRACKED_PATHS>,
+    all: bool,
+}
+
+impl<const MAX_TOPIC_LENGTH: usize> ChangedPaths<MAX_TOPIC_LENGTH> {
+    fn new() -> Self {
+        Self {
+            changed: Vec::new(),
+            all: false,
+        }
+    }
+
+    /// Record the change of a settings path.
+    ///
+    /// # Args
+    /// * `path` - The settings path that was changed.
+    fn record(&mut self, path: &str) {
+        if self.all || self.changed.iter().any(|changed| changed == path) {
+            return;
+        }
+
+        let mut changed = String::new();
+        if changed.push_str(path).is_err() || self.changed.push(changed).is_err() {
+            log::warn!(
+                "Change of `{}` cannot be tracked, considering all paths changed",
+                path
+            );
+            self.record_all();
         }
     }
+
+    /// Record the change of all settings paths.
+    fn record_all(&mut self) {
+        self.all = true;
+        self.changed.clear();
+    }
 }
 
 /// MQTT settings interface.
-pub struct MqttClient<Settings, Stack, Clock, const MESSAGE_SIZE: usize>
-where
-    Settings: Miniconf + Clone,
+///
+/// # Note
+/// Besides the buffers of the MQTT connection, the interface holds two scratch buffers of
+/// `MESSAGE_SIZE` bytes each to serialize and encode settings values, e.g. for republishing and
+/// responses to get requests. Up to 4 deferred updates of `MESSAGE_SIZE` bytes each are held while
+/// paused or debouncing. `MESSAGE_SIZE` therefore dominates the RAM used by the interface.
+///
+/// # Template Arguments
+/// * `MESSAGE_SIZE` - The maximum size of MQTT messages that can be sent and received.
+/// * `MAX_TOPIC_LENGTH` - The maximum length of any MQTT topic, including the device prefix.
+/// * `MAX_CHUNKED_SIZE` - The maximum size of values transferred in chunks. Chunked transfers are
+///   disabled if zero.
+/// * `MAX_VALUE_SIZE` - The maximum size of republished settings values, including their CBOR
+///   encoding if enabled. Larger values are skipped. Defaults to `MESSAGE_SIZE`, which also bounds
+///   it.
+pub struct MqttClient<
+    Settings,
+    Stack,
+    Clock,
+    const MESSAGE_SIZE: usize,
+    const MAX_TOPIC_LENGTH: usize = 128,
+    const MAX_CHUNKED_SIZE: usize = 0,
+    const MAX_VALUE_SIZE: usize = MESSAGE_SIZE,
+> where
+    Settings: Miniconf,
     Stack: TcpClientStack,
     Clock: embedded_time::Clock,
 {
@@ -119,13 +715,68 @@ where
     settings: Settings,
     state: sm::StateMachine<sm::Context<Clock>>,
     settings_prefix: String<MAX_TOPIC_LENGTH>,
+    settings_separator: &'static str,
+    response_topic: String<MAX_TOPIC_LENGTH>,
     prefix: String<MAX_TOPIC_LENGTH>,
+    audit: bool,
+    snapshot: bool,
+    republish_on_connect: bool,
+    paused: Option<PauseBehavior>,
+    republish_completed: bool,
+    format: Format,
+    persistent_session: bool,
+    session_established: bool,
+    authorize: Option<fn(&str, &[minimq::Property]) -> bool>,
+    topic_mapper: Option<&'static dyn TopicMapper<MAX_TOPIC_LENGTH>>,
+    pre_set: Option<PreSet>,
+    reply_with_value: bool,
+    respond_with_value: bool,
+    factory_default: Option<fn() -> Settings>,
+    handler_errors: HandlerErrors<MAX_TOPIC_LENGTH>,
+    changed: ChangedPaths<MAX_TOPIC_LENGTH>,
+    stats: MqttStats,
+    pending_updates: Vec<(String<MAX_TOPIC_LENGTH>, Vec<u8, MESSAGE_SIZE>), MAX_PENDING_UPDATES>,
+    chunked: ChunkedTransfer<Clock, MAX_TOPIC_LENGTH, MAX_CHUNKED_SIZE>,
+    get_request: Option<GetRequest<MAX_TOPIC_LENGTH>>,
+    settings_subscribed: bool,
+    republish_base: Option<String<MAX_TOPIC_LENGTH>>,
+    ignore_unknown_paths: bool,
+    allowed_paths: Option<&'static [&'static str]>,
+    live: Option<Settings>,
+    uncommitted: bool,
+    client_id: String<64>,
+    structured_liveness: bool,
+    schema_hash: Option<u32>,
+    reject_schema_mismatch: bool,
+    schema_mismatch: bool,
+    debounce: Option<embedded_time::duration::Milliseconds<u32>>,
+    debounce_deadline: Option<embedded_time::Instant<Clock>>,
+    /// Scratch buffers shared by the message handlers.
+    buffers: [[u8; MESSAGE_SIZE]; 2],
+    #[cfg(feature = "alloc")]
+    publisher: Option<Publisher<Settings>>,
 }
 
-impl<Settings, Stack, Clock, const MESSAGE_SIZE: usize>
-    MqttClient<Settings, Stack, Clock, MESSAGE_SIZE>
+impl<
+        Settings,
+        Stack,
+        Clock,
+        const MESSAGE_SIZE: usize,
+        const MAX_TOPIC_LENGTH: usize,
+        const MAX_CHUNKED_SIZE: usize,
+        const MAX_VALUE_SIZE: usize,
+    >
+    MqttClient<
+        Settings,
+        Stack,
+        Clock,
+        MESSAGE_SIZE,
+        MAX_TOPIC_LENGTH,
+        MAX_CHUNKED_SIZE,
+        MAX_VALUE_SIZE,
+    >
 where
-    Settings: Miniconf + Clone,
+    Settings: Miniconf,
     Stack: TcpClientStack,
     Clock: embedded_time::Clock + Clone,
 {
@@ -138,6 +789,11 @@ where
     /// * `broker` - The IP address of the MQTT broker to use.
     /// * `clock` - The clock for managing the MQTT connection.
     /// * `settings` - The initial settings values.
+    ///
+    /// # Returns
+    /// The settings interface, or an error if the prefix is not valid within topics, if the
+    /// client ID is invalid, if the settings topics do not fit within `MAX_TOPIC_LENGTH` or
+    /// cannot be published within `MESSAGE_SIZE`, or if the settings are nested too deeply.
     pub fn new(
         stack: Stack,
         client_id: &str,
@@ -145,8 +801,35 @@ where
         broker: IpAddr,
         clock: Clock,
         settings: Settings,
-    ) -> Result<Self, minimq::Error<Stack::Error>> {
-        let mut mqtt = minimq::Minimq::new(broker, client_id, stack, clock.clone())?;
+    ) -> Result<Self, ConstructionError<Stack::Error>> {
+        // Wildcards would render the settings subscription malformed.
+        if prefix.contains(['+', '#', '\0']) {
+            return Err(ConstructionError::InvalidPrefix);
+        }
+
+        // The client ID is not used in any topic, but it is embedded into the structured liveness
+        // payload.
+        if client_id.contains('\0') {
+            return Err(ConstructionError::InvalidClientId);
+        }
+        let client_id: String<64> =
+            String::from_str(client_id).map_err(|_| ConstructionError::InvalidClientId)?;
+
+        // All other topics of the client are shorter than the longest settings topic.
+        let max_topic_length =
+            prefix.len() + "/settings/".len() + settings.get_metadata().max_topic_size;
+        check_topic_length::<_, MESSAGE_SIZE, MAX_TOPIC_LENGTH>(max_topic_length)?;
+
+        // Iteration over the settings paths requires an index for every level of the settings.
+        let depth = settings.get_metadata().max_depth;
+        if depth > MAX_RECURSION_DEPTH {
+            return Err(ConstructionError::SettingsTooDeep {
+                depth,
+                max: MAX_RECURSION_DEPTH,
+            });
+        }
+
+        let mut mqtt = minimq::Minimq::new(broker, &client_id, stack, clock.clone())?;
 
         // Note(unwrap): The client was just created, so it's valid to set a keepalive interval
         // now, since we're not yet connected to the broker.
@@ -167,19 +850,57 @@ where
             )
             .unwrap();
 
+        // Note(unwrap): The topics were checked to fit above.
         let mut settings_prefix: String<MAX_TOPIC_LENGTH> = String::from(prefix);
         settings_prefix.push_str("/settings").unwrap();
 
-        assert!(
-            settings_prefix.len() + 1 + settings.get_metadata().max_topic_size <= MAX_TOPIC_LENGTH
-        );
+        let mut response_topic: String<MAX_TOPIC_LENGTH> = String::from(prefix);
+        response_topic.push_str("/response").unwrap();
 
         Ok(Self {
             mqtt,
-            state: sm::StateMachine::new(sm::Context::new(clock)),
+            state: sm::StateMachine::new(sm::Context::new(clock.clone())),
             settings,
             settings_prefix,
+            settings_separator: "/",
+            response_topic,
             prefix: String::from(prefix),
+            audit: false,
+            snapshot: false,
+            republish_on_connect: true,
+            paused: None,
+            republish_completed: false,
+            format: Format::Json,
+            persistent_session: false,
+            session_established: false,
+            authorize: None,
+            topic_mapper: None,
+            pre_set: None,
+            reply_with_value: false,
+            respond_with_value: false,
+            factory_default: None,
+            handler_errors: HandlerErrors::new(),
+            changed: ChangedPaths::new(),
+            stats: MqttStats::default(),
+            pending_updates: Vec::new(),
+            chunked: ChunkedTransfer::new(clock),
+            get_request: None,
+            settings_subscribed: false,
+            republish_base: None,
+            ignore_unknown_paths: false,
+            allowed_paths: None,
+            live: None,
+            uncommitted: false,
+            client_id,
+            structured_liveness: false,
+            schema_hash: None,
+            reject_schema_mismatch: false,
+            schema_mismatch: false,
+            debounce: None,
+            debounce_deadline: None,
+            buffers: [[0; MESSAGE_SIZE]; 2],
+            #[cfg(feature = "alloc")]
+            publisher: None,
         })
     }
 
@@ -188,101 +909,444 @@ where
             return;
         }
 
+        // A snapshot is only attempted at the start of a republish. If it fails, the settings are
+        // republished individually instead.
+        if self.snapshot && self.state.context().republish_count == 0 && self.publish_snapshot() {
+            self.complete_republish();
+            return;
+        }
+
+        // The scratch buffers are reused for all settings, limited to the size of republished
+        // values.
+        let size = MAX_VALUE_SIZE.min(MESSAGE_SIZE);
+        let [data, encoded] = &mut self.buffers;
+        let (data, encoded) = (&mut data[..size], &mut encoded[..size]);
+
+        let topic_mapper = self.topic_mapper;
+        let mut iterated = 0;
+        let mut complete = true;
+        // Note(unwrap): The settings were checked to fit the topic and iteration state when the
+        // client was constructed.
         for topic in self
             .settings
             .into_iter::<MAX_TOPIC_LENGTH>(&mut self.state.context_mut().republish_state)
             .unwrap()
         {
-            let mut data = [0; MESSAGE_SIZE];
+            iterated += 1;
 
-            // Note(unwrap): We know this topic exists already because we just got it from the
-            // iterator.
-            let len = self.settings.get(&topic, &mut data).unwrap();
+            // Only the subtree requested by `republish_subtree()`, if any, is republished.
+            if let Some(base) = &self.republish_base {
+                if !in_subtree(&topic, base) {
+                    continue;
+                }
+            }
 
-            let mut prefixed_topic: String<MAX_TOPIC_LENGTH> = String::new();
-            write!(&mut prefixed_topic, "{}/{}", &self.settings_prefix, &topic).unwrap();
+            if !is_allowed(self.allowed_paths, &topic) {
+                continue;
+            }
 
-            // Note(unwrap): This should not fail because `can_publish()` was checked before
-            // attempting this publish.
-            self.mqtt
-                .client
-                .publish(
-                    &prefixed_topic,
-                    &data[..len],
-                    QoS::AtMostOnce,
-                    Retain::NotRetained,
-                    &[],
-                )
-                .unwrap();
+            // Settings that do not fit within a single message cannot be republished.
+            let len = match self.settings.get(&topic, data) {
+                Ok(len) => len,
+                Err(err) => {
+                    log::warn!(
+                        "Skipping republish of `{}`: value does not fit within {} bytes ({:?})",
+                        &topic,
+                        size,
+                        err
+                    );
+                    self.stats.dropped_settings += 1;
+                    continue;
+                }
+            };
+
+            let value = match self.format.encode(&data[..len], encoded) {
+                Ok(value) => value,
+                Err(err) => {
+                    log::warn!("Failed to encode `{}` for republish: {:?}", &topic, err);
+                    self.stats.dropped_settings += 1;
+                    continue;
+                }
+            };
+
+            let prefixed_topic =
+                settings_topic(&self.settings_prefix, self.settings_separator, &topic)
+                    .and_then(|topic| map_to_broker(topic_mapper, topic));
+
+            let prefixed_topic = match prefixed_topic {
+                Some(topic) => topic,
+                None => {
+                    log::error!("Topic too long to republish `{}`", &topic);
+                    self.stats.dropped_settings += 1;
+                    continue;
+                }
+            };
+
+            // Note: `can_publish()` was checked before attempting this publish, so it can only fail
+            // if the topic and value do not fit within a single message.
+            if let Err(err) = self.mqtt.client.publish(
+                &prefixed_topic,
+                value,
+                QoS::AtMostOnce,
+                Retain::NotRetained,
+                &[],
+            ) {
+                log::warn!("Failed to republish `{}`: {:?}", &topic, err);
+                self.stats.dropped_settings += 1;
+            }
 
             // If we can't publish any more messages, bail out now to prevent the iterator from
             // progressing. If we don't bail out now, we'd silently drop a setting.
             if !self.mqtt.client.can_publish(QoS::AtMostOnce) {
-                return;
+                complete = false;
+                break;
             }
         }
 
-        // If we got here, we completed iterating over the topics and published them all.
+        self.state.context_mut().republish_count += iterated;
+
+        // If we got here without bailing out, we completed iterating over the topics and published
+        // them all.
+        if complete {
+            self.complete_republish();
+        }
+    }
+
+    fn complete_republish(&mut self) {
+        self.republish_base = None;
         self.state
             .process_event(sm::Events::RepublishComplete)
             .unwrap();
-    }
+        self.republish_completed = true;
+        self.stats.republishes += 1;
 
-    fn handle_subscription(&mut self) {
-        log::info!("MQTT connected, subscribing to settings");
+        // The broker session now holds the settings subscription and the published settings.
+        self.session_established = true;
+    }
 
-        // Note(unwrap): We construct a string with two more characters than the prefix
-        // structure, so we are guaranteed to have space for storage.
-        let mut settings_topic: String<MAX_TOPIC_LENGTH> =
-            String::from(self.settings_prefix.as_str());
-        settings_topic.push_str("/#").unwrap();
+    /// Publish all settings as a single snapshot object.
+    ///
+    /// # Returns
+    /// True if the snapshot was published.
+    fn publish_snapshot(&mut self) -> bool {
+        let [data, _] = &mut self.buffers;
+        let len = match self
+            .settings
+            .serialize_json_object::<MAX_TOPIC_LENGTH, MAX_RECURSION_DEPTH>(data)
+        {
+            Ok(len) => len,
+            Err(err) => {
+                log::warn!("Failed to serialize settings snapshot: {:?}", err);
+                return false;
+            }
+        };
 
-        if self.mqtt.client.subscribe(&settings_topic, &[]).is_ok() {
-            self.state.process_event(sm::Events::Subscribed).unwrap();
-        }
-    }
+        let topic_mapper = self.topic_mapper;
+        let topic = settings_topic(&self.settings_prefix, self.settings_separator, "snapshot")
+            .and_then(|topic| map_to_broker(topic_mapper, topic));
 
-    fn handle_indicating_alive(&mut self) {
-        // Publish a connection status message.
-        let mut connection_topic: String<MAX_TOPIC_LENGTH> = String::from(self.prefix.as_str());
-        connection_topic.push_str("/alive").unwrap();
+        let topic = match topic {
+            Some(topic) => topic,
+            None => {
+                log::error!("Topic too long to publish settings snapshot");
+                return false;
+            }
+        };
 
-        if self
+        // Note: `can_publish()` was checked before attempting this publish, so it can only fail
+        // if the snapshot does not fit within a single message.
+        match self
             .mqtt
             .client
-            .publish(
-                &connection_topic,
-                "1".as_bytes(),
-                QoS::AtMostOnce,
-                Retain::Retained,
-                &[],
-            )
-            .is_ok()
+            .publish(&topic, &data[..len], QoS::AtMostOnce, Retain::Retained, &[])
         {
-            self.state.process_event(sm::Events::IndicatedLife).unwrap();
+            Ok(()) => true,
+            Err(err) => {
+                log::warn!("Failed to publish settings snapshot: {:?}", err);
+                false
+            }
         }
     }
 
-    /// Update the MQTT interface and service the network. Pass any settings changes to the handler
-    /// supplied.
-    ///
-    /// # Args
-    /// * `handler` - A closure called with updated settings that can be used to apply current
-    ///   settings or validate the configuration. Arguments are (path, old_settings, new_settings).
-    ///
-    /// # Example
-    /// ```rust
-    /// #[derive(miniconf::Miniconf, Clone)]
-    /// struct Settings {
-    ///     threshold: u32,
-    /// }
-    ///
-    /// # let mut client: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
-    /// #     std_embedded_nal::Stack::default(),
-    /// #     "",
-    /// #     "sample/prefix",
-    /// #     "127.0.0.1".parse().unwrap(),
-    /// #     std_embedded_time::StandardClock::default(),
+    fn reset(&mut self) {
+        // Only resets of an established connection are counted, as the client resets repeatedly
+        // while disconnected.
+        if !matches!(self.state.state(), sm::States::Initial) {
+            self.stats.reconnects += 1;
+        }
+
+        // Requests in progress cannot be answered after the connection was lost.
+        self.get_request = None;
+        self.settings_subscribed = false;
+        self.republish_base = None;
+
+        // Note(unwrap): It's always safe to reset.
+        self.state.process_event(sm::Events::Reset).unwrap();
+    }
+
+    fn handle_get_request(&mut self) {
+        let GetRequest {
+            path: base,
+            response_topic,
+            state,
+        } = match self.get_request.as_mut() {
+            Some(request) => request,
+            None => return,
+        };
+
+        if !self.mqtt.client.can_publish(QoS::AtMostOnce) {
+            return;
+        }
+
+        let format = self.format;
+        // Note(unwrap): The settings were checked to fit the topic and iteration state when the
+        // client was constructed.
+        for path in self.settings.into_iter::<MAX_TOPIC_LENGTH>(state).unwrap() {
+            let requested = match path.strip_prefix(base.as_str()) {
+                Some(rest) => base.is_empty() || rest.is_empty() || rest.starts_with('/'),
+                None => false,
+            };
+
+            if !requested || !is_allowed(self.allowed_paths, &path) {
+                continue;
+            }
+
+            let [value, json] = &mut self.buffers;
+            let len = match self.settings.get(&path, value) {
+                Ok(len) => len,
+                Err(err) => {
+                    log::warn!("Failed to retrieve `{}`: {:?}", &path, err);
+                    continue;
+                }
+            };
+
+            // The value is no longer needed once it is wrapped, so its buffer is reused for the
+            // encoded payload.
+            let payload = path_value(&path, &value[..len], json)
+                .and_then(|json| format.encode(json, value).ok());
+
+            let published = match payload {
+                Some(payload) => self
+                    .mqtt
+                    .client
+                    .publish(
+                        response_topic,
+                        payload,
+                        QoS::AtMostOnce,
+                        Retain::NotRetained,
+                        &[],
+                    )
+                    .is_ok(),
+                None => false,
+            };
+
+            if !published {
+                log::warn!("Failed to publish value of `{}`", &path);
+            }
+
+            // If we can't publish any more messages, bail out now to prevent the iterator from
+            // progressing. The remaining values are published during the next update.
+            if !self.mqtt.client.can_publish(QoS::AtMostOnce) {
+                return;
+            }
+        }
+
+        // All values were published, so the request is concluded.
+        if let Some(request) = self.get_request.take() {
+            let response = MqttMessage::new(
+                &[],
+                &request.response_topic,
+                &SettingsResponse::ok(),
+                format,
+            );
+            self.mqtt
+                .client
+                .publish(
+                    response.topic,
+                    &response.message,
+                    QoS::AtMostOnce,
+                    Retain::NotRetained,
+                    &[],
+                )
+                .ok();
+        }
+    }
+
+    fn handle_subscription(&mut self) {
+        if self.persistent_session && self.session_established {
+            log::info!("MQTT session resumed, skipping settings subscription and republish");
+            self.state
+                .process_event(sm::Events::SessionResumed)
+                .unwrap();
+            return;
+        }
+
+        log::info!("MQTT connected, subscribing to settings");
+
+        // The wildcard only fails to fit if the settings have no members, so the settings prefix
+        // itself is subscribed to instead.
+        let settings_topic = suffixed(&self.settings_prefix, "/#").unwrap_or_else(|| {
+            log::warn!("Settings wildcard topic too long, subscribing to the settings prefix");
+            self.settings_prefix.clone()
+        });
+
+        let settings_topic = match map_to_broker(self.topic_mapper, settings_topic) {
+            Some(topic) => topic,
+            None => {
+                log::error!("Settings topic cannot be mapped to the broker");
+                return;
+            }
+        };
+
+        if !self.settings_subscribed {
+            if self.mqtt.client.subscribe(&settings_topic, &[]).is_err() {
+                return;
+            }
+
+            self.settings_subscribed = true;
+        }
+
+        let get_topic = suffixed(&self.prefix, "/get/#")
+            .and_then(|topic| map_to_broker(self.topic_mapper, topic));
+
+        // Settings can still be managed if get requests cannot be subscribed to.
+        match get_topic {
+            Some(topic) => {
+                if self.mqtt.client.subscribe(&topic, &[]).is_err() {
+                    return;
+                }
+            }
+            None => log::error!("Get topic cannot be subscribed to"),
+        }
+
+        if self.schema_hash.is_some() {
+            let expected_topic = suffixed(&self.prefix, "/schema/expected")
+                .and_then(|topic| map_to_broker(self.topic_mapper, topic));
+
+            match expected_topic {
+                Some(topic) => {
+                    if self.mqtt.client.subscribe(&topic, &[]).is_err() {
+                        return;
+                    }
+                }
+                None => log::error!("Expected schema topic cannot be subscribed to"),
+            }
+        }
+
+        self.state
+            .process_event(sm::Events::SubscriptionsRequested)
+            .unwrap();
+    }
+
+    fn handle_subscription_ack(&mut self) {
+        // Settings published before the broker acknowledged the subscription may be missed.
+        if self.mqtt.client.subscriptions_pending() {
+            return;
+        }
+
+        log::info!("MQTT settings subscription acknowledged");
+
+        if self.republish_on_connect {
+            self.state.process_event(sm::Events::Subscribed).unwrap();
+        } else {
+            self.state
+                .process_event(sm::Events::SubscribedWithoutRepublish)
+                .unwrap();
+
+            // The broker session now holds the settings subscription.
+            self.session_established = true;
+        }
+    }
+
+    /// Construct the payload of the liveness topic.
+    ///
+    /// # Args
+    /// * `online` - Specifies whether the device is connected.
+    fn liveness(&self, online: bool) -> Vec<u8, MAX_LIVENESS_SIZE> {
+        // Note(unwrap): The bare payloads are a single byte.
+        let bare = || Vec::from_slice(if online { b"1" } else { b"0" }).unwrap();
+        if !self.structured_liveness {
+            return bare();
+        }
+
+        let liveness = Liveness {
+            id: &self.client_id,
+            online,
+        };
+        serde_json_core::to_vec(&liveness).unwrap_or_else(|_| {
+            log::warn!("Structured liveness payload too large, using bare payload");
+            bare()
+        })
+    }
+
+    fn handle_indicating_alive(&mut self) {
+        // Publish a connection status message.
+        let mut connection_topic: String<MAX_TOPIC_LENGTH> = String::from(self.prefix.as_str());
+        connection_topic.push_str("/alive").unwrap();
+
+        let liveness = self.liveness(true);
+        if self
+            .mqtt
+            .client
+            .publish(
+                &connection_topic,
+                &liveness,
+                QoS::AtMostOnce,
+                Retain::Retained,
+                &[],
+            )
+            .is_ok()
+        {
+            self.state.process_event(sm::Events::IndicatedLife).unwrap();
+            self.publish_schema_hash();
+        }
+    }
+
+    /// Publish the hash of the settings structure, if enabled.
+    fn publish_schema_hash(&mut self) {
+        let Some(hash) = self.schema_hash else {
+            return;
+        };
+
+        // Note(unwrap): The schema topics were checked to fit when the check was enabled.
+        let topic: String<MAX_TOPIC_LENGTH> = suffixed(&self.prefix, "/schema").unwrap();
+
+        let mut payload: String<8> = String::new();
+        // Note(unwrap): The hash is formatted as 8 hexadecimal digits.
+        write!(&mut payload, "{:08x}", hash).unwrap();
+
+        self.mqtt
+            .client
+            .publish(
+                &topic,
+                payload.as_bytes(),
+                QoS::AtMostOnce,
+                Retain::Retained,
+                &[],
+            )
+            .ok();
+    }
+
+    /// Update the MQTT interface and service the network. Pass any settings changes to the handler
+    /// supplied.
+    ///
+    /// # Args
+    /// * `handler` - A closure called with updated settings that can be used to apply current
+    ///   settings or validate the configuration. Arguments are (path, old_settings, new_settings).
+    ///
+    /// # Example
+    /// ```rust
+    /// #[derive(miniconf::Miniconf, Clone)]
+    /// struct Settings {
+    ///     threshold: u32,
+    /// }
+    ///
+    /// # let mut client: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+    /// #     std_embedded_nal::Stack::default(),
+    /// #     "",
+    /// #     "sample/prefix",
+    /// #     "127.0.0.1".parse().unwrap(),
+    /// #     std_embedded_time::StandardClock::default(),
     /// #     Settings { threshold: 0 },
     /// # )
     /// # .unwrap();
@@ -299,114 +1363,418 @@ where
     /// }).unwrap();
     /// ```
     ///
+    /// # Note
+    /// Updates rejected by the handler are not committed. The handler is expected to leave the
+    /// current settings unchanged when returning an error.
+    ///
+    /// The updated settings are a clone of the current settings, which requires the settings to
+    /// implement `Clone`. See [MqttClient::handled_update_in_place] otherwise.
+    ///
     /// # Returns
-    /// True if the settings changed. False otherwise.
+    /// True if the handler accepted any settings change. False otherwise.
     pub fn handled_update<F, E>(&mut self, handler: F) -> Result<bool, minimq::Error<Stack::Error>>
     where
+        Settings: Clone,
+        F: FnMut(&str, &mut Settings, &Settings) -> Result<(), E>,
+        E: AsRef<str>,
+    {
+        Ok(self.poll(handler)? == PollOutcome::Changed)
+    }
+
+    /// Update the MQTT interface until it is connected, subscribed and done republishing settings.
+    ///
+    /// # Note
+    /// This encapsulates the common pattern of spinning on [MqttClient::handled_update] during
+    /// startup or in tests. The interface is polled continuously without yielding, so it should
+    /// not be used where other work must proceed concurrently.
+    ///
+    /// # Args
+    /// * `handler` - The handler of settings updates received while waiting, see
+    ///   [MqttClient::handled_update].
+    /// * `timeout` - The maximum duration to wait for, measured using the clock of the client.
+    ///
+    /// # Returns
+    /// [ActivationError::Timeout] if the interface did not become active within the timeout. The
+    /// error of the most recent failed connection attempt, if any, is reported by
+    /// [MqttClient::stats].
+    pub fn block_until_active<F, E>(
+        &mut self,
+        mut handler: F,
+        timeout: embedded_time::duration::Seconds<u32>,
+    ) -> Result<(), ActivationError<Stack::Error>>
+    where
+        Settings: Clone,
+        F: FnMut(&str, &mut Settings, &Settings) -> Result<(), E>,
+        E: AsRef<str>,
+    {
+        let deadline = self.state.context().now() + timeout;
+
+        while !matches!(self.state.state(), sm::States::Active) {
+            if self.state.context().now() > deadline {
+                return Err(ActivationError::Timeout);
+            }
+
+            match self.handled_update(&mut handler) {
+                Ok(_) => {}
+                // Failed connection attempts are retried until the timeout elapses.
+                Err(_) if !self.mqtt.client.is_connected() => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update the MQTT interface and service the network, reporting whether any settings messages
+    /// were received.
+    ///
+    /// # Note
+    /// This behaves like [MqttClient::handled_update], but distinguishes polls that did not
+    /// receive any settings messages, e.g. to sleep longer while idle.
+    ///
+    /// # Args
+    /// * `handler` - A closure called with updated settings that can be used to apply current
+    ///   settings or validate the configuration, as in [MqttClient::handled_update].
+    ///
+    /// # Returns
+    /// The outcome of the poll.
+    pub fn poll<F, E>(&mut self, handler: F) -> Result<PollOutcome, minimq::Error<Stack::Error>>
+    where
+        Settings: Clone,
         F: FnMut(&str, &mut Settings, &Settings) -> Result<(), E>,
         E: AsRef<str>,
     {
+        self.poll_handler(Validated(handler))
+    }
+
+    /// Update the settings from the network stack, applying updates directly to the settings.
+    ///
+    /// # Note
+    /// Unlike [MqttClient::handled_update], updates are not applied to a clone of the settings
+    /// for validation, so the settings do not need to implement `Clone`. Instead, the handler is
+    /// called with the path, the current settings and the received value before each update is
+    /// applied, and rejects the update by returning an error. Accepted updates are then
+    /// deserialized directly into the settings.
+    ///
+    /// The handler can thus only validate the received value, e.g. against bounds or allowed
+    /// values, and not the updated settings as a whole. An update cannot be rolled back once it
+    /// was applied. Values that fail to deserialize are rejected without changing the settings.
+    /// Factory resets are validated with an empty path and a `null` value.
+    ///
+    /// # Args
+    /// * `handler` - A closure called with the path, the current settings and the value of every
+    ///   update before it is applied.
+    ///
+    /// # Returns
+    /// True if any settings were changed. False otherwise.
+    pub fn handled_update_in_place<F, E>(
+        &mut self,
+        handler: F,
+    ) -> Result<bool, minimq::Error<Stack::Error>>
+    where
+        F: FnMut(&str, &Settings, &[u8]) -> Result<(), E>,
+        E: AsRef<str>,
+    {
+        Ok(self.poll_handler(InPlace(handler))? == PollOutcome::Changed)
+    }
+
+    /// Update the MQTT interface and service the network, reporting errors of the handler as JSON
+    /// objects.
+    ///
+    /// # Note
+    /// This behaves like [MqttClient::handled_update], but errors returned by the handler are
+    /// serialized into the `error` member of the response, e.g.
+    /// `{"code":255,"msg":"Rejected","error":{"code":3,"field":"gain"}}`. Errors that do not
+    /// serialize within 64 bytes are reported without the `error` member.
+    ///
+    /// # Args
+    /// * `handler` - A closure called with updated settings that can be used to apply current
+    ///   settings or validate the configuration, as in [MqttClient::handled_update].
+    ///
+    /// # Returns
+    /// True if the handler accepted any settings change. False otherwise.
+    pub fn handled_update_serde<F, E>(
+        &mut self,
+        mut handler: F,
+    ) -> Result<bool, minimq::Error<Stack::Error>>
+    where
+        Settings: Clone,
+        F: FnMut(&str, &mut Settings, &Settings) -> Result<(), E>,
+        E: Serialize,
+    {
+        let outcome = self.poll_handler(Validated(
+            |path: &str, old_settings: &mut Settings, new_settings: &Settings| {
+                handler(path, old_settings, new_settings).map_err(Structured)
+            },
+        ))?;
+
+        Ok(outcome == PollOutcome::Changed)
+    }
+
+    fn poll_handler<U: Updater<Settings>>(
+        &mut self,
+        mut updater: U,
+    ) -> Result<PollOutcome, minimq::Error<Stack::Error>> {
+        // Republish completion is only indicated for the poll in which it occurred.
+        self.republish_completed = false;
+
         if !self.mqtt.client.is_connected() {
-            // Note(unwrap): It's always safe to reset.
-            self.state.process_event(sm::Events::Reset).unwrap();
+            self.reset();
         }
 
-        match self.state.state() {
-            &sm::States::Initial => {
+        match *self.state.state() {
+            sm::States::Initial => {
                 if self.mqtt.client.is_connected() {
                     self.state.process_event(sm::Events::Connected).unwrap();
                 }
             }
-            &sm::States::ConnectedToBroker => self.handle_indicating_alive(),
-            &sm::States::PendingSubscribe => self.handle_subscription(),
-            &sm::States::PendingRepublish => {
+            sm::States::ConnectedToBroker => self.handle_indicating_alive(),
+            sm::States::PendingSubscribe => self.handle_subscription(),
+            sm::States::PendingSubscriptionAck => self.handle_subscription_ack(),
+            sm::States::PendingRepublish => {
                 if self.state.context().republish_has_timed_out() {
                     self.state
                         .process_event(sm::Events::StartRepublish)
                         .unwrap();
                 }
             }
-            &sm::States::RepublishingSettings => self.handle_republish(),
+            sm::States::RepublishingSettings => self.handle_republish(),
+
+            // Settings are periodically republished from the active state, if enabled.
+            sm::States::Active => {
+                if self.state.context().republish_has_timed_out() {
+                    self.state
+                        .process_event(sm::Events::StartRepublish)
+                        .unwrap();
+                }
+            }
+        }
 
-            // Nothing to do in the active state.
-            &sm::States::Active => {}
+        self.chunked.handle_timeout();
+
+        if self.mqtt.client.is_connected() {
+            self.handle_get_request();
+        }
+
+        // Debounced updates are applied once the window has elapsed.
+        let debouncing = self
+            .debounce_deadline
+            .is_some_and(|deadline| self.state.context().now() <= deadline);
+
+        // Updates deferred while paused are applied once the client has been resumed.
+        let pending = self.paused.is_none() && !debouncing && !self.pending_updates.is_empty();
+        if pending {
+            self.debounce_deadline = None;
         }
+        let applied = pending && self.handle_pending_updates(&mut updater);
 
         // All states must handle MQTT traffic.
-        self.handle_mqtt_traffic(handler)
+        let outcome = match self.handle_mqtt_traffic(updater)? {
+            _ if applied => PollOutcome::Changed,
+            PollOutcome::NoMessage if pending => PollOutcome::Unchanged,
+            outcome => outcome,
+        };
+
+        if outcome == PollOutcome::Changed {
+            // With deferred commits, updates only become live once they are committed.
+            if self.live.is_some() {
+                self.uncommitted = true;
+            } else {
+                self.publish_to_readers();
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Publish the current settings to all readers, if any.
+    fn publish_to_readers(&self) {
+        #[cfg(feature = "alloc")]
+        if let Some(publisher) = &self.publisher {
+            publisher.publish(&self.settings);
+        }
     }
 
-    fn handle_mqtt_traffic<F, E>(
+    /// Update the settings from the network stack, collecting the paths of all changed settings.
+    ///
+    /// # Note
+    /// A single update may process multiple settings messages. The path of every update accepted
+    /// by the handler is pushed into `out`.
+    ///
+    /// # Args
+    /// * `handler` - A closure called with updated settings that can be used to apply current
+    ///   settings or validate the configuration, as in [MqttClient::handled_update].
+    /// * `out` - The buffer to push the paths of changed settings into.
+    ///
+    /// # Returns
+    /// True if the settings changed. False otherwise.
+    pub fn handled_update_collecting<F, E, const M: usize>(
         &mut self,
         mut handler: F,
-    ) -> Result<bool, minimq::Error<Stack::Error>>
+        out: &mut Vec<String<MAX_TOPIC_LENGTH>, M>,
+    ) -> Result<bool, CollectError<Stack::Error>>
     where
+        Settings: Clone,
         F: FnMut(&str, &mut Settings, &Settings) -> Result<(), E>,
         E: AsRef<str>,
     {
-        let mut settings = &mut self.settings;
-        let mqtt = &mut self.mqtt;
-        let prefix = self.settings_prefix.as_str();
+        let mut overflow = false;
+        let updated = self.handled_update(|path, old_settings, new_settings| {
+            let result = handler(path, old_settings, new_settings);
 
-        let mut response_topic: String<MAX_TOPIC_LENGTH> = String::from(self.prefix.as_str());
-        response_topic.push_str("/log").unwrap();
-        let default_response_topic = response_topic.as_str();
+            // Note: The handler is only called for valid settings paths, which are checked to fit
+            // within `MAX_TOPIC_LENGTH` when the client is constructed.
+            if result.is_ok() && out.push(String::from(path)).is_err() {
+                overflow = true;
+            }
 
-        let mut updated = false;
-        match mqtt.poll(|client, topic, message, properties| {
-            let path = match topic.strip_prefix(prefix) {
-                // For paths, we do not want to include the leading slash.
-                Some(path) => {
-                    if !path.is_empty() {
-                        &path[1..]
-                    } else {
-                        path
-                    }
-                }
-                None => {
-                    info!("Unexpected MQTT topic: {}", topic);
-                    return;
-                }
-            };
+            result
+        })?;
 
-            let mut new_settings = settings.clone();
-            let message: SettingsResponse =
-                match new_settings.string_set(path.split('/').peekable(), message) {
-                    Ok(_) => {
-                        updated = true;
-                        handler(&path, &mut settings, &new_settings).into()
-                    }
-                    err => {
-                        let mut msg = String::new();
-                        if write!(&mut msg, "{:?}", err).is_err() {
-                            msg = String::from("Configuration Error");
-                        }
+        if overflow {
+            return Err(CollectError::PathBufferFull);
+        }
 
-                        SettingsResponse::error(msg)
-                    }
-                };
+        Ok(updated)
+    }
+
+    fn handle_pending_updates<U: Updater<Settings>>(&mut self, updater: &mut U) -> bool {
+        let log_topic: Option<String<MAX_TOPIC_LENGTH>> = suffixed(&self.prefix, "/log");
+
+        let mut updated = false;
+        for (path, value) in core::mem::take(&mut self.pending_updates).iter() {
+            let mut committed = false;
+            let message = apply_update(
+                &mut self.settings,
+                updater,
+                &mut self.handler_errors,
+                path,
+                value,
+                &mut committed,
+            );
+
+            updated |= committed;
+            if committed {
+                self.changed.record(path);
+            }
 
-            let response = MqttMessage::new(properties, default_response_topic, &message);
+            let mut json = [0; MAX_RESPONSE_VALUE_SIZE];
+            let value = if self.respond_with_value && committed {
+                committed_value(&self.settings, path, &mut json)
+            } else {
+                None
+            };
 
-            client
+            // The properties of the original request are no longer available, so the outcome of
+            // the deferred update is reported on the default response topic.
+            let response = match value {
+                Some(value) => MqttMessage::new(
+                    &[],
+                    &self.response_topic,
+                    &message.with_value(path, value),
+                    self.format,
+                ),
+                None => MqttMessage::new(&[], &self.response_topic, &message, self.format),
+            };
+            self.mqtt
+                .client
                 .publish(
                     response.topic,
                     &response.message,
-                    // TODO: When Minimq supports more QoS levels, this should be increased to
-                    // ensure that the client has received it at least once.
                     QoS::AtMostOnce,
                     Retain::NotRetained,
                     &response.properties,
                 )
                 .ok();
-        }) {
-            Ok(_) => Ok(updated),
+
+            if self.audit {
+                match &log_topic {
+                    Some(topic) => {
+                        self.mqtt
+                            .client
+                            .publish(
+                                topic,
+                                message.audit(path).as_bytes(),
+                                QoS::AtMostOnce,
+                                Retain::NotRetained,
+                                &[],
+                            )
+                            .ok();
+                    }
+                    None => log::warn!("Audit topic too long, skipping audit line"),
+                }
+            }
+        }
+
+        updated
+    }
+
+    fn handle_mqtt_traffic<U: Updater<Settings>>(
+        &mut self,
+        updater: U,
+    ) -> Result<PollOutcome, minimq::Error<Stack::Error>> {
+        let mut handler = MessageHandler {
+            settings: &mut self.settings,
+            updater,
+            handler_errors: &mut self.handler_errors,
+            changed: &mut self.changed,
+            chunked: &mut self.chunked,
+            pending_updates: &mut self.pending_updates,
+            get_request: &mut self.get_request,
+            schema_mismatch: &mut self.schema_mismatch,
+            debounce_deadline: &mut self.debounce_deadline,
+            buffers: &mut self.buffers,
+            prefix: &self.prefix,
+            settings_prefix: &self.settings_prefix,
+            settings_separator: self.settings_separator,
+            response_topic: &self.response_topic,
+            paused: self.paused,
+            format: self.format,
+            authorize: self.authorize,
+            allowed_paths: self.allowed_paths,
+            topic_mapper: self.topic_mapper,
+            pre_set: self.pre_set,
+            reply_with_value: self.reply_with_value,
+            respond_with_value: self.respond_with_value,
+            factory_default: self.factory_default,
+            audit: self.audit,
+            snapshot: self.snapshot,
+            ignore_unknown_paths: self.ignore_unknown_paths,
+            debounce: self.debounce,
+            now: self.state.context().now(),
+            schema_hash: self.schema_hash,
+            reject_schema_mismatch: self.reject_schema_mismatch,
+            updated: false,
+            reset: false,
+        };
+
+        let mut received = false;
+        let result = self.mqtt.poll(|client, topic, message, properties| {
+            received = true;
+            handler.handle(client, topic, message, properties);
+        });
+
+        let (updated, reset) = (handler.updated, handler.reset);
+
+        match result {
+            Ok(_) if reset => {
+                // All settings may have changed, so they are republished.
+                self.force_republish();
+                Ok(PollOutcome::Changed)
+            }
+            Ok(_) if updated => Ok(PollOutcome::Changed),
+            Ok(_) if received => Ok(PollOutcome::Unchanged),
+            Ok(_) => Ok(PollOutcome::NoMessage),
             Err(minimq::Error::SessionReset) => {
                 log::warn!("Settings MQTT session reset");
-                self.state.process_event(sm::Events::Reset).unwrap();
-                Ok(false)
+                self.session_established = false;
+                self.reset();
+                Ok(PollOutcome::NoMessage)
+            }
+            Err(other) => {
+                self.stats.last_error_kind.replace((&other).into());
+                Err(other)
             }
-            Err(other) => Err(other),
         }
     }
 
@@ -414,24 +1782,1726 @@ where
     ///
     /// # Returns
     /// True if the settings changed. False otherwise
-    pub fn update(&mut self) -> Result<bool, minimq::Error<Stack::Error>> {
+    pub fn update(&mut self) -> Result<bool, minimq::Error<Stack::Error>>
+    where
+        Settings: Clone,
+    {
         self.handled_update(|_, old, new| {
             *old = new.clone();
             Result::<(), &'static str>::Ok(())
         })
     }
 
+    /// Get the paths of all settings changed since the client was constructed.
+    ///
+    /// # Note
+    /// Paths are reported once, in the order they were first changed. Changes are tracked for up
+    /// to 32 distinct paths. If more paths changed, or after a factory reset, all settings paths
+    /// currently present are reported instead.
+    ///
+    /// # Args
+    /// * `out` - The buffer to append the changed paths to.
+    pub fn changed_paths<const M: usize>(
+        &self,
+        out: &mut Vec<String<MAX_TOPIC_LENGTH>, M>,
+    ) -> Result<(), CollectError<Stack::Error>> {
+        if !self.changed.all {
+            return out
+                .extend_from_slice(&self.changed.changed)
+                .map_err(|_| CollectError::PathBufferFull);
+        }
+
+        let mut state = [0; MAX_RECURSION_DEPTH];
+
+        // Note(unwrap): The settings were checked to fit the topic and iteration state when the
+        // client was constructed.
+        let paths = self
+            .settings
+            .into_iter::<MAX_TOPIC_LENGTH>(&mut state)
+            .unwrap();
+
+        for path in paths {
+            if out.push(path).is_err() {
+                return Err(CollectError::PathBufferFull);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pause the application of settings updates.
+    ///
+    /// # Note
+    /// While paused, the MQTT connection is still serviced, but settings updates are deferred
+    /// according to the provided behavior.
+    ///
+    /// # Args
+    /// * `behavior` - Specifies whether updates received while paused are queued or rejected.
+    pub fn pause(&mut self, behavior: PauseBehavior) {
+        self.paused.replace(behavior);
+    }
+
+    /// Resume the application of settings updates.
+    ///
+    /// # Note
+    /// Any updates queued while paused are applied during the next call to
+    /// [MqttClient::handled_update].
+    pub fn resume(&mut self) {
+        self.paused.take();
+    }
+
     /// Get the current settings from miniconf.
+    ///
+    /// # Note
+    /// If commits are deferred, these are the settings of the most recent
+    /// [MqttClient::commit], see [MqttClient::deferred_commit].
     pub fn settings(&self) -> &Settings {
-        &self.settings
+        self.live.as_ref().unwrap_or(&self.settings)
     }
 
-    /// Force republication of the current settings.
+    /// Make all updates accepted since the previous commit live.
     ///
     /// # Note
-    /// This is intended to be used if modification of a setting had side effects that affected
-    /// another setting.
-    pub fn force_republish(&mut self) {
-        self.state.process_event(sm::Events::StartRepublish).ok();
+    /// This has no effect unless commits are deferred, see [MqttClient::deferred_commit].
+    pub fn commit(&mut self)
+    where
+        Settings: Clone,
+    {
+        if !core::mem::take(&mut self.uncommitted) {
+            return;
+        }
+
+        if let Some(live) = self.live.as_mut() {
+            live.clone_from(&self.settings);
+        }
+
+        self.publish_to_readers();
+    }
+
+    /// Get a handle to read the settings without borrowing the client.
+    ///
+    /// # Note
+    /// The handle observes the settings committed by the most recent update, so that the settings
+    /// can be read from other execution contexts, e.g. interrupts, without blocking updates of the
+    /// client. Readers share a copy of the settings that is allocated on the first call.
+    ///
+    /// Updates are published to readers once the handler accepted them, i.e. after the update of
+    /// the client returned, or once they are committed if commits are deferred. See
+    /// [SettingsReader] for the consistency guarantees.
+    #[cfg(feature = "alloc")]
+    pub fn reader(&mut self) -> SettingsReader<Settings>
+    where
+        Settings: Copy,
+    {
+        let settings = &self.settings;
+        self.publisher
+            .get_or_insert_with(|| Publisher::new(settings))
+            .reader()
+    }
+
+    /// Specify the format of settings values and responses transmitted over MQTT.
+    ///
+    /// # Note
+    /// The format is used consistently for received settings values, responses and republished
+    /// settings. Defaults to [Format::Json].
+    ///
+    /// # Args
+    /// * `format` - The format to use.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Specify whether the client relies on a persistent MQTT session across reconnects.
+    ///
+    /// # Note
+    /// When enabled and the broker preserved the session of the client across a reconnect, the
+    /// settings subscription is retained by the broker and the settings are not republished. If
+    /// the session was reset, the client subscribes and republishes as usual. A fixed client ID
+    /// is required for the broker to associate the reconnect with the previous session. Defaults
+    /// to false.
+    ///
+    /// # Args
+    /// * `persistent` - Specifies whether a preserved session is reused.
+    pub fn persistent_session(mut self, persistent: bool) -> Self {
+        self.persistent_session = persistent;
+        self
+    }
+
+    /// Specify a function to authorize settings updates.
+    ///
+    /// # Note
+    /// The function is called with the path and the MQTT properties of every received settings
+    /// update before it is applied. This allows authorization based on MQTT user properties,
+    /// e.g. an authentication token. Updates that are not authorized are rejected with a
+    /// `Forbidden` response. By default, all updates are authorized.
+    ///
+    /// # Args
+    /// * `authorize` - Returns true if the update of the path is authorized.
+    pub fn authorize(mut self, authorize: fn(&str, &[minimq::Property]) -> bool) -> Self {
+        self.authorize.replace(authorize);
+        self
+    }
+
+    /// Specify a translation between the settings topics of the client and the broker topics.
+    ///
+    /// # Note
+    /// This allows topic rewriting required by specific brokers, e.g. an inserted organization
+    /// prefix. The mapped topics must fit within `MAX_TOPIC_LENGTH`. By default, topics are used
+    /// on the broker unchanged.
+    ///
+    /// # Args
+    /// * `mapper` - The translation of settings topics.
+    pub fn topic_mapper(mut self, mapper: &'static dyn TopicMapper<MAX_TOPIC_LENGTH>) -> Self {
+        self.topic_mapper.replace(mapper);
+        self
+    }
+
+    /// Specify the topic segment that settings paths are placed behind.
+    ///
+    /// # Note
+    /// Settings paths are placed behind `<prefix>/<root>/`. Other topics of the client, e.g. the
+    /// liveness topic, are unaffected. Defaults to `settings`.
+    ///
+    /// # Args
+    /// * `root` - The topic segment of the settings, without any `/`.
+    ///
+    /// # Returns
+    /// The settings interface, or an error if the longest settings topic does not fit within
+    /// `MAX_TOPIC_LENGTH` or cannot be published within `MESSAGE_SIZE`.
+    pub fn settings_root(mut self, root: &str) -> Result<Self, ConstructionError<Stack::Error>> {
+        let max_topic_length = self.prefix.len()
+            + 1
+            + root.len()
+            + self.settings_separator.len()
+            + self.settings.get_metadata().max_topic_size;
+        check_topic_length::<_, MESSAGE_SIZE, MAX_TOPIC_LENGTH>(max_topic_length)?;
+
+        // Note(unwrap): The topic was checked to fit above.
+        self.settings_prefix.clear();
+        write!(&mut self.settings_prefix, "{}/{}", self.prefix, root).unwrap();
+        Ok(self)
+    }
+
+    /// Specify the separator between the settings prefix and settings paths.
+    ///
+    /// # Note
+    /// Settings paths are placed behind `<prefix>/settings<separator>`, e.g. `<prefix>/settings//`
+    /// for a separator of `//`. The separator is used both to publish settings and to extract the
+    /// paths of received settings topics, which are ignored unless they use the separator.
+    /// Defaults to `/`.
+    ///
+    /// # Args
+    /// * `separator` - The separator, which must start with `/` so that all settings topics are
+    ///   covered by the wildcard subscription below the settings prefix.
+    ///
+    /// # Returns
+    /// The settings interface, or an error if the separator does not start with `/`, or if the
+    /// longest settings topic does not fit within `MAX_TOPIC_LENGTH` or cannot be published within
+    /// `MESSAGE_SIZE`.
+    pub fn settings_separator(
+        mut self,
+        separator: &'static str,
+    ) -> Result<Self, ConstructionError<Stack::Error>> {
+        if !separator.starts_with('/') {
+            return Err(ConstructionError::InvalidSeparator);
+        }
+
+        let max_topic_length = self.settings_prefix.len()
+            + separator.len()
+            + self.settings.get_metadata().max_topic_size;
+        check_topic_length::<_, MESSAGE_SIZE, MAX_TOPIC_LENGTH>(max_topic_length)?;
+
+        self.settings_separator = separator;
+        Ok(self)
+    }
+
+    /// Specify a function to rewrite settings values before they are applied.
+    ///
+    /// # Note
+    /// The function is called with the path and the value of every received settings update,
+    /// e.g. to convert units centrally. It may write a rewritten value into the provided scratch
+    /// buffer of `MESSAGE_SIZE` bytes, which is owned by the client, and return its length. The
+    /// received value is applied unchanged if it returns None. Updates are rejected if the
+    /// returned length exceeds the scratch buffer. Values of chunked transfers are not rewritten.
+    /// By default, values are applied unchanged.
+    ///
+    /// # Args
+    /// * `pre_set` - Returns the length of the rewritten value in the scratch buffer, if any.
+    pub fn pre_set(mut self, pre_set: fn(&str, &[u8], &mut [u8]) -> Option<usize>) -> Self {
+        self.pre_set.replace(pre_set);
+        self
+    }
+
+    /// Specify the topic that responses to settings requests are published to.
+    ///
+    /// # Note
+    /// Responses are published to `<prefix><suffix>` unless the request provides a response
+    /// topic. Defaults to `/response`.
+    ///
+    /// If the response topic does not fit within `MAX_TOPIC_LENGTH`, a warning is logged and the
+    /// previous response topic is kept.
+    ///
+    /// # Args
+    /// * `suffix` - The suffix of the response topic, including the leading `/`.
+    pub fn response_topic_suffix(mut self, suffix: &str) -> Self {
+        match suffixed(&self.prefix, suffix) {
+            Some(topic) => self.response_topic = topic,
+            None => log::warn!(
+                "Response topic too long, keeping `{}`",
+                self.response_topic.as_str()
+            ),
+        }
+        self
+    }
+
+    /// Specify whether human-readable audit lines are published for settings requests.
+    ///
+    /// # Note
+    /// When enabled, the outcome of every settings request is additionally published as text to
+    /// `<prefix>/log`, separate from the responses consumed by requestors. Defaults to false.
+    ///
+    /// # Args
+    /// * `audit` - Specifies whether audit lines are published.
+    pub fn audit_log(mut self, audit: bool) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// Specify whether settings are republished as a single snapshot.
+    ///
+    /// # Note
+    /// When enabled, republishing the settings (e.g. after connecting or using
+    /// [MqttClient::force_republish]) serializes all settings into a single nested JSON object
+    /// and publishes it retained to `<prefix>/settings/snapshot`, instead of publishing each
+    /// path individually. The snapshot is always JSON, independent of the configured [Format].
+    /// If the snapshot does not fit within `MESSAGE_SIZE`, the settings are republished
+    /// individually instead. While enabled, a setting named `snapshot` cannot be addressed.
+    /// Defaults to false.
+    ///
+    /// # Args
+    /// * `snapshot` - Specifies whether settings are republished as a snapshot.
+    pub fn republish_snapshot(mut self, snapshot: bool) -> Self {
+        self.snapshot = snapshot;
+        self
+    }
+
+    /// Specify whether the liveness of the device is published as structured JSON.
+    ///
+    /// # Note
+    /// When enabled, `<prefix>/alive` and its will carry `{"id":"<client_id>","online":true}` and
+    /// `{"id":"<client_id>","online":false}` instead of `1` and `0`, so that the device can be
+    /// identified without parsing the topic. The ID is the client ID provided to
+    /// [MqttClient::new], which is empty if the broker assigns the ID. Defaults to false.
+    ///
+    /// # Args
+    /// * `structured` - Specifies whether the liveness is published as structured JSON.
+    ///
+    /// # Returns
+    /// The settings interface, or an error if the will does not fit within `MESSAGE_SIZE`.
+    pub fn structured_liveness(
+        mut self,
+        structured: bool,
+    ) -> Result<Self, ConstructionError<Stack::Error>> {
+        self.structured_liveness = structured;
+
+        // Note(unwrap): The liveness topic fits as checked at construction.
+        let mut connection_topic: String<MAX_TOPIC_LENGTH> = String::from(self.prefix.as_str());
+        connection_topic.push_str("/alive").unwrap();
+
+        let will = self.liveness(false);
+        self.mqtt.client.set_will(
+            &connection_topic,
+            &will,
+            QoS::AtMostOnce,
+            Retain::Retained,
+            &[],
+        )?;
+        Ok(self)
+    }
+
+    /// Publish the hash of the settings structure and check it against the expected hash.
+    ///
+    /// # Note
+    /// The hash of the settings, see [Miniconf::schema_hash], is published as 8 hexadecimal
+    /// digits to the retained `<prefix>/schema` topic once connected. The hash expected by the
+    /// management plane is received in the same format from `<prefix>/schema/expected`, which is
+    /// typically retained. If the hashes differ, a warning is published to
+    /// `<prefix>/schema/warning`. This guards against settings shaped for a different firmware
+    /// version. The hash is computed once, so it does not reflect later changes of the settings
+    /// present, e.g. of options. Disabled by default.
+    ///
+    /// # Args
+    /// * `version` - The version of the settings schema, see [Miniconf::schema_hash].
+    /// * `reject` - Specifies whether settings updates are rejected while the hashes differ, until
+    ///   a matching hash is received or the mismatch is acknowledged using
+    ///   [MqttClient::acknowledge_schema_mismatch].
+    ///
+    /// # Returns
+    /// The settings interface, or [ConstructionError::TopicTooLong] if the schema topics do not
+    /// fit within `MAX_TOPIC_LENGTH`.
+    pub fn schema_check(
+        mut self,
+        version: u32,
+        reject: bool,
+    ) -> Result<Self, ConstructionError<Stack::Error>> {
+        if suffixed::<MAX_TOPIC_LENGTH>(&self.prefix, "/schema/expected").is_none() {
+            return Err(ConstructionError::TopicTooLong);
+        }
+
+        // Note(unwrap): The settings paths and depth were checked to fit on construction.
+        let hash = self
+            .settings
+            .schema_hash::<MAX_TOPIC_LENGTH, MAX_RECURSION_DEPTH>(version)
+            .unwrap();
+
+        self.schema_hash.replace(hash);
+        self.reject_schema_mismatch = reject;
+        Ok(self)
+    }
+
+    /// Accept settings updates despite a mismatch of the expected schema hash.
+    ///
+    /// # Note
+    /// This has no effect unless updates are rejected on mismatch, see
+    /// [MqttClient::schema_check]. A subsequently received mismatching hash rejects updates
+    /// again.
+    pub fn acknowledge_schema_mismatch(&mut self) {
+        self.schema_mismatch = false;
+    }
+
+    /// Determine if the expected schema hash differs from the hash of the settings.
+    ///
+    /// # Note
+    /// The mismatch persists until a matching hash is received or it is acknowledged, see
+    /// [MqttClient::schema_check].
+    pub fn schema_mismatch(&self) -> bool {
+        self.schema_mismatch
+    }
+
+    /// Coalesce rapid successive settings updates.
+    ///
+    /// # Note
+    /// When enabled, updates are queued as if the client was paused with [PauseBehavior::Queue]
+    /// and acknowledged with a `Queued` response. Once the window has elapsed since the first
+    /// queued update, only the latest value received for each path is applied and its outcome is
+    /// reported on the default response topic. This smooths storms of updates to the same path,
+    /// e.g. generated by dragging a slider, so that the handler runs once per path and window.
+    /// Updates are never rejected: updates to further paths while the queue is full are applied
+    /// immediately. Chunked transfers and factory resets are not debounced. Disabled by default.
+    ///
+    /// # Args
+    /// * `window` - The duration to coalesce updates for.
+    pub fn debounce(mut self, window: embedded_time::duration::Milliseconds<u32>) -> Self {
+        self.debounce.replace(window);
+        self
+    }
+
+    /// Specify whether settings are republished after connecting to the broker.
+    ///
+    /// # Note
+    /// When disabled, the client becomes active immediately after subscribing to the settings
+    /// without publishing any settings values. Settings can still be republished using
+    /// [MqttClient::force_republish]. Defaults to true.
+    ///
+    /// # Args
+    /// * `republish` - Specifies whether settings are republished after connecting.
+    pub fn republish_on_connect(mut self, republish: bool) -> Self {
+        self.republish_on_connect = republish;
+        self
+    }
+
+    /// Periodically republish all settings.
+    ///
+    /// # Note
+    /// While the client is active, all settings are republished once the interval has elapsed
+    /// since the previous republication completed. This keeps retained settings alive on brokers
+    /// that expire retained messages. Disabled by default.
+    ///
+    /// # Args
+    /// * `interval` - The interval between republications.
+    pub fn periodic_republish(mut self, interval: embedded_time::duration::Seconds<u32>) -> Self {
+        self.state.context_mut().periodic_interval.replace(interval);
+        self
+    }
+
+    /// Specify whether committed values are sent to requestors correlating their requests.
+    ///
+    /// # Note
+    /// When enabled, requests providing both a response topic and correlation data are answered
+    /// with the committed value of the setting following the response. The correlation data is
+    /// echoed, so the requestor can confirm the value that was applied. Defaults to false.
+    ///
+    /// # Args
+    /// * `reply` - Specifies whether committed values are sent to the requestor.
+    pub fn reply_with_value(mut self, reply: bool) -> Self {
+        self.reply_with_value = reply;
+        self
+    }
+
+    /// Specify whether responses to successful updates include the committed value.
+    ///
+    /// # Note
+    /// When enabled, the response to every committed update additionally carries the path and
+    /// the serialized value of the setting after the handler applied it, e.g.
+    /// `{"code":0,"msg":"OK","path":"gain","value":"1.5"}`. This confirms the stored value if the
+    /// handler adjusted it. Values that do not fit within 64 bytes are omitted. Defaults to false
+    /// to keep responses small.
+    ///
+    /// # Args
+    /// * `respond` - Specifies whether responses include the committed value.
+    pub fn respond_with_value(mut self, respond: bool) -> Self {
+        self.respond_with_value = respond;
+        self
+    }
+
+    /// Specify whether accepted updates only become live once they are committed.
+    ///
+    /// # Note
+    /// When enabled, updates are applied to a shadow copy of the settings, which is passed to the
+    /// handler, republished and retrieved by requests. [MqttClient::settings] and readers keep
+    /// observing the live settings until [MqttClient::commit] is called at a point where the
+    /// application can safely switch to new settings. Multiple updates received before a commit
+    /// are coalesced and become live together. Defaults to false.
+    ///
+    /// # Args
+    /// * `defer` - Specifies whether commits are deferred.
+    pub fn deferred_commit(mut self, defer: bool) -> Self
+    where
+        Settings: Clone,
+    {
+        self.live = if defer {
+            Some(self.settings.clone())
+        } else {
+            None
+        };
+        self.uncommitted = false;
+        self
+    }
+
+    /// Restrict the settings served by the client to an allowlist of paths.
+    ///
+    /// # Note
+    /// Each entry allows the path itself and all paths below it, e.g. `dsp` allows `dsp/gain`.
+    /// Updates of other paths are rejected with a `Forbidden` response, and other paths are
+    /// neither republished nor retrieved by requests. This narrows the externally accessible
+    /// settings of a deployment independent of the settings structure. Snapshots published using
+    /// [MqttClient::republish_snapshot] are not restricted. By default, all paths are served.
+    ///
+    /// # Args
+    /// * `paths` - The settings paths to serve.
+    pub fn allowed_paths(mut self, paths: &'static [&'static str]) -> Self {
+        self.allowed_paths.replace(paths);
+        self
+    }
+
+    /// Specify whether messages to unknown settings paths are ignored.
+    ///
+    /// # Note
+    /// When enabled, messages published below the settings prefix to paths that do not exist in
+    /// the settings, e.g. retained metadata of other tools, are ignored without responding with
+    /// an error. Defaults to false.
+    ///
+    /// # Args
+    /// * `ignore` - Specifies whether messages to unknown paths are ignored.
+    pub fn ignore_unknown_paths(mut self, ignore: bool) -> Self {
+        self.ignore_unknown_paths = ignore;
+        self
+    }
+
+    /// Escalate repeated handler errors to be logged as errors.
+    ///
+    /// # Note
+    /// Once the handler rejected the given number of consecutive updates of the same path, every
+    /// further rejection of that path is logged with `log::error!` so that stuck
+    /// misconfigurations are visible. Disabled by default.
+    ///
+    /// # Args
+    /// * `count` - The number of consecutive errors on the same path to escalate after.
+    pub fn escalate_handler_errors(mut self, count: usize) -> Self {
+        self.handler_errors.threshold.replace(count);
+        self
+    }
+
+    /// Get mutable access to the underlying MQTT client for advanced control.
+    ///
+    /// # Note
+    /// Misuse of the MQTT client may interfere with the operation of the settings interface. For
+    /// example, calling `poll()` directly will consume settings requests and connection events
+    /// without the settings state machine observing them.
+    pub fn minimq_mut(&mut self) -> &mut minimq::Minimq<Stack, Clock, MESSAGE_SIZE, 1> {
+        &mut self.mqtt
+    }
+
+    /// Get diagnostic statistics of the MQTT connection.
+    pub fn stats(&self) -> MqttStats {
+        self.stats
+    }
+
+    /// Determine if the republication of settings completed during the most recent update.
+    ///
+    /// # Note
+    /// This is edge-triggered and only returns true for the single update in which all settings
+    /// were published. It can be used to signal readiness once the configuration is fully out.
+    pub fn just_finished_republish(&self) -> bool {
+        self.republish_completed
     }
+
+    /// Get the progress of the republication of settings.
+    ///
+    /// # Note
+    /// Progress increases monotonically while settings are republished and is also reported for
+    /// the update in which the republication completed, see
+    /// [MqttClient::just_finished_republish]. Settings that are skipped, e.g. because they lie
+    /// outside of the subtree of [MqttClient::republish_subtree], count towards the progress.
+    ///
+    /// # Returns
+    /// The number of settings paths handled so far and the total number of settings paths. None
+    /// if settings are not being republished.
+    pub fn republish_progress(&self) -> Option<(usize, usize)> {
+        let total = self.settings.get_metadata().path_count;
+
+        if self.republish_completed {
+            return Some((total, total));
+        }
+
+        match self.state.state() {
+            sm::States::RepublishingSettings => {
+                let count = self.state.context().republish_count;
+                Some((count.min(total), total))
+            }
+            _ => None,
+        }
+    }
+
+    /// Determine if the broker has acknowledged the settings subscription.
+    ///
+    /// # Note
+    /// Settings published before the subscription is acknowledged may not be received. This can
+    /// be used to wait for the client to be ready before publishing settings, e.g. in tests. A
+    /// subscription retained by a resumed persistent session is considered acknowledged.
+    pub fn is_subscribed(&self) -> bool {
+        matches!(
+            self.state.state(),
+            sm::States::PendingRepublish | sm::States::RepublishingSettings | sm::States::Active
+        )
+    }
+
+    /// Force republication of the current settings.
+    ///
+    /// # Note
+    /// This is intended to be used if modification of a setting had side effects that affected
+    /// another setting.
+    pub fn force_republish(&mut self) {
+        self.republish_base = None;
+        self.state.process_event(sm::Events::StartRepublish).ok();
+    }
+
+    /// Republish only the settings below a base path.
+    ///
+    /// # Note
+    /// This is narrower than [MqttClient::force_republish], e.g. to reduce broker traffic while
+    /// iterating on a part of the settings. A trailing wildcard is accepted, so `dsp`, `dsp/*`
+    /// and `dsp/#` all republish the settings below `dsp`. If a republication of all settings is
+    /// already pending or in progress, the subtree is covered by it and nothing else is done. In
+    /// snapshot mode, the complete snapshot is published.
+    ///
+    /// # Args
+    /// * `base` - The path of the root of the subtree to republish. Nothing is republished if it
+    ///   does not match any settings path.
+    pub fn republish_subtree(&mut self, base: &str) {
+        let base = base.trim_end_matches(['*', '#']);
+        let base = base.trim_end_matches('/');
+
+        if self.settings.subtree_metadata(base).is_err() {
+            log::warn!("No settings to republish below `{}`", base);
+            return;
+        }
+
+        let mut filter: String<MAX_TOPIC_LENGTH> = String::new();
+        if filter.push_str(base).is_err() {
+            log::warn!("No settings to republish below `{}`", base);
+            return;
+        }
+
+        let full_republish_pending = match self.state.state() {
+            sm::States::PendingRepublish => true,
+            sm::States::RepublishingSettings => self.republish_base.is_none(),
+            _ => false,
+        };
+
+        if full_republish_pending {
+            log::info!("Republish of all settings pending, including `{}`", base);
+            return;
+        }
+
+        // The filter only applies once the republish was started, so a republish after
+        // reconnecting is not restricted.
+        if self.state.process_event(sm::Events::StartRepublish).is_ok() {
+            self.republish_base.replace(filter);
+        }
+    }
+}
+
+impl<
+        Settings,
+        Stack,
+        Clock,
+        const MESSAGE_SIZE: usize,
+        const MAX_TOPIC_LENGTH: usize,
+        const MAX_CHUNKED_SIZE: usize,
+        const MAX_VALUE_SIZE: usize,
+    >
+    MqttClient<
+        Settings,
+        Stack,
+        Clock,
+        MESSAGE_SIZE,
+        MAX_TOPIC_LENGTH,
+        MAX_CHUNKED_SIZE,
+        MAX_VALUE_SIZE,
+    >
+where
+    Settings: Miniconf + Default,
+    Stack: TcpClientStack,
+    Clock: embedded_time::Clock + Clone,
+{
+    /// Specify whether the settings can be reset to their defaults via MQTT.
+    ///
+    /// # Note
+    /// When enabled, publishing `null` to `<prefix>/settings` restores the default settings. The
+    /// handler is called once for the reset with an empty path and the default settings, and the
+    /// settings are republished once the handler accepted them. Defaults to false.
+    ///
+    /// # Args
+    /// * `enable` - Specifies whether factory resets are accepted.
+    pub fn factory_reset(mut self, enable: bool) -> Self {
+        self.factory_default = if enable {
+            Some(Settings::default)
+        } else {
+            None
+        };
+        self
+    }
+}
+
+/// A strategy to validate and apply settings updates using the user-provided handler.
+trait Updater<Settings> {
+    /// The error of the handler rejecting an update.
+    type Error: HandlerError;
+
+    /// Validate and apply the value of a setting.
+    ///
+    /// # Returns
+    /// The result of the handler, or an error if the value could not be applied.
+    fn update(
+        &mut self,
+        settings: &mut Settings,
+        path: &str,
+        value: &[u8],
+    ) -> Result<Result<(), Self::Error>, crate::Error>;
+
+    /// Validate and apply the default settings.
+    fn reset(&mut self, settings: &mut Settings, defaults: Settings) -> Result<(), Self::Error>;
+}
+
+/// Passes updates applied to a clone of the settings to a handler applying them.
+struct Validated<F>(F);
+
+impl<Settings, F, E> Updater<Settings> for Validated<F>
+where
+    Settings: Miniconf + Clone,
+    F: FnMut(&str, &mut Settings, &Settings) -> Result<(), E>,
+    E: HandlerError,
+{
+    type Error = E;
+
+    fn update(
+        &mut self,
+        settings: &mut Settings,
+        path: &str,
+        value: &[u8],
+    ) -> Result<Result<(), E>, crate::Error> {
+        let mut new_settings = settings.clone();
+        new_settings.set(path, value)?;
+        Ok((self.0)(path, settings, &new_settings))
+    }
+
+    fn reset(&mut self, settings: &mut Settings, defaults: Settings) -> Result<(), E> {
+        (self.0)("", settings, &defaults)
+    }
+}
+
+/// Applies updates directly to the settings once a handler validated the received values.
+struct InPlace<F>(F);
+
+impl<Settings, F, E> Updater<Settings> for InPlace<F>
+where
+    Settings: Miniconf,
+    F: FnMut(&str, &Settings, &[u8]) -> Result<(), E>,
+    E: HandlerError,
+{
+    type Error = E;
+
+    fn update(
+        &mut self,
+        settings: &mut Settings,
+        path: &str,
+        value: &[u8],
+    ) -> Result<Result<(), E>, crate::Error> {
+        if let Err(err) = (self.0)(path, settings, value) {
+            return Ok(Err(err));
+        }
+
+        settings.set(path, value)?;
+        Ok(Ok(()))
+    }
+
+    fn reset(&mut self, settings: &mut Settings, defaults: Settings) -> Result<(), E> {
+        (self.0)("", settings, b"null")?;
+        *settings = defaults;
+        Ok(())
+    }
+}
+
+/// The client state used to handle messages received from the broker.
+///
+/// # Note
+/// Messages are handled while the MQTT client is polled, so the remaining client state is borrowed
+/// separately. The MQTT client is passed to the handlers to publish responses.
+struct MessageHandler<
+    'a,
+    Settings,
+    U,
+    Clock,
+    const MESSAGE_SIZE: usize,
+    const MAX_TOPIC_LENGTH: usize,
+    const MAX_CHUNKED_SIZE: usize,
+> where
+    Clock: embedded_time::Clock,
+{
+    settings: &'a mut Settings,
+    updater: U,
+    handler_errors: &'a mut HandlerErrors<MAX_TOPIC_LENGTH>,
+    changed: &'a mut ChangedPaths<MAX_TOPIC_LENGTH>,
+    chunked: &'a mut ChunkedTransfer<Clock, MAX_TOPIC_LENGTH, MAX_CHUNKED_SIZE>,
+    pending_updates:
+        &'a mut Vec<(String<MAX_TOPIC_LENGTH>, Vec<u8, MESSAGE_SIZE>), MAX_PENDING_UPDATES>,
+    get_request: &'a mut Option<GetRequest<MAX_TOPIC_LENGTH>>,
+    schema_mismatch: &'a mut bool,
+    debounce_deadline: &'a mut Option<embedded_time::Instant<Clock>>,
+    buffers: &'a mut [[u8; MESSAGE_SIZE]; 2],
+    prefix: &'a str,
+    settings_prefix: &'a str,
+    settings_separator: &'a str,
+    response_topic: &'a str,
+    paused: Option<PauseBehavior>,
+    format: Format,
+    authorize: Option<fn(&str, &[minimq::Property]) -> bool>,
+    allowed_paths: Option<&'static [&'static str]>,
+    topic_mapper: Option<&'static dyn TopicMapper<MAX_TOPIC_LENGTH>>,
+    pre_set: Option<PreSet>,
+    reply_with_value: bool,
+    respond_with_value: bool,
+    factory_default: Option<fn() -> Settings>,
+    audit: bool,
+    snapshot: bool,
+    ignore_unknown_paths: bool,
+    debounce: Option<embedded_time::duration::Milliseconds<u32>>,
+    now: embedded_time::Instant<Clock>,
+    schema_hash: Option<u32>,
+    reject_schema_mismatch: bool,
+    /// Set if any settings update was committed.
+    updated: bool,
+    /// Set if the settings were reset to their defaults.
+    reset: bool,
+}
+
+impl<
+        'a,
+        Settings,
+        U,
+        Clock,
+        const MESSAGE_SIZE: usize,
+        const MAX_TOPIC_LENGTH: usize,
+        const MAX_CHUNKED_SIZE: usize,
+    > MessageHandler<'a, Settings, U, Clock, MESSAGE_SIZE, MAX_TOPIC_LENGTH, MAX_CHUNKED_SIZE>
+where
+    Settings: Miniconf,
+    U: Updater<Settings>,
+    Clock: embedded_time::Clock,
+{
+    /// Handle a message received from the broker.
+    fn handle<Stack: TcpClientStack>(
+        &mut self,
+        client: &mut minimq::mqtt_client::MqttClient<Stack, Clock, MESSAGE_SIZE, 1>,
+        topic: &str,
+        message: &[u8],
+        properties: &[minimq::Property],
+    ) {
+        let mapped: String<MAX_TOPIC_LENGTH>;
+        let topic = match self.topic_mapper {
+            Some(mapper) => match mapper.from_broker(topic) {
+                Some(topic) => {
+                    mapped = topic;
+                    mapped.as_str()
+                }
+                None => {
+                    info!("Unexpected MQTT topic: {}", topic);
+                    return;
+                }
+            },
+            None => topic,
+        };
+
+        if self.handle_expected_schema(client, topic, message) {
+            return;
+        }
+
+        // Requests to retrieve settings are answered once all values are published.
+        let get_prefix: Option<String<MAX_TOPIC_LENGTH>> = suffixed(self.prefix, "/get");
+        if let Some(path) = get_prefix.as_deref().and_then(|get| get_path(topic, get)) {
+            self.handle_get(client, path, properties);
+            return;
+        }
+
+        match settings_path(topic, self.settings_prefix, self.settings_separator) {
+            Some(path) => self.handle_settings(client, path, message, properties),
+            None => info!("Unexpected MQTT topic: {}", topic),
+        }
+    }
+
+    /// Handle the schema hash announced by the management plane.
+    ///
+    /// # Returns
+    /// True if the message was published to the expected schema topic.
+    fn handle_expected_schema<Stack: TcpClientStack>(
+        &mut self,
+        client: &mut minimq::mqtt_client::MqttClient<Stack, Clock, MESSAGE_SIZE, 1>,
+        topic: &str,
+        message: &[u8],
+    ) -> bool {
+        let hash = match self.schema_hash {
+            Some(hash) => hash,
+            None => return false,
+        };
+
+        let expected_topic: Option<String<MAX_TOPIC_LENGTH>> =
+            suffixed(self.prefix, "/schema/expected");
+        if expected_topic.as_deref() != Some(topic) {
+            return false;
+        }
+
+        let expected = core::str::from_utf8(message)
+            .ok()
+            .and_then(|expected| u32::from_str_radix(expected.trim(), 16).ok());
+
+        match expected {
+            Some(expected) if expected == hash => *self.schema_mismatch = false,
+            Some(expected) => {
+                log::warn!(
+                    "Settings schema mismatch: expected {:08x}, found {:08x}",
+                    expected,
+                    hash
+                );
+                *self.schema_mismatch = true;
+
+                let mut warning: String<64> = String::new();
+                // Note(unwrap): The warning is shorter than 64 bytes.
+                write!(
+                    &mut warning,
+                    "Schema mismatch: expected {:08x}, found {:08x}",
+                    expected, hash
+                )
+                .unwrap();
+
+                let warning_topic: Option<String<MAX_TOPIC_LENGTH>> =
+                    suffixed(self.prefix, "/schema/warning");
+                if let Some(topic) = &warning_topic {
+                    client
+                        .publish(
+                            topic,
+                            warning.as_bytes(),
+                            QoS::AtMostOnce,
+                            Retain::NotRetained,
+                            &[],
+                        )
+                        .ok();
+                }
+            }
+            None => log::warn!("Invalid expected schema hash"),
+        }
+
+        true
+    }
+
+    /// Start a request to retrieve the values of all settings below `path`.
+    ///
+    /// # Note
+    /// The values are published by [MqttClient::handle_get_request] as the MQTT client permits.
+    fn handle_get<Stack: TcpClientStack>(
+        &mut self,
+        client: &mut minimq::mqtt_client::MqttClient<Stack, Clock, MESSAGE_SIZE, 1>,
+        path: &str,
+        properties: &[minimq::Property],
+    ) {
+        let response = if self.get_request.is_some() {
+            SettingsResponse::busy()
+        } else {
+            let (response_topic, _) = response_properties(properties, self.response_topic);
+            let mut topic: String<MAX_TOPIC_LENGTH> = String::new();
+            match (
+                self.settings.subtree_metadata(path),
+                topic.push_str(response_topic),
+            ) {
+                // Note: Valid settings paths fit within `MAX_TOPIC_LENGTH`.
+                (Ok(_), Ok(())) => {
+                    self.get_request.replace(GetRequest {
+                        path: String::from(path),
+                        response_topic: topic,
+                        state: [0; MAX_RECURSION_DEPTH],
+                    });
+                    return;
+                }
+                (Err(err), _) => SettingsResponse::describe(err),
+                (_, Err(_)) => SettingsResponse::error(String::from("Response topic too long")),
+            }
+        };
+
+        let response = MqttMessage::new(properties, self.response_topic, &response, self.format);
+        client
+            .publish(
+                response.topic,
+                &response.message,
+                QoS::AtMostOnce,
+                Retain::NotRetained,
+                &response.properties,
+            )
+            .ok();
+    }
+
+    /// Handle a message published to a settings path.
+    fn handle_settings<Stack: TcpClientStack>(
+        &mut self,
+        client: &mut minimq::mqtt_client::MqttClient<Stack, Clock, MESSAGE_SIZE, 1>,
+        path: &str,
+        message: &[u8],
+        properties: &[minimq::Property],
+    ) {
+        // The retained snapshot is published by the client itself.
+        if self.snapshot && path == "snapshot" {
+            return;
+        }
+
+        let request = self.chunked.parse(path);
+
+        // Chunked transfers address the path of the transferred setting.
+        let setting = match request {
+            Some(Request::Chunk { path, .. }) | Some(Request::Commit { path }) => path,
+            None => path,
+        };
+
+        // Messages to paths outside of the settings may be published by other tooling.
+        if self.ignore_unknown_paths
+            && !path.ends_with('*')
+            && matches!(
+                self.settings.subtree_metadata(setting),
+                Err(crate::Error::PathNotFound)
+            )
+        {
+            info!("Ignoring unknown settings path: {}", path);
+            return;
+        }
+
+        let mut committed = false;
+        let response =
+            match self.request(client, path, request, message, properties, &mut committed) {
+                Some(response) => response,
+                None => return,
+            };
+
+        self.respond(client, path, setting, &response, properties, committed);
+    }
+
+    /// Process a request published to a settings path.
+    ///
+    /// # Returns
+    /// The response to the request, or None if the request was already answered.
+    fn request<Stack: TcpClientStack>(
+        &mut self,
+        client: &mut minimq::mqtt_client::MqttClient<Stack, Clock, MESSAGE_SIZE, 1>,
+        path: &str,
+        request: Option<Request>,
+        message: &[u8],
+        properties: &[minimq::Property],
+        committed: &mut bool,
+    ) -> Option<SettingsResponse> {
+        // An empty message to a wildcard path requests all settings values below it.
+        if message.is_empty() && path.ends_with('*') {
+            return self.handle_wildcard(client, path, properties);
+        }
+
+        if self.reject_schema_mismatch && *self.schema_mismatch {
+            return Some(SettingsResponse::error(String::from("Schema mismatch")));
+        }
+
+        // A `null` value published to the settings prefix itself requests a reset.
+        if let Some(defaults) = self.factory_default.filter(|_| path.is_empty()) {
+            if matches!(
+                self.format.decode(message, &mut self.buffers[0]),
+                Ok(b"null")
+            ) {
+                return Some(self.handle_reset(defaults, properties, committed));
+            }
+        }
+
+        Some(self.handle_update(path, request, message, properties, committed))
+    }
+
+    /// Publish the values of all settings below a wildcard path.
+    ///
+    /// # Returns
+    /// An error response if the values could not be published, None otherwise.
+    fn handle_wildcard<Stack: TcpClientStack>(
+        &mut self,
+        client: &mut minimq::mqtt_client::MqttClient<Stack, Clock, MESSAGE_SIZE, 1>,
+        path: &str,
+        properties: &[minimq::Property],
+    ) -> Option<SettingsResponse> {
+        let base = path.trim_end_matches('*').trim_end_matches('/');
+        if !is_allowed(self.allowed_paths, base) {
+            return Some(SettingsResponse::forbidden());
+        }
+
+        let [json, buffer] = &mut *self.buffers;
+        match get_wildcard(self.settings, path, self.format, json, buffer) {
+            Ok(payload) => {
+                let (topic, properties) = response_properties(properties, self.response_topic);
+                client
+                    .publish(
+                        topic,
+                        payload,
+                        QoS::AtMostOnce,
+                        Retain::NotRetained,
+                        &properties,
+                    )
+                    .ok();
+                None
+            }
+            Err(response) => Some(response),
+        }
+    }
+
+    /// Restore the factory default settings.
+    fn handle_reset(
+        &mut self,
+        defaults: fn() -> Settings,
+        properties: &[minimq::Property],
+        committed: &mut bool,
+    ) -> SettingsResponse {
+        if !self.authorized("", properties) {
+            SettingsResponse::forbidden()
+        } else if self.paused.is_some() {
+            // The reset cannot be queued, as it does not update a single path.
+            SettingsResponse::busy()
+        } else {
+            let response = factory_reset(
+                self.settings,
+                defaults(),
+                &mut self.updater,
+                self.handler_errors,
+                committed,
+            );
+            self.reset |= *committed;
+            response
+        }
+    }
+
+    /// Apply, defer or reject an update of a single setting.
+    fn handle_update(
+        &mut self,
+        path: &str,
+        request: Option<Request>,
+        message: &[u8],
+        properties: &[minimq::Property],
+        committed: &mut bool,
+    ) -> SettingsResponse {
+        let authorized = match request {
+            Some(Request::Chunk { path, .. }) | Some(Request::Commit { path }) => {
+                self.authorized(path, properties)
+            }
+            None => self.authorized(path, properties),
+        };
+
+        match request {
+            Some(_) if !authorized => SettingsResponse::forbidden(),
+            Some(Request::Chunk { path, index }) => {
+                match self.chunked.receive(path, index, message) {
+                    Ok(()) => SettingsResponse::chunk_received(),
+                    Err(err) => SettingsResponse::error(String::from(err)),
+                }
+            }
+
+            // Committed values may exceed the message size, so they cannot be queued.
+            Some(Request::Commit { .. }) if self.paused.is_some() => SettingsResponse::busy(),
+            Some(Request::Commit { path }) => commit_update(
+                self.settings,
+                self.chunked,
+                &mut self.updater,
+                self.handler_errors,
+                path,
+                self.format,
+                committed,
+            ),
+            None => {
+                let [buffer, scratch] = &mut *self.buffers;
+                match self.format.decode(message, buffer) {
+                    Ok(_) if !authorized => SettingsResponse::forbidden(),
+                    Ok(value) => match rewrite(self.pre_set, path, value, scratch) {
+                        None => SettingsResponse::error(String::from("Rewritten value too long")),
+                        Some(value) => match (self.paused, self.debounce) {
+                            (Some(PauseBehavior::Reject), _) => SettingsResponse::busy(),
+                            (Some(PauseBehavior::Queue), _) => {
+                                if defer_update(self.pending_updates, path, value) {
+                                    SettingsResponse::queued()
+                                } else {
+                                    SettingsResponse::busy()
+                                }
+                            }
+                            // Debounced updates are coalesced until the window elapsed.
+                            (None, Some(window))
+                                if defer_update(self.pending_updates, path, value) =>
+                            {
+                                self.debounce_deadline.get_or_insert(self.now + window);
+                                SettingsResponse::queued()
+                            }
+                            // Updates that cannot be debounced, e.g. because too many paths are
+                            // pending, are applied immediately instead of being rejected.
+                            (None, _) => apply_update(
+                                self.settings,
+                                &mut self.updater,
+                                self.handler_errors,
+                                path,
+                                value,
+                                committed,
+                            ),
+                        },
+                    },
+                    Err(_) => SettingsResponse::error(String::from("Invalid payload encoding")),
+                }
+            }
+        }
+    }
+
+    /// Check if the requestor may update a settings path.
+    fn authorized(&self, path: &str, properties: &[minimq::Property]) -> bool {
+        is_allowed(self.allowed_paths, path)
+            && match self.authorize {
+                Some(authorize) => authorize(path, properties),
+                None => true,
+            }
+    }
+
+    /// Publish the response to a settings request and record committed changes.
+    fn respond<Stack: TcpClientStack>(
+        &mut self,
+        client: &mut minimq::mqtt_client::MqttClient<Stack, Clock, MESSAGE_SIZE, 1>,
+        path: &str,
+        setting: &str,
+        message: &SettingsResponse,
+        properties: &[minimq::Property],
+        committed: bool,
+    ) {
+        let mut json = [0; MAX_RESPONSE_VALUE_SIZE];
+        let value = if self.respond_with_value && committed {
+            committed_value(self.settings, setting, &mut json)
+        } else {
+            None
+        };
+
+        let response = match value {
+            Some(value) => MqttMessage::new(
+                properties,
+                self.response_topic,
+                &message.with_value(setting, value),
+                self.format,
+            ),
+            None => MqttMessage::new(properties, self.response_topic, message, self.format),
+        };
+
+        client
+            .publish(
+                response.topic,
+                &response.message,
+                // TODO: When Minimq supports more QoS levels, this should be increased to
+                // ensure that the client has received it at least once.
+                QoS::AtMostOnce,
+                Retain::NotRetained,
+                &response.properties,
+            )
+            .ok();
+
+        if self.audit {
+            let log_topic: Option<String<MAX_TOPIC_LENGTH>> = suffixed(self.prefix, "/log");
+            match &log_topic {
+                Some(topic) => {
+                    client
+                        .publish(
+                            topic,
+                            message.audit(path).as_bytes(),
+                            QoS::AtMostOnce,
+                            Retain::NotRetained,
+                            &[],
+                        )
+                        .ok();
+                }
+                None => log::warn!("Audit topic too long, skipping audit line"),
+            }
+        }
+
+        if !committed {
+            return;
+        }
+
+        self.updated = true;
+        // A factory reset is committed for the settings prefix itself and affects all settings
+        // paths.
+        if setting.is_empty() {
+            self.changed.record_all();
+        } else {
+            self.changed.record(setting);
+        }
+
+        // Requestors correlating their requests are additionally sent the committed value.
+        let correlated = properties
+            .iter()
+            .any(|prop| matches!(prop, minimq::Property::CorrelationData(_)))
+            && properties
+                .iter()
+                .any(|prop| matches!(prop, minimq::Property::ResponseTopic(_)));
+
+        if self.reply_with_value && correlated {
+            let [json, buffer] = &mut *self.buffers;
+            match get_value(self.settings, setting, self.format, json, buffer) {
+                Ok(value) => {
+                    client
+                        .publish(
+                            response.topic,
+                            value,
+                            QoS::AtMostOnce,
+                            Retain::NotRetained,
+                            &response.properties,
+                        )
+                        .ok();
+                }
+                Err(err) => log::warn!("Failed to reply with value of `{}`: {:?}", setting, err),
+            }
+        }
+    }
+}
+
+/// Apply a settings update using the user-provided handler.
+///
+/// # Args
+/// * `settings` - The current settings.
+/// * `updater` - The strategy to validate and apply the updated settings.
+/// * `handler_errors` - The tracker of consecutive handler errors.
+/// * `path` - The settings path to update.
+/// * `value` - The serialized value of the setting.
+/// * `updated` - Set to true if the update was accepted by the handler.
+///
+/// # Returns
+/// The response to report to the requestor.
+fn apply_update<Settings, U, const MAX_TOPIC_LENGTH: usize>(
+    settings: &mut Settings,
+    updater: &mut U,
+    handler_errors: &mut HandlerErrors<MAX_TOPIC_LENGTH>,
+    path: &str,
+    value: &[u8],
+    updated: &mut bool,
+) -> SettingsResponse
+where
+    U: Updater<Settings>,
+{
+    match updater.update(settings, path, value) {
+        Ok(result) => {
+            *updated |= result.is_ok();
+            let response = SettingsResponse::from(result);
+            handler_errors.record(path, response.error_description());
+            response
+        }
+        // Note: Failed updates are described as the result of setting the value.
+        Err(err) => SettingsResponse::describe(Err::<(), _>(err)),
+    }
+}
+
+/// Extract the settings path of a request to retrieve settings.
+///
+/// # Args
+/// * `topic` - The topic of the request.
+/// * `get_prefix` - The topic prefix of requests to retrieve settings.
+///
+/// # Returns
+/// The requested settings path, which is empty for all settings. None if the topic is not a
+/// request to retrieve settings.
+fn get_path<'a>(topic: &'a str, get_prefix: &str) -> Option<&'a str> {
+    let path = topic.strip_prefix(get_prefix)?;
+    if path.is_empty() {
+        return Some(path);
+    }
+
+    let path = path.strip_prefix('/')?;
+    Some(
+        path.strip_suffix('#')
+            .map(|path| path.strip_suffix('/').unwrap_or(path))
+            .unwrap_or(path),
+    )
+}
+
+/// Determine if a settings path lies within the subtree below a base path.
+fn in_subtree(path: &str, base: &str) -> bool {
+    base.is_empty()
+        || path
+            .strip_prefix(base)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Determine whether a settings path is served according to an optional allowlist.
+fn is_allowed(allowed: Option<&[&str]>, path: &str) -> bool {
+    match allowed {
+        Some(paths) => paths.iter().any(|base| in_subtree(path, base)),
+        None => true,
+    }
+}
+
+/// Serialize a settings value along with its path.
+///
+/// # Args
+/// * `path` - The settings path of the value.
+/// * `value` - The serialized JSON value.
+/// * `json` - The buffer to serialize into.
+///
+/// # Returns
+/// The JSON object of the path and value, or None if it does not fit into the buffer.
+fn path_value<'a>(path: &str, value: &[u8], json: &'a mut [u8]) -> Option<&'a [u8]> {
+    let mut len = 0;
+    for part in [
+        b"{\"path\":\"".as_slice(),
+        path.as_bytes(),
+        b"\",\"value\":",
+        value,
+        b"}",
+    ] {
+        json.get_mut(len..len + part.len())?.copy_from_slice(part);
+        len += part.len();
+    }
+
+    Some(&json[..len])
+}
+
+/// Check that a settings topic fits within the topic and message buffers of the client.
+///
+/// # Args
+/// * `topic_length` - The length of the longest settings topic.
+fn check_topic_length<E, const MESSAGE_SIZE: usize, const MAX_TOPIC_LENGTH: usize>(
+    topic_length: usize,
+) -> Result<(), ConstructionError<E>> {
+    if topic_length > MAX_TOPIC_LENGTH {
+        return Err(ConstructionError::TopicTooLong);
+    }
+
+    if topic_length + PUBLISH_OVERHEAD > MESSAGE_SIZE {
+        return Err(ConstructionError::MessageSizeTooSmall);
+    }
+
+    Ok(())
+}
+
+/// Translate a settings topic of the client into the topic used on the broker.
+///
+/// # Args
+/// * `mapper` - The translation of settings topics, if any.
+/// * `topic` - The settings topic of the client.
+///
+/// # Returns
+/// The topic used on the broker, or None if the topic cannot be translated.
+fn map_to_broker<const MAX_TOPIC_LENGTH: usize>(
+    mapper: Option<&dyn TopicMapper<MAX_TOPIC_LENGTH>>,
+    topic: String<MAX_TOPIC_LENGTH>,
+) -> Option<String<MAX_TOPIC_LENGTH>> {
+    match mapper {
+        Some(mapper) => mapper.to_broker(&topic),
+        None => Some(topic),
+    }
+}
+
+/// Construct the topic of a settings path.
+///
+/// # Note
+/// The empty path, e.g. of top-level enums, is published to the settings prefix itself.
+///
+/// # Args
+/// * `prefix` - The settings prefix.
+/// * `separator` - The separator between the settings prefix and the path.
+/// * `path` - The settings path.
+///
+/// # Returns
+/// The topic, or None if it does not fit within `MAX_TOPIC_LENGTH`.
+fn settings_topic<const MAX_TOPIC_LENGTH: usize>(
+    prefix: &str,
+    separator: &str,
+    path: &str,
+) -> Option<String<MAX_TOPIC_LENGTH>> {
+    if path.is_empty() {
+        return suffixed(prefix, "");
+    }
+
+    let mut topic: String<MAX_TOPIC_LENGTH> = suffixed(prefix, separator)?;
+    topic.push_str(path).ok()?;
+    Some(topic)
+}
+
+/// Extract the settings path from a settings topic.
+///
+/// # Note
+/// This is the inverse of [settings_topic].
+///
+/// # Args
+/// * `topic` - The received topic.
+/// * `prefix` - The settings prefix.
+/// * `separator` - The separator between the settings prefix and the path.
+///
+/// # Returns
+/// The settings path, or None if the topic is not a settings topic.
+fn settings_path<'a>(topic: &'a str, prefix: &str, separator: &str) -> Option<&'a str> {
+    let path = topic.strip_prefix(prefix)?;
+    if path.is_empty() {
+        Some(path)
+    } else {
+        path.strip_prefix(separator)
+    }
+}
+
+/// Construct a topic from a prefix and a suffix.
+///
+/// # Returns
+/// The topic, or None if it does not fit within `MAX_TOPIC_LENGTH`.
+fn suffixed<const MAX_TOPIC_LENGTH: usize>(
+    prefix: &str,
+    suffix: &str,
+) -> Option<String<MAX_TOPIC_LENGTH>> {
+    let mut topic = String::new();
+    topic.push_str(prefix).ok()?;
+    topic.push_str(suffix).ok()?;
+    Some(topic)
+}
+
+/// Rewrite a settings value using the user-provided function.
+///
+/// # Args
+/// * `pre_set` - The function to rewrite values, if any.
+/// * `path` - The settings path of the value.
+/// * `value` - The received value.
+/// * `scratch` - The buffer to write the rewritten value into.
+///
+/// # Returns
+/// The value to apply, or None if the reported length of the rewritten value exceeds the scratch
+/// buffer.
+fn rewrite<'a>(
+    pre_set: Option<PreSet>,
+    path: &str,
+    value: &'a [u8],
+    scratch: &'a mut [u8],
+) -> Option<&'a [u8]> {
+    match pre_set.and_then(|pre_set| pre_set(path, value, scratch)) {
+        Some(len) => scratch.get(..len),
+        None => Some(value),
+    }
+}
+
+/// Restore the default settings using the user-provided handler.
+///
+/// # Args
+/// * `settings` - The current settings.
+/// * `defaults` - The default settings.
+/// * `updater` - The strategy to validate and apply the updated settings.
+/// * `handler_errors` - The tracker of consecutive handler errors.
+/// * `updated` - Set to true if the reset was accepted by the handler.
+///
+/// # Returns
+/// The response to report to the requestor.
+fn factory_reset<Settings, U, const MAX_TOPIC_LENGTH: usize>(
+    settings: &mut Settings,
+    defaults: Settings,
+    updater: &mut U,
+    handler_errors: &mut HandlerErrors<MAX_TOPIC_LENGTH>,
+    updated: &mut bool,
+) -> SettingsResponse
+where
+    U: Updater<Settings>,
+{
+    let result = updater.reset(settings, defaults);
+    *updated |= result.is_ok();
+    let response = SettingsResponse::from(result);
+    handler_errors.record("", response.error_description());
+    response
+}
+
+/// Apply the value of a completed chunked transfer.
+///
+/// # Note
+/// The transfer is concluded regardless of the outcome of the update.
+///
+/// # Args
+/// * `settings` - The current settings.
+/// * `chunked` - The chunked transfer holding the value.
+/// * `updater` - The strategy to validate and apply the updated settings.
+/// * `handler_errors` - The tracker of consecutive handler errors.
+/// * `path` - The settings path to update.
+/// * `format` - The format of the transferred value.
+/// * `updated` - Set to true if the update was accepted by the handler.
+///
+/// # Returns
+/// The response to report to the requestor.
+fn commit_update<Settings, U, C, const MAX_TOPIC_LENGTH: usize, const SIZE: usize>(
+    settings: &mut Settings,
+    chunked: &mut ChunkedTransfer<C, MAX_TOPIC_LENGTH, SIZE>,
+    updater: &mut U,
+    handler_errors: &mut HandlerErrors<MAX_TOPIC_LENGTH>,
+    path: &str,
+    format: Format,
+    updated: &mut bool,
+) -> SettingsResponse
+where
+    U: Updater<Settings>,
+    C: embedded_time::Clock,
+{
+    let mut buffer = [0; SIZE];
+    let response = match chunked.value(path) {
+        Ok(value) => match format.decode(value, &mut buffer) {
+            Ok(value) => apply_update(settings, updater, handler_errors, path, value, updated),
+            Err(_) => SettingsResponse::error(String::from("Invalid payload encoding")),
+        },
+        Err(err) => SettingsResponse::error(String::from(err)),
+    };
+
+    chunked.abort();
+    response
+}
+
+/// Retrieve the value of a setting.
+///
+/// # Args
+/// * `settings` - The settings to retrieve the value from.
+/// * `path` - The path of the setting.
+/// * `format` - The format to encode the value in.
+/// * `json` - A buffer to serialize the value into.
+/// * `buffer` - A buffer to store the encoded value in, if necessary.
+///
+/// # Returns
+/// The encoded value.
+fn get_value<'a, Settings: Miniconf>(
+    settings: &Settings,
+    path: &str,
+    format: Format,
+    json: &'a mut [u8],
+    buffer: &'a mut [u8],
+) -> Result<&'a [u8], crate::Error> {
+    let len = settings.get(path, json)?;
+
+    format
+        .encode(&json[..len], buffer)
+        .map_err(|_| crate::Error::SerializationFailed)
+}
+
+/// Retrieve the committed value of a setting to include in a response.
+///
+/// # Args
+/// * `settings` - The current settings.
+/// * `path` - The path of the updated setting.
+/// * `json` - A buffer to serialize the value into.
+///
+/// # Returns
+/// The serialized value, or None if the path or the value are too large to include.
+fn committed_value<'a, Settings: Miniconf>(
+    settings: &Settings,
+    path: &str,
+    json: &'a mut [u8; MAX_RESPONSE_VALUE_SIZE],
+) -> Option<&'a str> {
+    if path.len() > MAX_RESPONSE_VALUE_SIZE {
+        return None;
+    }
+
+    let len = settings.get(path, json).ok()?;
+    core::str::from_utf8(&json[..len]).ok()
+}
+
+/// Retrieve all settings values below a wildcard path.
+///
+/// # Args
+/// * `settings` - The settings to retrieve values from.
+/// * `path` - The wildcard path to retrieve.
+/// * `format` - The format to encode the values in.
+/// * `json` - A buffer to serialize the values into.
+/// * `buffer` - A buffer to store the encoded values in, if necessary.
+///
+/// # Returns
+/// The encoded values or the response to report to the requestor on failure.
+// Note: The response is reported to the requestor by value, so its size is intentional.
+#[allow(clippy::result_large_err)]
+fn get_wildcard<'a, Settings: Miniconf>(
+    settings: &Settings,
+    path: &str,
+    format: Format,
+    json: &'a mut [u8],
+    buffer: &'a mut [u8],
+) -> Result<&'a [u8], SettingsResponse> {
+    let len = settings
+        .get_wildcard(path, json)
+        .map_err(SettingsResponse::describe)?;
+
+    format
+        .encode(&json[..len], buffer)
+        .map_err(|_| SettingsResponse::error(String::from("Response too large")))
+}
+
+/// Defer a settings update, e.g. while the client is paused.
+///
+/// # Note
+/// If the update cannot be queued, any pending update of the path is discarded, so that it does
+/// not overwrite the update once it has been handled otherwise.
+///
+/// # Args
+/// * `pending_updates` - The queue of deferred updates.
+/// * `path` - The settings path to update.
+/// * `value` - The serialized value of the setting.
+///
+/// # Returns
+/// True if the update was queued, or false if too many paths are pending or the update does not
+/// fit within the queue.
+fn defer_update<const MESSAGE_SIZE: usize, const MAX_TOPIC_LENGTH: usize>(
+    pending_updates: &mut Vec<
+        (String<MAX_TOPIC_LENGTH>, Vec<u8, MESSAGE_SIZE>),
+        MAX_PENDING_UPDATES,
+    >,
+    path: &str,
+    value: &[u8],
+) -> bool {
+    // Values rewritten by a pre-set function or decoded from CBOR may exceed the message size.
+    let value = match Vec::from_slice(value) {
+        Ok(value) => value,
+        Err(()) => {
+            pending_updates.retain(|(topic, _)| topic != path);
+            return false;
+        }
+    };
+
+    // Only the latest value of each path is retained.
+    if let Some((_, pending)) = pending_updates.iter_mut().find(|(topic, _)| topic == path) {
+        *pending = value;
+        return true;
+    }
+
+    let mut topic = String::new();
+    topic.push_str(path).is_ok() && pending_updates.push((topic, value)).is_ok()
 }
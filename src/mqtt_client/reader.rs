@@ -0,0 +1,154 @@
+//! Lock-free read access to the settings of an [crate::MqttClient].
+use alloc::sync::Arc;
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{fence, AtomicUsize, Ordering},
+};
+
+/// The double-buffered settings shared between the client and its readers.
+struct Shared<Settings> {
+    buffers: [UnsafeCell<Settings>; 2],
+
+    /// Odd while the unpublished buffer is written. The published buffer is given by the second
+    /// bit.
+    version: AtomicUsize,
+}
+
+// Note: Concurrent access to the buffers is synchronized using the version counter.
+unsafe impl<Settings: Send> Sync for Shared<Settings> {}
+
+impl<Settings: Copy> Shared<Settings> {
+    fn new(settings: Settings) -> Self {
+        Self {
+            buffers: [UnsafeCell::new(settings), UnsafeCell::new(settings)],
+            version: AtomicUsize::new(0),
+        }
+    }
+
+    /// Publish new settings.
+    ///
+    /// # Note
+    /// There must only be a single writer.
+    fn write(&self, settings: &Settings) {
+        let version = self.version.load(Ordering::Relaxed);
+        let buffer = &self.buffers[((version >> 1) + 1) & 1];
+
+        self.version
+            .store(version.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        // Safety: Readers only access the unpublished buffer while the version is odd and discard
+        // anything read from it.
+        unsafe { core::ptr::write_volatile(buffer.get(), *settings) };
+
+        self.version
+            .store(version.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Attempt to copy the published settings.
+    ///
+    /// # Returns
+    /// The settings and the number of updates published, or None if the settings were
+    /// overwritten during the read.
+    fn try_read(&self) -> Option<(Settings, usize)> {
+        let version = self.version.load(Ordering::Acquire);
+        let buffer = &self.buffers[(version >> 1) & 1];
+
+        // Safety: The copy is not interpreted as settings unless the buffer was not written during
+        // the read.
+        let copy = unsafe {
+            core::ptr::read_volatile(buffer.get() as *const Settings as *const MaybeUninit<Settings>)
+        };
+
+        fence(Ordering::Acquire);
+        let current = self.version.load(Ordering::Relaxed);
+
+        // The buffer is only written again once the version advanced past the subsequent update.
+        if current.wrapping_sub(version & !1) >= 3 {
+            return None;
+        }
+
+        // Safety: The buffer was not written during the read, so the copy is consistent.
+        Some((unsafe { copy.assume_init() }, version >> 1))
+    }
+}
+
+/// Publishes the settings of the client to its readers.
+pub struct Publisher<Settings> {
+    shared: Arc<Shared<Settings>>,
+    write: fn(&Shared<Settings>, &Settings),
+}
+
+impl<Settings> Publisher<Settings> {
+    pub fn new(settings: &Settings) -> Self
+    where
+        Settings: Copy,
+    {
+        Self {
+            shared: Arc::new(Shared::new(*settings)),
+            write: Shared::write,
+        }
+    }
+
+    /// Publish updated settings to all readers.
+    pub fn publish(&self, settings: &Settings) {
+        (self.write)(&self.shared, settings)
+    }
+
+    /// Get a new reader of the published settings.
+    pub fn reader(&self) -> SettingsReader<Settings> {
+        SettingsReader {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// A handle to read the settings of an [crate::MqttClient] without borrowing the client.
+///
+/// # Memory model
+/// The settings are double-buffered: the client writes updated settings into the buffer that is
+/// not currently published and then publishes it by advancing a version counter. Readers copy the
+/// published buffer and validate the copy against the version counter afterwards, retrying if the
+/// buffer may have been overwritten in the meantime.
+///
+/// On a single core, a reader executing in an interrupt that preempts the update of the client
+/// always observes the previously published settings and never retries, since the client cannot
+/// run until the read completed. A reader that is preempted by an update only retries if the
+/// client published two updates during the read, i.e. if it wrapped around to the buffer being
+/// read. The same holds for readers on other cores, as all buffer accesses are ordered using
+/// acquire and release fences on the version counter.
+///
+/// Values are copied bitwise, so the settings must be `Copy`. A copy that turns out to be
+/// inconsistent is discarded without being interpreted as settings.
+pub struct SettingsReader<Settings> {
+    shared: Arc<Shared<Settings>>,
+}
+
+impl<Settings> Clone for SettingsReader<Settings> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<Settings: Copy> SettingsReader<Settings> {
+    /// Get a consistent copy of the most recently published settings.
+    pub fn read(&self) -> Settings {
+        self.read_versioned().0
+    }
+
+    /// Get a consistent copy of the most recently published settings along with their version.
+    ///
+    /// # Returns
+    /// The settings and the number of updates published before them. The version can be used to
+    /// detect changes of the settings between reads.
+    pub fn read_versioned(&self) -> (Settings, usize) {
+        loop {
+            if let Some(read) = self.shared.try_read() {
+                return read;
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+use super::{Attributes, Error, LeafValue, Miniconf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Lock the settings, reporting a poisoned lock as [Error::Poisoned].
+fn lock<T>(mutex: &Mutex<T>) -> Result<MutexGuard<'_, T>, Error> {
+    mutex.lock().map_err(|_| Error::Poisoned)
+}
+
+/// Settings behind a mutex occupy the same paths as the settings they contain. The mutex is locked
+/// for the duration of each operation.
+///
+/// # Note
+/// Metadata and paths are retrieved from settings behind a poisoned lock, as they cannot report
+/// errors. `Mutex` has unstable inherent methods named `set()` and `get()`, so
+/// [Miniconf::set] and [Miniconf::get] are best called using fully qualified syntax.
+impl<T: Miniconf> Miniconf for Mutex<T> {
+    fn string_set(
+        &mut self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        lock(self)?.string_set(topic_parts, value)
+    }
+
+    fn string_get(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &mut [u8],
+    ) -> Result<usize, Error> {
+        lock(self)?.string_get(topic_parts, value)
+    }
+
+    fn string_value_len(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        lock(self)?.string_value_len(topic_parts)
+    }
+
+    fn string_attributes(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Attributes, Error> {
+        lock(self)?.string_attributes(topic_parts)
+    }
+
+    fn string_type_name(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static str, Error> {
+        lock(self)?.string_type_name(topic_parts)
+    }
+
+    fn string_variants(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static [&'static str], Error> {
+        lock(self)?.string_variants(topic_parts)
+    }
+
+    fn string_leaf_value(
+        &mut self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        // Exclusive access to the mutex does not require locking it.
+        self.get_mut()
+            .map_err(|_| Error::Poisoned)?
+            .string_leaf_value(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn string_metadata(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<MiniconfMetadata, Error> {
+        lock(self)?.string_metadata(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn get_metadata(&self) -> MiniconfMetadata {
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_metadata()
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl TopicBuffer) -> Option<()> {
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .recurse_paths(index, topic)
+    }
+}
+
+/// Shared references to settings behind a mutex allow updating the settings from multiple threads.
+/// The mutex is locked for the duration of each operation.
+///
+/// # Note
+/// Leaf values cannot be accessed beyond the lock, so they are not reachable, e.g. by
+/// [Miniconf::map_leaves].
+impl<T: Miniconf> Miniconf for &Mutex<T> {
+    fn string_set(
+        &mut self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        lock(self)?.string_set(topic_parts, value)
+    }
+
+    fn string_get(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &mut [u8],
+    ) -> Result<usize, Error> {
+        (*self).string_get(topic_parts, value)
+    }
+
+    fn string_value_len(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        (*self).string_value_len(topic_parts)
+    }
+
+    fn string_attributes(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Attributes, Error> {
+        (*self).string_attributes(topic_parts)
+    }
+
+    fn string_type_name(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static str, Error> {
+        (*self).string_type_name(topic_parts)
+    }
+
+    fn string_variants(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static [&'static str], Error> {
+        (*self).string_variants(topic_parts)
+    }
+
+    fn string_leaf_value(
+        &mut self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        // The path is still validated.
+        lock(self)?.string_leaf_value(topic_parts).map(|_| None)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn string_metadata(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<MiniconfMetadata, Error> {
+        (*self).string_metadata(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn get_metadata(&self) -> MiniconfMetadata {
+        (*self).get_metadata()
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl TopicBuffer) -> Option<()> {
+        (*self).recurse_paths(index, topic)
+    }
+}
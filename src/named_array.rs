@@ -0,0 +1,253 @@
+use super::{array::parse_index, Attributes, Error, LeafValue, Miniconf};
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+
+/// An array whose elements are addressed by name.
+///
+/// # Note
+/// Elements may be addressed by either their name or their numeric index, e.g. both
+/// `channel/left/gain` and `channel/0/gain` refer to the same setting. Paths are iterated using
+/// the element names.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NamedArray<T, const N: usize> {
+    names: &'static [&'static str; N],
+    data: [T; N],
+}
+
+impl<T, const N: usize> NamedArray<T, N> {
+    /// Construct a named array.
+    ///
+    /// # Args
+    /// * `names` - The name of each element. Names must be unique and may not contain `/`.
+    /// * `data` - The elements of the array.
+    pub const fn new(names: &'static [&'static str; N], data: [T; N]) -> Self {
+        Self { names, data }
+    }
+
+    /// Get the names of the elements.
+    pub fn names(&self) -> &'static [&'static str; N] {
+        self.names
+    }
+
+    /// Find the index of an element from its name or numeric index.
+    fn index(&self, part: Option<&str>) -> Result<usize, Error> {
+        // Note(unwrap): Callers check for the presence of the path part beforehand.
+        let part = part.unwrap();
+
+        if let Some(i) = self.names.iter().position(|name| *name == part) {
+            return Ok(i);
+        }
+
+        let i = parse_index(part)?;
+
+        if i >= N {
+            return Err(Error::BadIndex);
+        }
+
+        Ok(i)
+    }
+}
+
+impl<T, const N: usize> core::ops::Deref for NamedArray<T, N> {
+    type Target = [T; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T, const N: usize> core::ops::DerefMut for NamedArray<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<T: Miniconf, const N: usize> Miniconf for NamedArray<T, N> {
+    fn string_set(
+        &mut self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::ExpectedLeaf);
+        }
+
+        let i = self.index(next)?;
+        self.data[i].string_set(topic_parts, value)
+    }
+
+    fn string_get(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &mut [u8],
+    ) -> Result<usize, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        let i = self.index(next)?;
+        self.data[i].string_get(topic_parts, value)
+    }
+
+    fn string_value_len(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        let i = self.index(next)?;
+        self.data[i].string_value_len(topic_parts)
+    }
+
+    fn string_attributes(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Attributes, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        let i = self.index(next)?;
+        self.data[i].string_attributes(topic_parts)
+    }
+
+    fn string_type_name(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static str, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        let i = self.index(next)?;
+        self.data[i].string_type_name(topic_parts)
+    }
+
+    fn string_variants(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static [&'static str], Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        let i = self.index(next)?;
+        self.data[i].string_variants(topic_parts)
+    }
+
+    fn string_leaf_value(
+        &mut self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Err(Error::PathTooShort);
+        }
+
+        let i = self.index(next)?;
+        self.data[i].string_leaf_value(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn string_metadata(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<MiniconfMetadata, Error> {
+        let next = topic_parts.next();
+        if next.is_none() {
+            return Ok(self.get_metadata());
+        }
+
+        let i = self.index(next)?;
+        self.data[i].string_metadata(topic_parts)
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn get_metadata(&self) -> MiniconfMetadata {
+        // Empty arrays have no members, but still require an index for iteration.
+        if N == 0 {
+            return MiniconfMetadata {
+                max_topic_size: 0,
+                max_depth: 1,
+                path_count: 0,
+            };
+        }
+
+        let name_size = self.names.iter().map(|name| name.len()).max().unwrap_or(0);
+        let metadata = self.data[0].get_metadata();
+        let path_count = self
+            .data
+            .iter()
+            .map(|item| item.get_metadata().path_count)
+            .sum();
+
+        // If the sub-members have topic size, we also need to include an additional character for
+        // the path separator.
+        if metadata.max_topic_size > 0 {
+            MiniconfMetadata {
+                max_topic_size: metadata.max_topic_size + name_size + 1,
+                max_depth: metadata.max_depth + 1,
+                path_count,
+            }
+        } else {
+            MiniconfMetadata {
+                max_topic_size: name_size,
+                max_depth: metadata.max_depth + 1,
+                path_count,
+            }
+        }
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl TopicBuffer) -> Option<()> {
+        let original_length = topic.len();
+
+        if index.is_empty() {
+            // Note: During expected execution paths using `into_iter()`, the size of the
+            // index stack is checked in advance to make sure this condition doesn't occur.
+            // However, it's possible to happen if the user manually calls `recurse_paths`.
+            unreachable!("Index stack too small");
+        }
+
+        while index[0] < N {
+            // Add the element name to the topic name.
+            let name = self.names[index[0]];
+            let written = (topic.is_empty() || topic.write_char('/').is_ok())
+                && topic.write_str(name).is_ok();
+
+            if !written {
+                // Note: During expected execution paths using `into_iter()`, the size of the
+                // topic buffer is checked in advance to make sure this condition doesn't occur.
+                // However, it's possible to happen if the user manually calls `recurse_paths`.
+                // The remaining paths of this array are dropped instead of panicking.
+                log::error!("Topic buffer too short for array element `{}`", name);
+                topic.truncate(original_length);
+                return None;
+            }
+
+            if self.data[index[0]]
+                .recurse_paths(&mut index[1..], topic)
+                .is_some()
+            {
+                return Some(());
+            }
+
+            // Strip off the previously prepended name, since we completed that element and need
+            // to instead check the next one.
+            topic.truncate(original_length);
+
+            index[0] += 1;
+            index[1..].iter_mut().for_each(|x| *x = 0);
+        }
+
+        None
+    }
+}
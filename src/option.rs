@@ -1,4 +1,6 @@
-use super::{Error, Miniconf, MiniconfMetadata};
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+use super::{Attributes, Error, LeafValue, Miniconf};
 
 impl<T: Miniconf> Miniconf for Option<T> {
     fn string_set(
@@ -21,17 +23,70 @@ impl<T: Miniconf> Miniconf for Option<T> {
         })
     }
 
+    fn string_value_len(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        self.as_ref().map_or(Err(Error::PathNotFound), |inner| {
+            inner.string_value_len(topic_parts)
+        })
+    }
+
+    fn string_attributes(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Attributes, Error> {
+        self.as_ref().map_or(Err(Error::PathNotFound), |inner| {
+            inner.string_attributes(topic_parts)
+        })
+    }
+
+    fn string_type_name(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static str, Error> {
+        self.as_ref().map_or(Err(Error::PathNotFound), |inner| {
+            inner.string_type_name(topic_parts)
+        })
+    }
+
+    fn string_variants(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static [&'static str], Error> {
+        self.as_ref().map_or(Err(Error::PathNotFound), |inner| {
+            inner.string_variants(topic_parts)
+        })
+    }
+
+    fn string_leaf_value(
+        &mut self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        self.as_mut().map_or(Err(Error::PathNotFound), |inner| {
+            inner.string_leaf_value(topic_parts)
+        })
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn string_metadata(
+        &self,
+        topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<MiniconfMetadata, Error> {
+        self.as_ref().map_or(Err(Error::PathNotFound), |inner| {
+            inner.string_metadata(topic_parts)
+        })
+    }
+
+    #[cfg(not(feature = "set-only"))]
     fn get_metadata(&self) -> MiniconfMetadata {
         self.as_ref()
             .map(|value| value.get_metadata())
             .unwrap_or_default()
     }
 
-    fn recurse_paths<const TS: usize>(
-        &self,
-        index: &mut [usize],
-        topic: &mut heapless::String<TS>,
-    ) -> Option<()> {
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl TopicBuffer) -> Option<()> {
         self.as_ref()
             .and_then(|value| value.recurse_paths(index, topic))
     }
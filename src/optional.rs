@@ -0,0 +1,211 @@
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+use super::{Attributes, Error, LeafValue, Miniconf};
+
+// The names of the members of an `Optional`.
+#[cfg(not(feature = "set-only"))]
+const MEMBERS: [&str; 2] = ["enabled", "value"];
+
+/// A value that can be enabled or disabled at run-time without losing its configuration.
+///
+/// # Note
+/// Both members are always accessible at `<path>/enabled` and `<path>/value`, so the value is
+/// retained and can still be configured while disabled.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Optional<T> {
+    /// Specifies whether the value is enabled.
+    pub enabled: bool,
+
+    /// The configured value.
+    pub value: T,
+}
+
+impl<T> Optional<T> {
+    /// Construct an enabled value.
+    ///
+    /// # Args
+    /// * `value` - The configured value.
+    pub const fn new(value: T) -> Self {
+        Self {
+            enabled: true,
+            value,
+        }
+    }
+
+    /// Get the value if it is enabled.
+    pub fn as_option(&self) -> Option<&T> {
+        if self.enabled {
+            Some(&self.value)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Miniconf> Miniconf for Optional<T> {
+    fn string_set(
+        &mut self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        match topic_parts.next() {
+            None => Err(Error::ExpectedLeaf),
+            Some("enabled") => self.enabled.string_set(topic_parts, value),
+            Some("value") => self.value.string_set(topic_parts, value),
+            Some(_) => Err(Error::PathNotFound),
+        }
+    }
+
+    fn string_get(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+        value: &mut [u8],
+    ) -> Result<usize, Error> {
+        match topic_parts.next() {
+            None => Err(Error::PathTooShort),
+            Some("enabled") => self.enabled.string_get(topic_parts, value),
+            Some("value") => self.value.string_get(topic_parts, value),
+            Some(_) => Err(Error::PathNotFound),
+        }
+    }
+
+    fn string_value_len(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<usize, Error> {
+        match topic_parts.next() {
+            None => Err(Error::PathTooShort),
+            Some("enabled") => self.enabled.string_value_len(topic_parts),
+            Some("value") => self.value.string_value_len(topic_parts),
+            Some(_) => Err(Error::PathNotFound),
+        }
+    }
+
+    fn string_attributes(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Attributes, Error> {
+        match topic_parts.next() {
+            None => Err(Error::PathTooShort),
+            Some("enabled") => self.enabled.string_attributes(topic_parts),
+            Some("value") => self.value.string_attributes(topic_parts),
+            Some(_) => Err(Error::PathNotFound),
+        }
+    }
+
+    fn string_type_name(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static str, Error> {
+        match topic_parts.next() {
+            None => Err(Error::PathTooShort),
+            Some("enabled") => self.enabled.string_type_name(topic_parts),
+            Some("value") => self.value.string_type_name(topic_parts),
+            Some(_) => Err(Error::PathNotFound),
+        }
+    }
+
+    fn string_variants(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<&'static [&'static str], Error> {
+        match topic_parts.next() {
+            None => Err(Error::PathTooShort),
+            Some("enabled") => self.enabled.string_variants(topic_parts),
+            Some("value") => self.value.string_variants(topic_parts),
+            Some(_) => Err(Error::PathNotFound),
+        }
+    }
+
+    fn string_leaf_value(
+        &mut self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<Option<&mut dyn LeafValue>, Error> {
+        match topic_parts.next() {
+            None => Err(Error::PathTooShort),
+            Some("enabled") => self.enabled.string_leaf_value(topic_parts),
+            Some("value") => self.value.string_leaf_value(topic_parts),
+            Some(_) => Err(Error::PathNotFound),
+        }
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn string_metadata(
+        &self,
+        mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+    ) -> Result<MiniconfMetadata, Error> {
+        match topic_parts.next() {
+            None => Ok(self.get_metadata()),
+            Some("enabled") => self.enabled.string_metadata(topic_parts),
+            Some("value") => self.value.string_metadata(topic_parts),
+            Some(_) => Err(Error::PathNotFound),
+        }
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn get_metadata(&self) -> MiniconfMetadata {
+        let metadata = self.value.get_metadata();
+
+        // If the value has sub-members, an additional character is required for the path
+        // separator.
+        let value_size = if metadata.max_topic_size > 0 {
+            MEMBERS[1].len() + 1 + metadata.max_topic_size
+        } else {
+            MEMBERS[1].len()
+        };
+
+        MiniconfMetadata {
+            max_topic_size: value_size.max(MEMBERS[0].len()),
+            // The `enabled` member requires a single index.
+            max_depth: metadata.max_depth.max(1) + 1,
+            path_count: metadata.path_count + 1,
+        }
+    }
+
+    #[cfg(not(feature = "set-only"))]
+    fn recurse_paths(&self, index: &mut [usize], topic: &mut impl TopicBuffer) -> Option<()> {
+        let original_length = topic.len();
+
+        if index.is_empty() {
+            // Note: During expected execution paths using `into_iter()`, the size of the
+            // index stack is checked in advance to make sure this condition doesn't occur.
+            // However, it's possible to happen if the user manually calls `recurse_paths`.
+            unreachable!("Index stack too small");
+        }
+
+        while index[0] < MEMBERS.len() {
+            let name = MEMBERS[index[0]];
+            let written = (topic.is_empty() || topic.write_char('/').is_ok())
+                && topic.write_str(name).is_ok();
+
+            if !written {
+                // Note: During expected execution paths using `into_iter()`, the size of the
+                // topic buffer is checked in advance to make sure this condition doesn't occur.
+                // However, it's possible to happen if the user manually calls `recurse_paths`.
+                // The remaining paths are dropped instead of panicking.
+                log::error!("Topic buffer too short for `{}`", name);
+                topic.truncate(original_length);
+                return None;
+            }
+
+            let found = if index[0] == 0 {
+                self.enabled.recurse_paths(&mut index[1..], topic)
+            } else {
+                self.value.recurse_paths(&mut index[1..], topic)
+            };
+
+            if found.is_some() {
+                return Some(());
+            }
+
+            // Strip off the previously prepended name, since we completed that member and need
+            // to instead check the next one.
+            topic.truncate(original_length);
+
+            index[0] += 1;
+            index[1..].iter_mut().for_each(|x| *x = 0);
+        }
+
+        None
+    }
+}
@@ -0,0 +1,345 @@
+//! Read access to the members of values that are serialized as a whole.
+use super::{counter::json_len, Error};
+use core::{iter::Peekable, str::Split};
+use serde::ser::{self, Impossible, Serialize};
+
+/// An error that occurred while selecting a member.
+#[derive(Debug)]
+struct SelectError(Error);
+
+impl core::fmt::Display for SelectError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl ser::StdError for SelectError {}
+
+impl ser::Error for SelectError {
+    fn custom<T: core::fmt::Display>(_msg: T) -> Self {
+        SelectError(Error::SerializationFailed)
+    }
+}
+
+/// A serializer that only serializes the member of a value at the remaining path.
+struct Select<'a, 'b> {
+    topic_parts: Peekable<Split<'a, char>>,
+    // The member is only measured if there is no buffer.
+    value: Option<&'b mut [u8]>,
+}
+
+impl<'a, 'b> Select<'a, 'b> {
+    /// Serialize a member of the value, descending into it if the path continues.
+    fn member<T: Serialize + ?Sized>(mut self, member: &T) -> Result<usize, SelectError> {
+        if self.topic_parts.peek().is_some() {
+            return member.serialize(self);
+        }
+
+        match self.value {
+            Some(value) => serde_json_core::to_slice(member, value)
+                .map_err(|_| SelectError(Error::SerializationFailed)),
+            None => json_len(member).map_err(SelectError),
+        }
+    }
+}
+
+/// Selects a field of a struct by its serialized name.
+struct Fields<'a, 'b> {
+    key: &'a str,
+    select: Option<Select<'a, 'b>>,
+    len: Option<usize>,
+}
+
+impl<'a, 'b> ser::SerializeStruct for Fields<'a, 'b> {
+    type Ok = usize;
+    type Error = SelectError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SelectError> {
+        if key == self.key {
+            if let Some(select) = self.select.take() {
+                self.len.replace(select.member(value)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<usize, SelectError> {
+        self.len.ok_or(SelectError(Error::PathNotFound))
+    }
+}
+
+/// Selects an element of a sequence by its index.
+struct Elements<'a, 'b> {
+    index: usize,
+    count: usize,
+    select: Option<Select<'a, 'b>>,
+    len: Option<usize>,
+}
+
+impl<'a, 'b> Elements<'a, 'b> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SelectError> {
+        if self.count == self.index {
+            if let Some(select) = self.select.take() {
+                self.len.replace(select.member(value)?);
+            }
+        }
+
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<usize, SelectError> {
+        self.len.ok_or(SelectError(Error::BadIndex))
+    }
+}
+
+impl<'a, 'b> ser::SerializeSeq for Elements<'a, 'b> {
+    type Ok = usize;
+    type Error = SelectError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SelectError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<usize, SelectError> {
+        Elements::end(self)
+    }
+}
+
+impl<'a, 'b> ser::SerializeTuple for Elements<'a, 'b> {
+    type Ok = usize;
+    type Error = SelectError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SelectError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<usize, SelectError> {
+        Elements::end(self)
+    }
+}
+
+impl<'a, 'b> ser::SerializeTupleStruct for Elements<'a, 'b> {
+    type Ok = usize;
+    type Error = SelectError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SelectError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<usize, SelectError> {
+        Elements::end(self)
+    }
+}
+
+macro_rules! leaf {
+    ($($method:ident: $ty:ty),+) => {
+        $(
+        fn $method(self, _value: $ty) -> Result<usize, SelectError> {
+            Err(SelectError(Error::PathTooLong))
+        }
+        )+
+    };
+}
+
+impl<'a, 'b> ser::Serializer for Select<'a, 'b> {
+    type Ok = usize;
+    type Error = SelectError;
+    type SerializeSeq = Elements<'a, 'b>;
+    type SerializeTuple = Elements<'a, 'b>;
+    type SerializeTupleStruct = Elements<'a, 'b>;
+    type SerializeTupleVariant = Impossible<usize, SelectError>;
+    type SerializeMap = Impossible<usize, SelectError>;
+    type SerializeStruct = Fields<'a, 'b>;
+    type SerializeStructVariant = Impossible<usize, SelectError>;
+
+    leaf!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+        serialize_bytes: &[u8],
+        serialize_unit_struct: &'static str
+    );
+
+    fn serialize_none(self) -> Result<usize, SelectError> {
+        // Absent options have no members.
+        Err(SelectError(Error::PathNotFound))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<usize, SelectError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<usize, SelectError> {
+        Err(SelectError(Error::PathTooLong))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+    ) -> Result<usize, SelectError> {
+        Err(SelectError(Error::PathTooLong))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<usize, SelectError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        mut self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<usize, SelectError> {
+        // The data of the variant is serialized as the member named by the variant.
+        if self.topic_parts.next() != Some(variant) {
+            return Err(SelectError(Error::PathNotFound));
+        }
+
+        self.member(value)
+    }
+
+    fn serialize_seq(mut self, _len: Option<usize>) -> Result<Elements<'a, 'b>, SelectError> {
+        let index = self
+            .topic_parts
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or(SelectError(Error::BadIndex))?;
+
+        Ok(Elements {
+            index,
+            count: 0,
+            select: Some(self),
+            len: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Elements<'a, 'b>, SelectError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Elements<'a, 'b>, SelectError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SelectError> {
+        Err(SelectError(Error::PathNotFound))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SelectError> {
+        Err(SelectError(Error::PathNotFound))
+    }
+
+    fn serialize_struct(
+        mut self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Fields<'a, 'b>, SelectError> {
+        // Note(unwrap): Members are only selected if the path continues.
+        let key = self.topic_parts.next().unwrap();
+
+        Ok(Fields {
+            key,
+            select: Some(self),
+            len: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SelectError> {
+        Err(SelectError(Error::PathNotFound))
+    }
+
+    fn collect_str<T: core::fmt::Display + ?Sized>(self, _value: &T) -> Result<usize, SelectError> {
+        Err(SelectError(Error::PathTooLong))
+    }
+}
+
+/// Serialize the member of a value at a path.
+///
+/// # Note
+/// Struct fields are addressed by their serialized names, elements of sequences and tuples by
+/// their index and the data of newtype enum variants by the name of the variant. Members of maps
+/// and of other enum variants cannot be addressed.
+///
+/// # Args
+/// * `value` - The value to serialize a member of.
+/// * `topic_parts` - The path of the member.
+/// * `buf` - The buffer to serialize the member into.
+///
+/// # Returns
+/// The length of the serialized member.
+#[doc(hidden)]
+pub fn get_member<T: Serialize + ?Sized>(
+    value: &T,
+    topic_parts: Peekable<Split<char>>,
+    buf: &mut [u8],
+) -> Result<usize, Error> {
+    Select {
+        topic_parts,
+        value: Some(buf),
+    }
+    .member(value)
+    .map_err(|err| err.0)
+}
+
+/// Determine the serialized length of the member of a value at a path.
+///
+/// # Note
+/// Members are addressed as for [get_member].
+///
+/// # Args
+/// * `value` - The value to measure a member of.
+/// * `topic_parts` - The path of the member.
+///
+/// # Returns
+/// The length of the serialized member.
+#[doc(hidden)]
+pub fn member_len<T: Serialize + ?Sized>(
+    value: &T,
+    topic_parts: Peekable<Split<char>>,
+) -> Result<usize, Error> {
+    Select {
+        topic_parts,
+        value: None,
+    }
+    .member(value)
+    .map_err(|err| err.0)
+}
@@ -0,0 +1,238 @@
+use super::{array::parse_index, Attributes, Error, LeafValue, Miniconf};
+#[cfg(not(feature = "set-only"))]
+use super::{iter::TopicBuffer, MiniconfMetadata};
+
+/// Add the metadata of a member to the metadata of the settings containing it.
+///
+/// # Args
+/// * `maximum_sizes` - The metadata of the containing settings.
+/// * `key_len` - The length of the path segment addressing the member.
+/// * `meta` - The metadata of the member.
+#[cfg(not(feature = "set-only"))]
+pub(crate) fn add_member_metadata(
+    maximum_sizes: &mut MiniconfMetadata,
+    key_len: usize,
+    mut meta: MiniconfMetadata,
+) {
+    // If the member has additional paths, we need to add space for a separator.
+    if meta.max_topic_size > 0 {
+        meta.max_topic_size += 1;
+    }
+
+    meta.max_topic_size += key_len;
+
+    maximum_sizes.max_topic_size =
+        core::cmp::max(maximum_sizes.max_topic_size, meta.max_topic_size);
+    maximum_sizes.max_depth = core::cmp::max(maximum_sizes.max_depth, meta.max_depth);
+    maximum_sizes.path_count += meta.path_count;
+}
+
+/// Iterate the paths of a fixed number of members, e.g. the elements of a tuple.
+///
+/// # Args
+/// * `index` - The iteration state, starting with the index of the current member.
+/// * `topic` - The buffer to write the paths into.
+/// * `members` - The number of members.
+/// * `write_key` - Writes the path segment addressing a member into the topic.
+/// * `recurse` - Continues the iteration within a member.
+///
+/// # Returns
+/// Some if a path was written into the topic, None once all members were iterated.
+#[cfg(not(feature = "set-only"))]
+pub(crate) fn recurse_members<T: TopicBuffer>(
+    index: &mut [usize],
+    topic: &mut T,
+    members: usize,
+    mut write_key: impl FnMut(usize, &mut T) -> core::fmt::Result,
+    mut recurse: impl FnMut(usize, &mut [usize], &mut T) -> Option<()>,
+) -> Option<()> {
+    let original_length = topic.len();
+
+    if index.is_empty() {
+        // Note: During expected execution paths using `into_iter()`, the size of the index stack
+        // is checked in advance to make sure this condition doesn't occur. However, it's possible
+        // to happen if the user manually calls `recurse_paths`.
+        unreachable!("Index stack too small");
+    }
+
+    while index[0] < members {
+        // Add the key of the member to the topic name.
+        let written = (topic.is_empty() || topic.write_char('/').is_ok())
+            && write_key(index[0], topic).is_ok();
+
+        if !written {
+            // Note: During expected execution paths using `into_iter()`, the size of the topic
+            // buffer is checked in advance to make sure this condition doesn't occur. However,
+            // it's possible to happen if the user manually calls `recurse_paths`. The remaining
+            // paths are dropped instead of panicking.
+            log::error!("Topic buffer too short for member {}", index[0]);
+            topic.truncate(original_length);
+            return None;
+        }
+
+        if recurse(index[0], &mut index[1..], topic).is_some() {
+            return Some(());
+        }
+
+        // Strip off the previously prepended key, since we completed that member and need to
+        // instead check the next one.
+        topic.truncate(original_length);
+
+        index[0] += 1;
+        index[1..].iter_mut().for_each(|x| *x = 0);
+    }
+
+    None
+}
+
+// Settings of tuples are addressed by the index of the element, e.g. `0/gain` for the `gain`
+// member of the first element. Elements are iterated in order.
+macro_rules! impl_tuple {
+    ($n:literal; $($i:tt: $t:ident),+) => {
+        impl<$($t: Miniconf),+> Miniconf for ($($t,)+) {
+            fn string_set(
+                &mut self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+                value: &[u8],
+            ) -> Result<(), Error> {
+                let i = parse_index(topic_parts.next().ok_or(Error::ExpectedLeaf)?)?;
+                match i {
+                    $($i => self.$i.string_set(topic_parts, value),)+
+                    _ => Err(Error::BadIndex),
+                }
+            }
+
+            fn string_get(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+                value: &mut [u8],
+            ) -> Result<usize, Error> {
+                let i = parse_index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.$i.string_get(topic_parts, value),)+
+                    _ => Err(Error::BadIndex),
+                }
+            }
+
+            fn string_value_len(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<usize, Error> {
+                let i = parse_index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.$i.string_value_len(topic_parts),)+
+                    _ => Err(Error::BadIndex),
+                }
+            }
+
+            fn string_attributes(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<Attributes, Error> {
+                let i = parse_index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.$i.string_attributes(topic_parts),)+
+                    _ => Err(Error::BadIndex),
+                }
+            }
+
+            fn string_type_name(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<&'static str, Error> {
+                let i = parse_index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.$i.string_type_name(topic_parts),)+
+                    _ => Err(Error::BadIndex),
+                }
+            }
+
+            fn string_variants(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<&'static [&'static str], Error> {
+                let i = parse_index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.$i.string_variants(topic_parts),)+
+                    _ => Err(Error::BadIndex),
+                }
+            }
+
+            fn string_leaf_value(
+                &mut self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<Option<&mut dyn LeafValue>, Error> {
+                let i = parse_index(topic_parts.next().ok_or(Error::PathTooShort)?)?;
+                match i {
+                    $($i => self.$i.string_leaf_value(topic_parts),)+
+                    _ => Err(Error::BadIndex),
+                }
+            }
+
+            #[cfg(not(feature = "set-only"))]
+            fn string_metadata(
+                &self,
+                mut topic_parts: core::iter::Peekable<core::str::Split<char>>,
+            ) -> Result<MiniconfMetadata, Error> {
+                let i = match topic_parts.next() {
+                    Some(next) => parse_index(next)?,
+                    None => return Ok(self.get_metadata()),
+                };
+
+                match i {
+                    $($i => self.$i.string_metadata(topic_parts),)+
+                    _ => Err(Error::BadIndex),
+                }
+            }
+
+            #[cfg(not(feature = "set-only"))]
+            fn get_metadata(&self) -> MiniconfMetadata {
+                let mut maximum_sizes = MiniconfMetadata::default();
+
+                $(
+                    add_member_metadata(
+                        &mut maximum_sizes,
+                        stringify!($i).len(),
+                        self.$i.get_metadata(),
+                    );
+                )+
+
+                // We need an additional index depth for the element.
+                maximum_sizes.max_depth += 1;
+
+                maximum_sizes
+            }
+
+            #[cfg(not(feature = "set-only"))]
+            fn recurse_paths(
+                &self,
+                index: &mut [usize],
+                topic: &mut impl TopicBuffer,
+            ) -> Option<()> {
+                recurse_members(
+                    index,
+                    topic,
+                    $n,
+                    |i, topic| write!(topic, "{}", i),
+                    |i, index, topic| match i {
+                        $($i => self.$i.recurse_paths(index, topic),)+
+                        _ => unreachable!(),
+                    },
+                )
+            }
+        }
+    };
+}
+
+impl_tuple!(1; 0: A);
+impl_tuple!(2; 0: A, 1: B);
+impl_tuple!(3; 0: A, 1: B, 2: C);
+impl_tuple!(4; 0: A, 1: B, 2: C, 3: D);
+impl_tuple!(5; 0: A, 1: B, 2: C, 3: D, 4: E);
+impl_tuple!(6; 0: A, 1: B, 2: C, 3: D, 4: E, 5: F);
+impl_tuple!(7; 0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G);
+impl_tuple!(8; 0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H);
+impl_tuple!(9; 0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I);
+impl_tuple!(10; 0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J);
+impl_tuple!(11; 0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J, 10: K);
+impl_tuple!(12; 0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J, 10: K, 11: L);
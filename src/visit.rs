@@ -0,0 +1,57 @@
+use super::{Error, Miniconf};
+
+/// A handle to a settings leaf visited by [Miniconf::visit_leaves].
+pub struct LeafRef<'a, Settings: Miniconf + ?Sized> {
+    pub(crate) settings: &'a Settings,
+    pub(crate) path: &'a str,
+}
+
+impl<'a, Settings: Miniconf + ?Sized> LeafRef<'a, Settings> {
+    /// Get the path of the leaf.
+    pub fn path(&self) -> &str {
+        self.path
+    }
+
+    /// Retrieve the serialized value of the leaf.
+    ///
+    /// # Args
+    /// * `data` - The location to serialize the data into.
+    ///
+    /// # Returns
+    /// The number of bytes used in the `data` buffer for serialization.
+    pub fn get(&self, data: &mut [u8]) -> Result<usize, Error> {
+        self.settings.get(self.path, data)
+    }
+}
+
+/// A mutable handle to a settings leaf visited by [Miniconf::visit_leaves_mut].
+pub struct LeafMut<'a, Settings: Miniconf + ?Sized> {
+    pub(crate) settings: &'a mut Settings,
+    pub(crate) path: &'a str,
+}
+
+impl<'a, Settings: Miniconf + ?Sized> LeafMut<'a, Settings> {
+    /// Get the path of the leaf.
+    pub fn path(&self) -> &str {
+        self.path
+    }
+
+    /// Retrieve the serialized value of the leaf.
+    ///
+    /// # Args
+    /// * `data` - The location to serialize the data into.
+    ///
+    /// # Returns
+    /// The number of bytes used in the `data` buffer for serialization.
+    pub fn get(&self, data: &mut [u8]) -> Result<usize, Error> {
+        self.settings.get(self.path, data)
+    }
+
+    /// Update the value of the leaf.
+    ///
+    /// # Args
+    /// * `data` - The serialized data making up the contents of the configured value.
+    pub fn set(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.settings.set(self.path, data)
+    }
+}
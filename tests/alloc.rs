@@ -0,0 +1,100 @@
+#![cfg(feature = "alloc")]
+
+use miniconf::Miniconf;
+
+#[derive(Miniconf, Default)]
+struct Inner {
+    a_very_long_field_name_that_would_not_fit_in_a_small_buffer: f32,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    a: f32,
+    b: [u32; 2],
+    c: Inner,
+}
+
+#[test]
+fn iterate_paths() {
+    let settings = Settings::default();
+
+    let paths: Vec<String> = settings.iter_paths().collect();
+    assert_eq!(
+        paths,
+        [
+            "a",
+            "b/0",
+            "b/1",
+            "c/a_very_long_field_name_that_would_not_fit_in_a_small_buffer"
+        ]
+    );
+}
+
+#[test]
+fn sorted_paths() {
+    #[derive(Miniconf, Default)]
+    struct Unordered {
+        zeta: u32,
+        alpha: [u8; 2],
+        mid: Inner,
+        beta: bool,
+    }
+
+    let settings = Unordered::default();
+
+    // Iteration remains in declaration order.
+    let paths: Vec<String> = settings.iter_paths().collect();
+    assert_eq!(paths[0], "zeta");
+
+    assert_eq!(
+        settings.sorted_paths(),
+        [
+            "alpha/0",
+            "alpha/1",
+            "beta",
+            "mid/a_very_long_field_name_that_would_not_fit_in_a_small_buffer",
+            "zeta"
+        ]
+    );
+}
+
+#[test]
+fn boxed_settings() {
+    #[derive(Miniconf, Default)]
+    struct Outer {
+        rate: u32,
+        inner: Box<Inner>,
+        filters: Box<[Inner; 2]>,
+    }
+
+    let mut settings = Outer::default();
+
+    // Boxed settings occupy the same paths as unboxed ones.
+    settings
+        .set(
+            "inner/a_very_long_field_name_that_would_not_fit_in_a_small_buffer",
+            b"1.5",
+        )
+        .unwrap();
+    assert_eq!(
+        settings
+            .inner
+            .a_very_long_field_name_that_would_not_fit_in_a_small_buffer,
+        1.5
+    );
+
+    settings
+        .set(
+            "filters/1/a_very_long_field_name_that_would_not_fit_in_a_small_buffer",
+            b"2.5",
+        )
+        .unwrap();
+    assert_eq!(
+        settings.filters[1].a_very_long_field_name_that_would_not_fit_in_a_small_buffer,
+        2.5
+    );
+
+    let paths: Vec<String> = settings.iter_paths().collect();
+    assert_eq!(paths.len(), 4);
+    assert_eq!(paths.len(), settings.get_metadata().path_count);
+}
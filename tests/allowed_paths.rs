@@ -0,0 +1,137 @@
+use miniconf::{minimq, Miniconf};
+use serde::Deserialize;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Dsp {
+    gain: f32,
+    offset: f32,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    dsp: Dsp,
+    rate: u32,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    code: u8,
+    msg: heapless::String<64>,
+}
+
+type Interface = miniconf::MqttClient<Settings, Stack, StandardClock, 256>;
+type Tester = minimq::Minimq<Stack, StandardClock, 256, 1>;
+
+const RESPONSE_TOPIC: &str = "allowed_paths/response";
+
+/// Poll the interface and the tester, collecting the settings topics republished by the interface
+/// and the responses to requests.
+fn poll(interface: &mut Interface, mqtt: &mut Tester) -> (Vec<String>, Vec<(u8, String)>) {
+    let mut topics = Vec::new();
+    let mut responses = Vec::new();
+
+    for _ in 0..100 {
+        interface.update().unwrap();
+        mqtt.poll(|_, topic, message, _| {
+            if topic == RESPONSE_TOPIC {
+                let response: Response = serde_json_core::from_slice(message).unwrap().0;
+                responses.push((response.code, response.msg.as_str().to_string()));
+            } else {
+                topics.push(topic.to_string());
+            }
+        })
+        .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    (topics, responses)
+}
+
+fn request(interface: &mut Interface, mqtt: &mut Tester, path: &str, value: &[u8]) -> (u8, String) {
+    let mut topic = String::from("allowed_paths/device/settings/");
+    topic.push_str(path);
+    mqtt.client
+        .publish(
+            &topic,
+            value,
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[minimq::Property::ResponseTopic(RESPONSE_TOPIC)],
+        )
+        .unwrap();
+
+    let (_, mut responses) = poll(interface, mqtt);
+    assert_eq!(responses.len(), 1);
+    responses.remove(0)
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: Tester = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    let mut interface: Interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "allowed_paths/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .allowed_paths(&["dsp/gain"]);
+
+    // Wait for both clients to be subscribed.
+    let mut subscribed = false;
+    for _ in 0..500 {
+        interface.update().unwrap();
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+
+        if mqtt.client.is_connected() && !subscribed {
+            mqtt.client
+                .subscribe("allowed_paths/device/settings/#", &[])
+                .unwrap();
+            mqtt.client.subscribe(RESPONSE_TOPIC, &[]).unwrap();
+            subscribed = true;
+        }
+
+        if subscribed && !mqtt.client.subscriptions_pending() && interface.is_subscribed() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(interface.is_subscribed());
+
+    // Only the allowed path is republished.
+    interface.force_republish();
+    let (topics, _) = poll(&mut interface, &mut mqtt);
+    assert_eq!(topics, ["allowed_paths/device/settings/dsp/gain"]);
+
+    // Other paths cannot be updated.
+    let (code, msg) = request(&mut interface, &mut mqtt, "dsp/offset", b"1.0");
+    assert!(code != 0);
+    assert_eq!(msg, "Forbidden");
+    assert_eq!(interface.settings().dsp.offset, 0.0);
+
+    let (code, _) = request(&mut interface, &mut mqtt, "rate", b"10");
+    assert!(code != 0);
+    assert_eq!(interface.settings().rate, 0);
+
+    // The allowed path is updated.
+    let (code, _) = request(&mut interface, &mut mqtt, "dsp/gain", b"2.5");
+    assert_eq!(code, 0);
+    assert_eq!(interface.settings().dsp.gain, 2.5);
+}
@@ -1,17 +1,6 @@
 use miniconf::{Error, Miniconf};
 use serde::Deserialize;
 
-#[derive(Debug, Default, Miniconf, Deserialize)]
-struct AdditionalSettings {
-    inner: u8,
-}
-
-#[derive(Debug, Default, Miniconf, Deserialize)]
-struct Settings {
-    data: u32,
-    more: AdditionalSettings,
-}
-
 #[test]
 fn simple_array() {
     #[derive(Miniconf, Default)]
@@ -108,3 +97,74 @@ fn array_of_structs_indexing() {
     assert_eq!(metadata.max_depth, 4);
     assert_eq!(metadata.max_topic_size, "a/2/b".len());
 }
+
+#[test]
+fn set_non_leaf() {
+    #[derive(Miniconf, Default)]
+    struct S {
+        a: [u8; 3],
+    }
+
+    let mut s = S::default();
+
+    // Setting the array without an index requires a leaf.
+    assert_eq!(s.set("a", b"[1, 2, 3]"), Err(Error::ExpectedLeaf));
+
+    // Invalid indices are still reported as bad indices.
+    assert_eq!(s.set("a/3", b"1"), Err(Error::BadIndex));
+    assert_eq!(s.set("a/x", b"1"), Err(Error::BadIndex));
+}
+
+#[test]
+fn index_parsing() {
+    #[derive(Miniconf, Default)]
+    struct S {
+        a: [u8; 11],
+    }
+
+    let s = S::default();
+
+    // Canonical indices are accepted, as they were when parsed as JSON.
+    for index in ["0", "1", "9", "10"] {
+        let i: usize = serde_json_core::from_str(index).unwrap().0;
+        assert!(i < 11);
+
+        let mut buf = [0; 8];
+        assert!(
+            s.get(&format!("a/{}", index), &mut buf).is_ok(),
+            "{}",
+            index
+        );
+    }
+
+    // Signs, leading zeros, fractions and other representations are rejected, as they were when
+    // parsed as JSON.
+    for index in [
+        "01",
+        "00",
+        "+1",
+        "-1",
+        "1.0",
+        "1e1",
+        "0x1",
+        "1_0",
+        "\"1\"",
+        "",
+        "a",
+        "18446744073709551616",
+    ] {
+        assert!(serde_json_core::from_str::<usize>(index).is_err());
+
+        let mut buf = [0; 8];
+        assert_eq!(
+            s.get(&format!("a/{}", index), &mut buf),
+            Err(Error::BadIndex),
+            "{:?}",
+            index
+        );
+    }
+
+    // Indices out of bounds are rejected.
+    let mut buf = [0; 8];
+    assert_eq!(s.get("a/11", &mut buf), Err(Error::BadIndex));
+}
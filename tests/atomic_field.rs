@@ -0,0 +1,73 @@
+use miniconf::{Error, Miniconf};
+use serde::{Deserialize, Serialize};
+
+#[derive(Miniconf, Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+struct Calibration {
+    offset: i32,
+    gain: f32,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    #[miniconf(atomic)]
+    calibration: Calibration,
+    tuned: Calibration,
+}
+
+#[test]
+fn set_whole() {
+    let mut settings = Settings::default();
+
+    settings
+        .set("calibration", b"{\"offset\": -3, \"gain\": 1.5}")
+        .unwrap();
+    assert_eq!(
+        settings.calibration,
+        Calibration {
+            offset: -3,
+            gain: 1.5
+        }
+    );
+
+    let mut buf = [0; 64];
+    let len = settings.get("calibration", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"{\"offset\":-3,\"gain\":1.5}");
+
+    // The same type is still accessible through its members where it is not atomic.
+    settings.set("tuned/offset", b"7").unwrap();
+    assert_eq!(settings.tuned.offset, 7);
+}
+
+#[test]
+fn no_sub_paths() {
+    let mut settings = Settings::default();
+    let mut buf = [0; 64];
+
+    assert_eq!(
+        settings.set("calibration/offset", b"1"),
+        Err(Error::AtomicUpdateRequired)
+    );
+    assert_eq!(settings.calibration, Calibration::default());
+
+    // Members can only be read.
+    settings.calibration.offset = -3;
+    let len = settings.get("calibration/offset", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"-3");
+}
+
+#[test]
+fn iteration() {
+    let settings = Settings::default();
+
+    let meta = settings.get_metadata();
+    assert_eq!(meta.path_count, 3);
+    assert_eq!(meta.max_depth, 3);
+
+    let mut state = [0; 8];
+    let paths: Vec<_> = settings
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .map(|path| path.to_string())
+        .collect();
+    assert_eq!(paths, ["calibration", "tuned/offset", "tuned/gain"]);
+}
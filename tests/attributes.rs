@@ -0,0 +1,72 @@
+use miniconf::{Attributes, Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Filter {
+    #[miniconf(unit = "Hz", description = "cutoff frequency")]
+    cutoff: f32,
+    order: u8,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    #[miniconf(description = "Input filters")]
+    filters: [Filter; 2],
+    #[miniconf(unit = "V")]
+    offset: Option<f32>,
+}
+
+#[test]
+fn schema() {
+    let settings = Settings {
+        offset: Some(0.0),
+        ..Default::default()
+    };
+
+    // Generate a schema of all paths and their attributes.
+    let mut state = [0; 8];
+    let schema: Vec<(String, Attributes)> = settings
+        .into_iter::<64>(&mut state)
+        .unwrap()
+        .map(|path| (path.to_string(), settings.get_attributes(&path).unwrap()))
+        .collect();
+
+    assert_eq!(schema.len(), 5);
+    for index in 0..2 {
+        assert!(schema.contains(&(
+            format!("filters/{}/cutoff", index),
+            Attributes {
+                unit: Some("Hz"),
+                description: Some("cutoff frequency"),
+            }
+        )));
+        assert!(schema.contains(&(format!("filters/{}/order", index), Attributes::default())));
+    }
+
+    assert!(schema.contains(&(
+        "offset".to_string(),
+        Attributes {
+            unit: Some("V"),
+            description: None,
+        }
+    )));
+}
+
+#[test]
+fn intermediate_paths() {
+    let settings = Settings::default();
+
+    // Attributes of non-leaf nodes are available as well.
+    assert_eq!(
+        settings.get_attributes("filters").unwrap().description,
+        Some("Input filters")
+    );
+
+    assert_eq!(
+        settings.get_attributes("filters/2/cutoff"),
+        Err(Error::BadIndex)
+    );
+    assert_eq!(
+        settings.get_attributes("filters/0/gain"),
+        Err(Error::PathNotFound)
+    );
+}
@@ -0,0 +1,84 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+mod common;
+use common::Tester;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    secret: u32,
+}
+
+/// Deny all updates of `secret`.
+fn authorize(path: &str, _properties: &[minimq::Property]) -> bool {
+    path != "secret"
+}
+
+async fn request(mqtt: &mut Tester, topic: &str) -> (u8, String) {
+    common::request(mqtt, topic, b"5", "authorize/device/response").await
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    mqtt.client
+        .subscribe("authorize/device/response", &[])
+        .unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    let (code, msg) = request(&mut mqtt, "authorize/device/settings/data").await;
+    assert_eq!((code, msg.as_str()), (0, "OK"));
+
+    let (code, msg) = request(&mut mqtt, "authorize/device/settings/secret").await;
+    assert!(code != 0);
+    assert_eq!(msg, "Forbidden");
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "authorize/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .authorize(authorize);
+
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    // Only the authorized update should have been applied.
+    assert_eq!(interface.settings().data, 5);
+    assert_eq!(interface.settings().secret, 0);
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "backup")]
+
+use miniconf::{BackupError, Error, Miniconf};
+
+#[derive(Miniconf, Default, Debug, PartialEq)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Miniconf, Default, Debug, PartialEq)]
+struct Settings {
+    rate: u32,
+    channels: [Inner; 2],
+}
+
+// A previous version of the settings containing a path that has since been removed.
+#[derive(Miniconf, Default)]
+struct OldSettings {
+    rate: u32,
+    removed: bool,
+}
+
+#[test]
+fn round_trip() {
+    let mut settings = Settings {
+        rate: 10,
+        ..Default::default()
+    };
+    settings.channels[1].gain = 2.5;
+
+    let mut data = [0; 256];
+    let len = settings.serialize_all(&mut data).unwrap();
+
+    assert_eq!(Settings::from_backup(&data[..len]).unwrap(), settings);
+}
+
+#[test]
+fn insufficient_space() {
+    let settings = Settings::default();
+    let mut data = [0; 16];
+    assert_eq!(
+        settings.serialize_all(&mut data),
+        Err(Error::SerializationFailed)
+    );
+}
+
+#[test]
+fn schema_drift() {
+    let mut data = [0; 256];
+    let len = OldSettings::default().serialize_all(&mut data).unwrap();
+
+    assert_eq!(
+        Settings::from_backup(&data[..len]),
+        Err(BackupError::Apply {
+            path: "removed",
+            error: Error::PathNotFound
+        })
+    );
+}
+
+#[test]
+fn malformed() {
+    let mut data = [0; 256];
+    Settings::default().serialize_all(&mut data).unwrap();
+
+    // The backup is too short to contain a checksum.
+    assert_eq!(
+        Settings::from_backup(&data[..3]),
+        Err(BackupError::Malformed)
+    );
+}
+
+#[test]
+fn corrupt() {
+    let mut settings = Settings {
+        rate: 10,
+        ..Default::default()
+    };
+    settings.channels[0].gain = -1.0;
+
+    let mut data = [0; 256];
+    let len = settings.serialize_all(&mut data).unwrap();
+    assert_eq!(Settings::from_backup(&data[..len]).unwrap(), settings);
+
+    // Every flipped bit is detected.
+    for i in 0..len {
+        let mut corrupted = data;
+        corrupted[i] ^= 0x10;
+        assert_eq!(
+            Settings::from_backup(&corrupted[..len]),
+            Err(BackupError::Corrupt),
+            "{}",
+            i
+        );
+    }
+
+    // Truncated backups do not match their checksum either.
+    assert_eq!(
+        Settings::from_backup(&data[..len - 1]),
+        Err(BackupError::Corrupt)
+    );
+}
@@ -0,0 +1,105 @@
+use miniconf::{
+    embedded_time::duration::Extensions, minimq::embedded_nal, ActivationError, Miniconf,
+};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+use embedded_nal::{nb, SocketAddr, TcpClientStack};
+
+/// A network stack that only connects after a number of connection attempts.
+struct DelayedConnect {
+    stack: Stack,
+    remaining: Option<usize>,
+}
+
+impl TcpClientStack for DelayedConnect {
+    type TcpSocket = <Stack as TcpClientStack>::TcpSocket;
+    type Error = <Stack as TcpClientStack>::Error;
+
+    fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
+        self.stack.socket()
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        remote: SocketAddr,
+    ) -> nb::Result<(), Self::Error> {
+        match &mut self.remaining {
+            Some(0) => self.stack.connect(socket, remote),
+            Some(remaining) => {
+                *remaining -= 1;
+                Err(nb::Error::WouldBlock)
+            }
+            None => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    fn is_connected(&mut self, socket: &Self::TcpSocket) -> Result<bool, Self::Error> {
+        self.stack.is_connected(socket)
+    }
+
+    fn send(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &[u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.stack.send(socket, buffer)
+    }
+
+    fn receive(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.stack.receive(socket, buffer)
+    }
+
+    fn close(&mut self, socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        self.stack.close(socket)
+    }
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+type Interface = miniconf::MqttClient<Settings, DelayedConnect, StandardClock, 256>;
+
+fn interface(prefix: &str, remaining: Option<usize>) -> Interface {
+    miniconf::MqttClient::new(
+        DelayedConnect {
+            stack: Stack,
+            remaining,
+        },
+        "",
+        prefix,
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn active() {
+    let mut interface = interface("block_until_active/device", Some(5));
+
+    interface
+        .block_until_active(|_, _, _| Result::<(), &str>::Ok(()), 10.seconds())
+        .unwrap();
+
+    // The settings were republished once the interface became active.
+    assert!(interface.is_subscribed());
+    assert!(interface.just_finished_republish());
+}
+
+#[test]
+fn timeout() {
+    let mut interface = interface("block_until_active/unreachable", None);
+
+    let result = interface.block_until_active(|_, _, _| Result::<(), &str>::Ok(()), 1.seconds());
+    assert!(matches!(result, Err(ActivationError::Timeout)));
+    assert!(!interface.is_subscribed());
+}
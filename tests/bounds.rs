@@ -0,0 +1,68 @@
+use miniconf::{Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    #[miniconf(min = 0, max = 100)]
+    percent: u8,
+    #[miniconf(min = "-1.5", max = 1.5)]
+    gain: f32,
+    #[miniconf(min = "-10")]
+    offset: i32,
+}
+
+#[test]
+fn in_range() {
+    let mut settings = Settings::default();
+
+    settings.set("percent", b"100").unwrap();
+    assert_eq!(settings.percent, 100);
+
+    // Float bounds are inclusive.
+    settings.set("gain", b"-1.5").unwrap();
+    assert_eq!(settings.gain, -1.5);
+
+    settings.set("offset", b"2000").unwrap();
+    assert_eq!(settings.offset, 2000);
+}
+
+#[test]
+fn out_of_range() {
+    let mut settings = Settings {
+        percent: 5,
+        gain: 0.5,
+        offset: 1,
+    };
+
+    assert_eq!(
+        settings.set("percent", b"101"),
+        Err(Error::OutOfRange {
+            min: Some("0"),
+            max: Some("100")
+        })
+    );
+    assert_eq!(settings.percent, 5);
+
+    assert_eq!(
+        settings.set("gain", b"1.6"),
+        Err(Error::OutOfRange {
+            min: Some("-1.5"),
+            max: Some("1.5")
+        })
+    );
+    assert_eq!(settings.gain, 0.5);
+
+    assert_eq!(
+        settings.set("offset", b"-11"),
+        Err(Error::OutOfRange {
+            min: Some("-10"),
+            max: None
+        })
+    );
+    assert_eq!(settings.offset, 1);
+
+    // Values that cannot be deserialized are still reported as such.
+    assert!(matches!(
+        settings.set("percent", b"300"),
+        Err(Error::Deserialization(_))
+    ));
+}
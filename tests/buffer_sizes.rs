@@ -0,0 +1,102 @@
+use miniconf::{assert_buffer_sizes, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    data: u32,
+    inner: [Inner; 2],
+}
+
+#[test]
+fn sufficient() {
+    assert_buffer_sizes!(Settings, "buffer-sizes/device", 256);
+    assert_buffer_sizes!(Settings, "buffer-sizes/device", 64, 48);
+
+    // Representative values are checked instead of the defaults.
+    let settings = Settings {
+        data: u32::MAX,
+        ..Default::default()
+    };
+    assert_buffer_sizes!(&settings, "buffer-sizes/device", 64);
+}
+
+#[test]
+#[should_panic(expected = "Settings topics require 41 bytes, exceeding MAX_TOPIC_LENGTH of 32")]
+fn topic_too_long() {
+    assert_buffer_sizes!(Settings, "buffer-sizes/device", 256, 32);
+}
+
+#[test]
+#[should_panic(expected = "Publication of `data` requires 51 bytes, exceeding MESSAGE_SIZE of 50")]
+fn message_too_small() {
+    let settings = Settings {
+        data: u32::MAX,
+        ..Default::default()
+    };
+    assert_buffer_sizes!(&settings, "buffer-sizes/device", 50);
+}
+
+#[test]
+fn settings_topic_options() {
+    assert_buffer_sizes!(Settings, "buffer-sizes/device", 256, 37; root = "cfg", separator = "//");
+}
+
+#[test]
+#[should_panic(expected = "Settings topics require 37 bytes, exceeding MAX_TOPIC_LENGTH of 36")]
+fn settings_topic_options_too_long() {
+    assert_buffer_sizes!(Settings, "buffer-sizes/device", 256, 36; root = "cfg", separator = "//");
+}
+
+#[test]
+#[should_panic(expected = "Value of `data` requires 10 bytes, exceeding MAX_VALUE_SIZE of 9")]
+fn value_too_large() {
+    let settings = Settings {
+        data: u32::MAX,
+        ..Default::default()
+    };
+    assert_buffer_sizes!(&settings, "buffer-sizes/device", 64; max_value_size = 9);
+}
+
+/// Exposes a counter as a fixed-width hexadecimal string.
+mod hex {
+    use core::fmt::Write;
+    use miniconf::Error;
+
+    pub fn set(_field: &mut u32, _value: &[u8]) -> Result<(), Error> {
+        Err(Error::SerializationFailed)
+    }
+
+    pub fn get(field: &u32, buffer: &mut [u8]) -> Result<usize, Error> {
+        let mut value: heapless::String<10> = heapless::String::new();
+        write!(&mut value, "\"{:08x}\"", field).unwrap();
+        buffer
+            .get_mut(..value.len())
+            .ok_or(Error::SerializationFailed)?
+            .copy_from_slice(value.as_bytes());
+        Ok(value.len())
+    }
+}
+
+#[derive(Miniconf, Default)]
+struct Custom {
+    #[miniconf(with = "hex")]
+    counter: u32,
+}
+
+#[test]
+fn custom_serialization() {
+    // Values of fields with custom serialization cannot be measured, so they are serialized.
+    assert_buffer_sizes!(Custom, "buffer-sizes/device", 64);
+}
+
+#[test]
+#[should_panic(
+    expected = "Publication of `counter` requires 54 bytes, exceeding MESSAGE_SIZE of 53"
+)]
+fn custom_serialization_too_large() {
+    assert_buffer_sizes!(Custom, "buffer-sizes/device", 53);
+}
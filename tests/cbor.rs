@@ -0,0 +1,158 @@
+use miniconf::{minimq, Format, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    gain: f32,
+}
+
+// The CBOR encoding of `{"code": 0, "msg": "OK"}`.
+const OK_RESPONSE: &[u8] = b"\xa2\x64code\x00\x63msg\x62OK";
+
+// The message of the response to values that are not valid CBOR.
+const INVALID_MESSAGE: &[u8] = b"Invalid payload encoding";
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "cbor/device/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    // Configure settings using CBOR-encoded values: the unsigned integer 7 and the single
+    // precision float 1.5.
+    let properties = [minimq::Property::ResponseTopic(response_topic)];
+    for (topic, value) in [
+        ("cbor/device/settings/data", &b"\x07"[..]),
+        ("cbor/device/settings/gain", &b"\xfa\x3f\xc0\x00\x00"[..]),
+    ] {
+        mqtt.client
+            .publish(
+                topic,
+                value,
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &properties,
+            )
+            .unwrap();
+
+        // Responses should be CBOR-encoded as well.
+        let mut response = None;
+        while response.is_none() {
+            mqtt.poll(|_client, _topic, message, _properties| {
+                response.replace(message.to_vec());
+            })
+            .unwrap();
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(response.unwrap(), OK_RESPONSE);
+    }
+
+    // Malformed values must be rejected without affecting the device: deeply nested arrays and
+    // tags, a text string claiming a length exceeding any buffer, and a truncated integer.
+    let nested_arrays = [&[0x81; 64][..], &[0x00]].concat();
+    let nested_tags = [&[0xC0; 64][..], &[0x00]].concat();
+    for value in [
+        &nested_arrays[..],
+        &nested_tags[..],
+        &b"\x7b\xff\xff\xff\xff\xff\xff\xff\xff"[..],
+        &b"\x19\x01"[..],
+    ] {
+        mqtt.client
+            .publish(
+                "cbor/device/settings/data",
+                value,
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &properties,
+            )
+            .unwrap();
+
+        let mut response = None;
+        while response.is_none() {
+            mqtt.poll(|_client, _topic, message, _properties| {
+                response.replace(message.to_vec());
+            })
+            .unwrap();
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+
+        let response = response.unwrap();
+        assert!(response
+            .windows(INVALID_MESSAGE.len())
+            .any(|window| window == INVALID_MESSAGE));
+    }
+
+    // Wait for the device to republish its settings in CBOR.
+    mqtt.client
+        .subscribe("cbor/device/settings/#", &[])
+        .unwrap();
+
+    let mut republished = std::collections::HashMap::new();
+    while republished.len() < 2 {
+        mqtt.poll(|_client, topic, message, _properties| {
+            republished.insert(topic.to_string(), message.to_vec());
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(republished["cbor/device/settings/data"], b"\x07");
+    assert_eq!(
+        republished["cbor/device/settings/gain"],
+        b"\xfa\x3f\xc0\x00\x00"
+    );
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "cbor/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .format(Format::Cbor);
+
+    // Update the client until the republished settings have been verified.
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    assert_eq!(interface.settings().data, 7);
+    assert_eq!(interface.settings().gain, 1.5);
+}
@@ -0,0 +1,71 @@
+use miniconf::{Error, Miniconf};
+
+#[cfg(feature = "alloc")]
+#[derive(Miniconf, Default)]
+struct Gpio {
+    level: bool,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    a: u32,
+    #[cfg(any())]
+    disabled: u32,
+    #[cfg(feature = "alloc")]
+    gpio: Gpio,
+    #[cfg(test)]
+    b: u32,
+}
+
+#[test]
+fn disabled_field() {
+    let mut settings = Settings::default();
+    let mut value = [0; 32];
+
+    assert_eq!(settings.set("disabled", b"1"), Err(Error::PathNotFound));
+    assert_eq!(
+        settings.get("disabled", &mut value),
+        Err(Error::PathNotFound)
+    );
+
+    settings.set("b", b"2").unwrap();
+    assert_eq!(settings.b, 2);
+}
+
+#[test]
+fn feature_field() {
+    let mut settings = Settings::default();
+    let mut value = [0; 32];
+
+    let result = settings.set("gpio/level", b"true");
+    if cfg!(feature = "alloc") {
+        assert_eq!(result, Ok(()));
+        assert_eq!(settings.get("gpio/level", &mut value), Ok(4));
+    } else {
+        assert_eq!(result, Err(Error::PathNotFound));
+    }
+}
+
+#[test]
+fn iteration() {
+    let settings = Settings::default();
+    let mut state = [0; 8];
+    let paths: Vec<_> = settings
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .map(|path| path.to_string())
+        .collect();
+
+    let metadata = settings.get_metadata();
+    if cfg!(feature = "alloc") {
+        assert_eq!(paths, ["a", "gpio/level", "b"]);
+        assert_eq!(metadata.path_count, 3);
+        assert_eq!(metadata.max_topic_size, "gpio/level".len());
+        assert_eq!(metadata.max_depth, 3);
+    } else {
+        assert_eq!(paths, ["a", "b"]);
+        assert_eq!(metadata.path_count, 2);
+        assert_eq!(metadata.max_topic_size, 1);
+        assert_eq!(metadata.max_depth, 2);
+    }
+}
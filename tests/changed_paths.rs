@@ -0,0 +1,119 @@
+use miniconf::{heapless::String, minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    extra: Option<u32>,
+    data: u32,
+    enabled: bool,
+    inner: Inner,
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "changed-paths/tester/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    for (topic, value) in [
+        ("changed-paths/device/settings/data", "5"),
+        ("changed-paths/device/settings/inner/gain", "1.5"),
+        ("changed-paths/device/settings/data", "6"),
+    ] {
+        mqtt.client
+            .publish(
+                topic,
+                value.as_bytes(),
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[minimq::Property::ResponseTopic(response_topic)],
+            )
+            .unwrap();
+
+        let mut response = None;
+        while response.is_none() {
+            mqtt.poll(|_client, _topic, message, _properties| {
+                response.replace(message.to_vec());
+            })
+            .unwrap();
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(response.unwrap(), br#"{"code":0,"msg":"OK"}"#);
+    }
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Republished settings would be received by the client itself and count as changes.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "changed-paths/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false);
+
+    let mut paths: miniconf::heapless::Vec<String<128>, 4> = miniconf::heapless::Vec::new();
+    interface.changed_paths(&mut paths).unwrap();
+    assert!(paths.is_empty());
+
+    // Changing the gain makes another setting present, which precedes the changed paths.
+    let mut handler = |path: &str, old: &mut Settings, new: &Settings| {
+        *old = new.clone();
+        if path == "inner/gain" {
+            old.extra.replace(0);
+        }
+        Result::<(), &str>::Ok(())
+    };
+
+    while !task.is_finished() {
+        interface.handled_update(&mut handler).unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    // Every changed path is reported once, in the order of the first change.
+    interface.changed_paths(&mut paths).unwrap();
+    assert_eq!(paths, ["data", "inner/gain"]);
+
+    // The buffer must hold all changed paths.
+    let mut paths: miniconf::heapless::Vec<String<128>, 1> = miniconf::heapless::Vec::new();
+    assert!(matches!(
+        interface.changed_paths(&mut paths),
+        Err(miniconf::CollectError::PathBufferFull)
+    ));
+}
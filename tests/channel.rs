@@ -0,0 +1,110 @@
+#![cfg(feature = "channel")]
+
+use miniconf::{embedded_io, Miniconf, SettingsChannel};
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+#[derive(Miniconf, Default)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    data: u32,
+    inner: Inner,
+}
+
+/// An in-memory pipe. Requests are queued for reading and responses are collected from writing.
+#[derive(Clone, Default)]
+struct Pipe {
+    rx: Rc<RefCell<VecDeque<u8>>>,
+    tx: Rc<RefCell<Vec<u8>>>,
+}
+
+impl Pipe {
+    fn request(&self, request: &str) {
+        self.rx.borrow_mut().extend(request.as_bytes());
+    }
+
+    fn response(&self) -> String {
+        String::from_utf8(self.tx.borrow_mut().split_off(0)).unwrap()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rx.borrow().is_empty()
+    }
+}
+
+impl embedded_io::ErrorType for Pipe {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_io::Read for Pipe {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut rx = self.rx.borrow_mut();
+        let len = buf.len().min(rx.len());
+        for (byte, data) in buf.iter_mut().zip(rx.drain(..len)) {
+            *byte = data;
+        }
+        Ok(len)
+    }
+}
+
+impl embedded_io::Write for Pipe {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.tx.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn handle<const N: usize>(channel: &mut SettingsChannel<Settings, Pipe, N>, pipe: &Pipe) -> bool {
+    let mut updated = false;
+    while !pipe.is_empty() {
+        updated |= channel.update().unwrap();
+    }
+    updated
+}
+
+#[test]
+fn get_set() {
+    let pipe = Pipe::default();
+    let mut channel: SettingsChannel<_, _> =
+        SettingsChannel::new(pipe.clone(), Settings::default());
+
+    pipe.request("SET data 5\nSET inner/gain 1.5\r\n");
+    assert!(handle(&mut channel, &pipe));
+    assert_eq!(pipe.response(), "OK\nOK\n");
+    assert_eq!(channel.settings().data, 5);
+    assert_eq!(channel.settings().inner.gain, 1.5);
+
+    pipe.request("GET data\nGET inner/gain\n");
+    assert!(!handle(&mut channel, &pipe));
+    assert_eq!(pipe.response(), "OK 5\nOK 1.5\n");
+
+    pipe.request("LIST\n");
+    handle(&mut channel, &pipe);
+    assert_eq!(pipe.response(), "data\ninner/gain\nOK\n");
+}
+
+#[test]
+fn errors() {
+    let pipe = Pipe::default();
+    let mut channel: SettingsChannel<_, _, 16> =
+        SettingsChannel::new(pipe.clone(), Settings::default());
+
+    pipe.request("GET missing\nSET data true\nRESET\n");
+    assert!(!handle(&mut channel, &pipe));
+    assert_eq!(
+        pipe.response(),
+        "ERR PathNotFound\nERR Deserialization(InvalidType)\nERR Unknown command\n"
+    );
+
+    // Requests that exceed the line size are rejected without affecting subsequent requests.
+    pipe.request("SET data 123456789012345\nGET data\n");
+    handle(&mut channel, &pipe);
+    assert_eq!(pipe.response(), "ERR Request too long\nOK 0\n");
+}
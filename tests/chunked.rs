@@ -0,0 +1,109 @@
+use miniconf::{minimq, Miniconf, MiniconfAtomic};
+use serde::{Deserialize, Serialize};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+mod common;
+use common::Tester;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, MiniconfAtomic)]
+struct Table {
+    values: [u16; 32],
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    table: Table,
+}
+
+fn table() -> Table {
+    let mut table = Table::default();
+    for (i, value) in table.values.iter_mut().enumerate() {
+        *value = 1000 + i as u16;
+    }
+    table
+}
+
+async fn request(mqtt: &mut Tester, topic: &str, value: &[u8]) -> (u8, String) {
+    common::request(mqtt, topic, value, "chunked/device/response").await
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    mqtt.client
+        .subscribe("chunked/device/response", &[])
+        .unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    // The serialized value exceeds the message size of the device.
+    let value: heapless::Vec<u8, 256> = serde_json_core::to_vec(&table()).unwrap();
+    assert!(value.len() > 128);
+
+    // Chunks received out of order are rejected.
+    let (code, msg) = request(&mut mqtt, "chunked/device/settings/table/chunk/1", b"{").await;
+    assert!(code != 0);
+    assert_eq!(msg, "Chunk out of order");
+
+    let size = value.len().div_ceil(3);
+    for (index, chunk) in value.chunks(size).enumerate() {
+        let topic = format!("chunked/device/settings/table/chunk/{}", index);
+        let (code, msg) = request(&mut mqtt, &topic, chunk).await;
+        assert_eq!(code, 0);
+        assert_eq!(msg, "Chunk received");
+    }
+
+    let (code, msg) = request(&mut mqtt, "chunked/device/settings/table/commit", b"").await;
+    assert_eq!(code, 0);
+    assert_eq!(msg, "OK");
+
+    // The transfer is concluded after it was committed.
+    let (code, msg) = request(&mut mqtt, "chunked/device/settings/table/commit", b"").await;
+    assert!(code != 0);
+    assert_eq!(msg, "No transfer in progress");
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 128, 128, 512> =
+        miniconf::MqttClient::new(
+            Stack,
+            "",
+            "chunked/device",
+            "127.0.0.1".parse().unwrap(),
+            StandardClock::default(),
+            Settings::default(),
+        )
+        .unwrap();
+
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    assert_eq!(interface.settings().table, table());
+}
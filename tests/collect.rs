@@ -0,0 +1,139 @@
+use miniconf::{minimq, CollectError, Miniconf};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    gain: f32,
+}
+
+async fn wait_for_stage(stage: &AtomicUsize, value: usize) {
+    while stage.load(Ordering::SeqCst) != value {
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+}
+
+async fn client_task(stage: Arc<AtomicUsize>) {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    for (round, values) in [(1, [&b"5"[..], b"1.5"]), (3, [b"6", b"2.5"])] {
+        // Wait for the device to stop servicing MQTT traffic, then deliver two updates so that
+        // they are received in a single poll.
+        wait_for_stage(&stage, round).await;
+
+        for (topic, value) in [
+            "collect/device/settings/data",
+            "collect/device/settings/gain",
+        ]
+        .iter()
+        .zip(values.iter())
+        {
+            mqtt.client
+                .publish(
+                    topic,
+                    value,
+                    minimq::QoS::AtMostOnce,
+                    minimq::Retain::NotRetained,
+                    &[],
+                )
+                .unwrap();
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        stage.store(round + 1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    let stage = Arc::new(AtomicUsize::new(0));
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn({
+        let stage = stage.clone();
+        async move { client_task(stage).await }
+    });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "collect/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    // Service the client until the initial settings republish has been received back.
+    let mut polls_since_republish = None;
+    while polls_since_republish.unwrap_or(0) < 20 {
+        interface.update().unwrap();
+        if interface.just_finished_republish() {
+            polls_since_republish.replace(0);
+        }
+
+        polls_since_republish = polls_since_republish.map(|polls| polls + 1);
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    stage.store(1, Ordering::SeqCst);
+    wait_for_stage(&stage, 2).await;
+
+    // Both updates should be reported from a single poll.
+    let mut paths: heapless::Vec<heapless::String<128>, 4> = heapless::Vec::new();
+    assert!(interface
+        .handled_update_collecting(
+            |_path, old, new| {
+                *old = new.clone();
+                Result::<(), &'static str>::Ok(())
+            },
+            &mut paths,
+        )
+        .unwrap());
+    assert_eq!(paths, ["data", "gain"]);
+    assert_eq!(interface.settings().data, 5);
+    assert_eq!(interface.settings().gain, 1.5);
+
+    // Overflowing the path buffer is reported, but the settings are still applied.
+    stage.store(3, Ordering::SeqCst);
+    wait_for_stage(&stage, 4).await;
+
+    let mut paths: heapless::Vec<heapless::String<128>, 1> = heapless::Vec::new();
+    assert!(matches!(
+        interface.handled_update_collecting(
+            |_path, old, new| {
+                *old = new.clone();
+                Result::<(), &'static str>::Ok(())
+            },
+            &mut paths,
+        ),
+        Err(CollectError::PathBufferFull)
+    ));
+    assert_eq!(paths, ["data"]);
+    assert_eq!(interface.settings().data, 6);
+    assert_eq!(interface.settings().gain, 2.5);
+
+    task.await.expect("Client task failed");
+}
@@ -0,0 +1,89 @@
+//! Helpers shared by the MQTT integration tests.
+#![allow(dead_code)]
+
+use miniconf::minimq;
+use serde::Deserialize;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+use tokio::time::{Duration, Instant};
+
+/// The client used by the tests to publish requests to the device under test.
+pub type Tester = minimq::Minimq<Stack, StandardClock, 256, 1>;
+
+/// The time to wait for the device to respond before failing the test.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize)]
+struct Response {
+    code: u8,
+    msg: heapless::String<64>,
+}
+
+/// Publish a message, optionally requesting a response on `response_topic`.
+pub fn publish(mqtt: &mut Tester, topic: &str, value: &[u8], response_topic: Option<&str>) {
+    let properties: &[minimq::Property] = match response_topic {
+        Some(response_topic) => &[minimq::Property::ResponseTopic(response_topic)],
+        None => &[],
+    };
+
+    mqtt.client
+        .publish(
+            topic,
+            value,
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            properties,
+        )
+        .unwrap();
+}
+
+/// Poll the client, passing every received message to `handle` until it returns `true`.
+///
+/// # Panics
+/// If `handle` did not return `true` within the `TIMEOUT`.
+pub async fn poll_until(mqtt: &mut Tester, mut handle: impl FnMut(&str, &[u8]) -> bool) {
+    let deadline = Instant::now() + TIMEOUT;
+    let mut done = false;
+    loop {
+        mqtt.poll(|_client, topic, message, _properties| {
+            done |= handle(topic, message);
+        })
+        .unwrap();
+
+        if done {
+            return;
+        }
+
+        assert!(
+            Instant::now() < deadline,
+            "No response received within {:?}",
+            TIMEOUT
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+/// Publish a settings request and wait for the response on `response_topic`.
+///
+/// # Returns
+/// The code and message of the response.
+pub async fn request(
+    mqtt: &mut Tester,
+    topic: &str,
+    value: &[u8],
+    response_topic: &str,
+) -> (u8, String) {
+    publish(mqtt, topic, value, Some(response_topic));
+
+    let mut response = None;
+    poll_until(mqtt, |topic, message| {
+        if topic == response_topic {
+            let data: Response = serde_json_core::from_slice(message).unwrap().0;
+            response.replace((data.code, data.msg.as_str().to_string()));
+        }
+        response.is_some()
+    })
+    .await;
+
+    response.unwrap()
+}
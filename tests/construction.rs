@@ -0,0 +1,139 @@
+use miniconf::{ConstructionError, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    long_setting_name: u32,
+}
+
+fn construct<const MESSAGE_SIZE: usize, const MAX_TOPIC_LENGTH: usize>(
+    prefix: &str,
+) -> Result<(), ConstructionError<<Stack as miniconf::minimq::embedded_nal::TcpClientStack>::Error>>
+{
+    miniconf::MqttClient::<Settings, _, _, MESSAGE_SIZE, MAX_TOPIC_LENGTH>::new(
+        Stack,
+        "",
+        prefix,
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn topic_too_long() {
+    // The longest topic is `<prefix>/settings/long_setting_name`.
+    let prefix = "x".repeat(64 - "/settings/long_setting_name".len());
+    assert!(construct::<256, 64>(&prefix).is_ok());
+
+    let prefix = format!("{}x", prefix);
+    assert!(matches!(
+        construct::<256, 64>(&prefix),
+        Err(ConstructionError::TopicTooLong)
+    ));
+}
+
+#[test]
+fn invalid_prefix() {
+    for prefix in ["device/#", "device/+/a", "dev+ice", "device\0"] {
+        assert!(matches!(
+            construct::<256, 128>(prefix),
+            Err(ConstructionError::InvalidPrefix)
+        ));
+    }
+
+    assert!(construct::<256, 128>("site/device-1").is_ok());
+}
+
+#[test]
+fn message_size_too_small() {
+    // The topic fits within the maximum topic length, but a message publishing to it does not
+    // fit within the message size.
+    let prefix = "x".repeat(40);
+    assert!(construct::<128, 128>(&prefix).is_ok());
+    assert!(matches!(
+        construct::<64, 128>(&prefix),
+        Err(ConstructionError::MessageSizeTooSmall)
+    ));
+}
+
+#[test]
+fn settings_too_deep() {
+    type Cube = [[[u32; 1]; 1]; 1];
+
+    #[derive(Clone, Debug, Default, Miniconf)]
+    struct Deep {
+        data: [[[[Cube; 1]; 1]; 1]; 1],
+    }
+
+    let result = miniconf::MqttClient::<Deep, _, _, 256>::new(
+        Stack,
+        "",
+        "construction/deep",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Deep::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(ConstructionError::SettingsTooDeep { depth: 9, max: 8 })
+    ));
+}
+
+#[test]
+fn schema_topic_too_long() {
+    #[derive(Clone, Debug, Default, Miniconf)]
+    struct Short {
+        a: u32,
+    }
+
+    // The schema topics are longer than the settings topic `<prefix>/settings/a`.
+    let construct = |prefix: &str| {
+        miniconf::MqttClient::<Short, _, _, 256, 64>::new(
+            Stack,
+            "",
+            prefix,
+            "127.0.0.1".parse().unwrap(),
+            StandardClock::default(),
+            Short::default(),
+        )
+        .unwrap()
+        .schema_check(1, false)
+        .map(|_| ())
+    };
+
+    let prefix = "x".repeat(64 - "/schema/expected".len());
+    assert!(construct(&prefix).is_ok());
+
+    let prefix = format!("{}x", prefix);
+    assert!(matches!(
+        construct(&prefix),
+        Err(ConstructionError::TopicTooLong)
+    ));
+}
+
+#[test]
+fn invalid_client_id() {
+    let construct = |client_id: &str| {
+        miniconf::MqttClient::<Settings, _, _, 256>::new(
+            Stack,
+            client_id,
+            "device",
+            "127.0.0.1".parse().unwrap(),
+            StandardClock::default(),
+            Settings::default(),
+        )
+        .map(|_| ())
+    };
+
+    // Client IDs are limited to 64 bytes and must not contain null characters.
+    assert!(construct(&"x".repeat(64)).is_ok());
+    for client_id in ["x".repeat(65), String::from("device\0")] {
+        assert!(matches!(
+            construct(&client_id),
+            Err(ConstructionError::InvalidClientId)
+        ));
+    }
+}
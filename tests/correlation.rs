@@ -0,0 +1,100 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "correlation/device/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    let properties = [
+        minimq::Property::ResponseTopic(response_topic),
+        minimq::Property::CorrelationData(b"request-1"),
+    ];
+    mqtt.client
+        .publish(
+            "correlation/device/settings/data",
+            b"20",
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &properties,
+        )
+        .unwrap();
+
+    // The response is followed by the committed value, both carrying the correlation data.
+    let mut replies: Vec<Vec<u8>> = Vec::new();
+    while replies.len() < 2 {
+        mqtt.poll(|_client, topic, message, properties| {
+            assert_eq!(topic, response_topic);
+            assert!(properties
+                .iter()
+                .any(|prop| matches!(prop, minimq::Property::CorrelationData(b"request-1"))));
+            replies.push(message.to_vec());
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(replies[0], br#"{"code":0,"msg":"OK"}"#);
+
+    // The handler limited the requested value.
+    assert_eq!(replies[1], b"10");
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "correlation/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .reply_with_value(true);
+
+    while !task.is_finished() {
+        interface
+            .handled_update(|_path, old, new| {
+                *old = new.clone();
+                old.data = old.data.min(10);
+                Result::<(), &'static str>::Ok(())
+            })
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+    assert_eq!(interface.settings().data, 10);
+}
@@ -0,0 +1,162 @@
+use miniconf::{embedded_time::duration::Extensions, minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    gain: u32,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Many {
+    values: [u32; 6],
+}
+
+#[test]
+fn main() {
+    let _ = env_logger::try_init();
+
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "debounce/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .debounce(300.milliseconds());
+
+    let mut calls = Vec::new();
+    let mut handler = |path: &str, old: &mut Settings, new: &Settings| {
+        calls.push((path.to_string(), new.gain));
+        *old = new.clone();
+        Result::<(), &str>::Ok(())
+    };
+
+    // Wait for both clients to be connected.
+    for _ in 0..500 {
+        interface.handled_update(&mut handler).unwrap();
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+
+        if mqtt.client.is_connected() && interface.is_subscribed() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(interface.is_subscribed());
+
+    // Deliver a burst of updates to the same path.
+    for value in ["1", "2", "3"] {
+        mqtt.client
+            .publish(
+                "debounce/device/settings/gain",
+                value.as_bytes(),
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[],
+            )
+            .unwrap();
+    }
+
+    // The updates are coalesced during the window.
+    let start = std::time::Instant::now();
+    let mut applied = None;
+    while start.elapsed() < std::time::Duration::from_secs(2) {
+        interface.handled_update(&mut handler).unwrap();
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+
+        if applied.is_none() && interface.settings().gain != 0 {
+            applied.replace(start.elapsed());
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // Only the final value is applied once the window elapsed.
+    assert!(applied.unwrap() >= std::time::Duration::from_millis(300));
+    assert_eq!(calls, [("gain".to_string(), 3)]);
+    assert_eq!(interface.settings().gain, 3);
+}
+
+#[test]
+fn many_paths() {
+    let _ = env_logger::try_init();
+
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    let mut interface: miniconf::MqttClient<Many, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "debounce/many",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Many::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .debounce(300.milliseconds());
+
+    // Wait for both clients to be connected.
+    for _ in 0..500 {
+        interface.update().unwrap();
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+
+        if mqtt.client.is_connected() && interface.is_subscribed() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(interface.is_subscribed());
+    mqtt.client
+        .subscribe("debounce/many/response", &[])
+        .unwrap();
+
+    // Deliver updates to more paths than can be coalesced.
+    for index in 0..6 {
+        mqtt.client
+            .publish(
+                &format!("debounce/many/settings/values/{}", index),
+                format!("{}", index + 1).as_bytes(),
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[minimq::Property::ResponseTopic("debounce/many/response")],
+            )
+            .unwrap();
+    }
+
+    let mut responses = Vec::new();
+    let start = std::time::Instant::now();
+    while start.elapsed() < std::time::Duration::from_secs(2) {
+        interface.update().unwrap();
+        mqtt.poll(|_, _, message, _| responses.push(String::from_utf8(message.to_vec()).unwrap()))
+            .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // No update is rejected, and all of them are applied. The four coalesced updates additionally
+    // report their outcome once applied.
+    assert_eq!(responses.len(), 6 + 4);
+    assert!(responses.iter().all(|response| !response.contains("Busy")));
+    assert_eq!(interface.settings().values, [1, 2, 3, 4, 5, 6]);
+}
@@ -0,0 +1,48 @@
+use miniconf::{Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Inner {
+    data: [u32; 2],
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    a: u32,
+    inner: Inner,
+    optional: Option<Inner>,
+}
+
+fn deep_path(prefix: &str) -> String {
+    let mut path = String::from(prefix);
+    for _ in 0..200 {
+        path.push_str("/x");
+    }
+    path
+}
+
+#[test]
+fn deep_paths_rejected() {
+    // Run on a small stack so that recursing once per path segment would overflow it.
+    std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(|| {
+            let mut settings = Settings {
+                optional: Some(Inner::default()),
+                ..Default::default()
+            };
+            let mut data = [0; 64];
+
+            for prefix in ["a", "inner/data/0", "optional/data/1"] {
+                let path = deep_path(prefix);
+                assert_eq!(settings.set(&path, b"1"), Err(Error::PathTooLong));
+                assert_eq!(settings.get(&path, &mut data), Err(Error::PathTooLong));
+            }
+
+            // Paths that do not exist in the type are rejected at the first unknown segment.
+            let path = deep_path("inner");
+            assert_eq!(settings.set(&path, b"1"), Err(Error::PathNotFound));
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
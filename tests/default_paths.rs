@@ -0,0 +1,79 @@
+use miniconf::Miniconf;
+
+#[derive(Miniconf, Clone)]
+struct Inner {
+    gain: f32,
+    offset: i32,
+}
+
+impl Default for Inner {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            offset: -2,
+        }
+    }
+}
+
+#[derive(Miniconf, Clone)]
+struct Settings {
+    rate: u32,
+    channels: [Inner; 2],
+    value: Option<u8>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rate: 100,
+            channels: Default::default(),
+            value: None,
+        }
+    }
+}
+
+#[test]
+fn template() {
+    let template: Vec<_> = miniconf::default_paths::<Settings, 64, 32, 4>()
+        .unwrap()
+        .map(|entry| {
+            let (path, value) = entry.unwrap();
+            (path.to_string(), String::from_utf8(value.to_vec()).unwrap())
+        })
+        .collect();
+
+    // Absent options are not part of the template.
+    assert_eq!(
+        template,
+        [
+            ("rate", "100"),
+            ("channels/0/gain", "1.0"),
+            ("channels/0/offset", "-2"),
+            ("channels/1/gain", "1.0"),
+            ("channels/1/offset", "-2"),
+        ]
+        .map(|(path, value)| (path.to_string(), value.to_string()))
+    );
+}
+
+#[test]
+fn reproduces_defaults() {
+    let mut settings = Settings {
+        rate: 5,
+        ..Default::default()
+    };
+    settings.channels[1].offset = 7;
+
+    for entry in miniconf::default_paths::<Settings, 64, 32, 4>().unwrap() {
+        let (path, value) = entry.unwrap();
+        settings.set(&path, &value).unwrap();
+    }
+
+    assert_eq!(settings.rate, 100);
+    assert_eq!(settings.channels[1].offset, -2);
+}
+
+#[test]
+fn insufficient_depth() {
+    assert!(miniconf::default_paths::<Settings, 64, 32, 1>().is_err());
+}
@@ -0,0 +1,109 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, PartialEq, Miniconf)]
+struct Settings {
+    gain: f32,
+    offset: f32,
+}
+
+type Interface = miniconf::MqttClient<Settings, Stack, StandardClock, 256>;
+type Tester = minimq::Minimq<Stack, StandardClock, 256, 1>;
+
+/// Publish an update and poll the interface until the handler accepted it.
+fn update(interface: &mut Interface, mqtt: &mut Tester, path: &str, value: &[u8]) {
+    let mut topic = String::from("deferred_commit/device/settings/");
+    topic.push_str(path);
+    mqtt.client
+        .publish(
+            &topic,
+            value,
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[],
+        )
+        .unwrap();
+
+    let mut updated = false;
+    for _ in 0..100 {
+        updated |= interface
+            .handled_update(|_, old, new| {
+                *old = new.clone();
+                Result::<(), &str>::Ok(())
+            })
+            .unwrap();
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+
+        if updated {
+            return;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    panic!("Update of `{}` was not received", path);
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: Tester = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    let mut interface: Interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "deferred_commit/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .deferred_commit(true);
+
+    // Wait for both clients to be connected and the interface to be subscribed.
+    for _ in 0..500 {
+        interface.update().unwrap();
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+
+        if mqtt.client.is_connected() && interface.is_subscribed() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(interface.is_subscribed());
+
+    // Committing without updates has no effect.
+    interface.commit();
+    assert_eq!(interface.settings(), &Settings::default());
+
+    // Accepted updates coalesce without affecting the live settings.
+    update(&mut interface, &mut mqtt, "gain", b"2.0");
+    update(&mut interface, &mut mqtt, "offset", b"-1.0");
+    assert_eq!(interface.settings(), &Settings::default());
+
+    // All updates become live on commit.
+    interface.commit();
+    assert_eq!(
+        interface.settings(),
+        &Settings {
+            gain: 2.0,
+            offset: -1.0
+        }
+    );
+
+    update(&mut interface, &mut mqtt, "gain", b"3.0");
+    assert_eq!(interface.settings().gain, 2.0);
+    interface.commit();
+    assert_eq!(interface.settings().gain, 3.0);
+}
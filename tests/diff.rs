@@ -0,0 +1,81 @@
+use miniconf::Miniconf;
+
+#[derive(Miniconf, Default, Clone)]
+struct Inner {
+    gain: f32,
+    offset: i32,
+}
+
+#[derive(Miniconf, Default, Clone)]
+struct Settings {
+    rate: u32,
+    channels: [Inner; 2],
+    value: Option<u8>,
+}
+
+#[test]
+fn identical() {
+    let settings = Settings::default();
+    assert_eq!(
+        miniconf::diff::<_, 64, 32, 4>(&settings, &settings.clone())
+            .unwrap()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn nested_difference() {
+    let current = Settings::default();
+    let mut desired = current.clone();
+    desired.channels[1].offset = -3;
+
+    let updates: Vec<_> = miniconf::diff::<_, 64, 32, 4>(&current, &desired)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].0, "channels/1/offset");
+    assert_eq!(&updates[0].1[..], b"-3");
+
+    // Applying the updates reconciles the settings.
+    let mut reconciled = current.clone();
+    for (path, value) in updates {
+        reconciled.set(&path, &value).unwrap();
+    }
+    assert_eq!(
+        miniconf::diff::<_, 64, 32, 4>(&reconciled, &desired)
+            .unwrap()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn absent_option() {
+    let current = Settings::default();
+    let mut desired = current.clone();
+    desired.value.replace(4);
+
+    let updates: Vec<_> = miniconf::diff::<_, 64, 32, 4>(&current, &desired)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].0, "value");
+}
+
+#[test]
+fn insufficient_buffers() {
+    let current = Settings::default();
+    let mut desired = current.clone();
+    desired.rate = 1000;
+
+    assert!(miniconf::diff::<_, 64, 32, 1>(&current, &desired).is_err());
+
+    let mut updates = miniconf::diff::<_, 64, 2, 4>(&current, &desired).unwrap();
+    assert!(matches!(
+        updates.next(),
+        Some(Err(miniconf::Error::SerializationFailed))
+    ));
+}
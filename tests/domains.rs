@@ -0,0 +1,132 @@
+use miniconf::{Domains, DomainsError, Error, Miniconf};
+
+#[derive(Miniconf, Default, Clone, Debug, PartialEq)]
+struct Network {
+    port: u16,
+    dhcp: bool,
+}
+
+#[derive(Miniconf, Default, Clone, Debug, PartialEq)]
+struct Filter {
+    cutoff: f32,
+}
+
+#[derive(Miniconf, Default, Clone, Debug, PartialEq)]
+struct Dsp {
+    gain: f32,
+    filters: [Filter; 2],
+}
+
+fn domains() -> Domains<(Network, Dsp), 2> {
+    Domains::new(&["net", "dsp"], (Network::default(), Dsp::default())).unwrap()
+}
+
+#[test]
+fn set_each_domain() {
+    let mut settings = domains();
+
+    settings.set("net/port", b"1883").unwrap();
+    settings.set("dsp/filters/1/cutoff", b"2.5").unwrap();
+
+    assert_eq!(settings.0.port, 1883);
+    assert_eq!(settings.1.filters[1].cutoff, 2.5);
+
+    let mut buffer = [0; 16];
+    let len = settings.get("net/port", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"1883");
+
+    // The domains remain independent structures.
+    let (network, dsp) = settings.into_inner();
+    assert_eq!(
+        network,
+        Network {
+            port: 1883,
+            dhcp: false
+        }
+    );
+    assert_eq!(dsp.filters[1].cutoff, 2.5);
+}
+
+#[test]
+fn invalid_paths() {
+    let mut settings = domains();
+
+    assert_eq!(settings.set("io/port", b"1"), Err(Error::PathNotFound));
+    assert_eq!(settings.set("net", b"1"), Err(Error::ExpectedLeaf));
+    assert_eq!(
+        settings.set("net/port/extra", b"1"),
+        Err(Error::PathTooLong)
+    );
+}
+
+#[test]
+fn iteration() {
+    let settings = domains();
+
+    // Domains are iterated in order.
+    let mut state = [0; 8];
+    let paths: Vec<_> = settings
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .map(|path| path.as_str().to_owned())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "net/port",
+            "net/dhcp",
+            "dsp/gain",
+            "dsp/filters/0/cutoff",
+            "dsp/filters/1/cutoff"
+        ]
+    );
+
+    let metadata = settings.get_metadata();
+    assert_eq!(metadata.max_topic_size, "dsp/filters/0/cutoff".len());
+    assert_eq!(metadata.max_depth, 5);
+    assert_eq!(metadata.path_count, 5);
+}
+
+#[test]
+fn three_domains() {
+    let mut settings = Domains::new(
+        &["net", "dsp", "io"],
+        (Network::default(), Dsp::default(), Filter::default()),
+    )
+    .unwrap();
+
+    settings.set("io/cutoff", b"1.0").unwrap();
+    assert_eq!(settings.2.cutoff, 1.0);
+    assert_eq!(settings.get_metadata().path_count, 6);
+}
+
+#[test]
+fn borrowed_domains() {
+    let mut network = Network::default();
+    let mut dsp = Dsp::default();
+
+    let mut settings = Domains::new(&["net", "dsp"], (&mut network, &mut dsp)).unwrap();
+    settings.set("net/dhcp", b"true").unwrap();
+    settings.set("dsp/filters/0/cutoff", b"0.5").unwrap();
+    assert_eq!(settings.get_metadata().path_count, 5);
+
+    // The domains remain owned by their users.
+    assert!(network.dhcp);
+    assert_eq!(dsp.filters[0].cutoff, 0.5);
+}
+
+#[test]
+fn invalid_names() {
+    assert_eq!(
+        Domains::new(&["net", "net"], (Network::default(), Dsp::default())),
+        Err(DomainsError::DuplicateName("net"))
+    );
+    assert_eq!(
+        Domains::new(&["net", "dsp/0"], (Network::default(), Dsp::default())),
+        Err(DomainsError::InvalidName("dsp/0"))
+    );
+    assert_eq!(
+        Domains::new(&["", "dsp"], (Network::default(), Dsp::default())),
+        Err(DomainsError::InvalidName(""))
+    );
+}
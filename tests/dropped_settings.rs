@@ -0,0 +1,121 @@
+use miniconf::{minimq, Miniconf, MiniconfAtomic};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, MiniconfAtomic, Serialize, Deserialize)]
+struct Label {
+    text: heapless::String<512>,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    label: Label,
+    more: u8,
+}
+
+/// A logger recording all warnings.
+struct WarningLog(Mutex<Vec<String>>);
+
+impl log::Log for WarningLog {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static WARNINGS: WarningLog = WarningLog(Mutex::new(Vec::new()));
+
+async fn verify_settings() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    // Subscribe to the settings topic.
+    mqtt.client
+        .subscribe("dropped-settings/device/settings/#", &[])
+        .unwrap();
+
+    // Wait the other device to connect and publish settings.
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+    let mut received = Vec::new();
+    for _ in 0..50 {
+        mqtt.poll(|_, topic, _value, _properties| {
+            received.push(topic.to_string());
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // The oversize setting is skipped while the remaining settings are still published.
+    received.sort();
+    assert_eq!(
+        received,
+        [
+            "dropped-settings/device/settings/data",
+            "dropped-settings/device/settings/more",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn main() {
+    log::set_logger(&WARNINGS).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    // Spawn a task to receive the republished settings.
+    let task = tokio::task::spawn(async move { verify_settings().await });
+
+    // The label does not fit within a single message.
+    let mut settings = Settings::default();
+    while settings.label.text.push('x').is_ok() {}
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "dropped-settings/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        settings,
+    )
+    .unwrap();
+
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Unexpected settings received");
+
+    assert_eq!(interface.stats().republishes, 1);
+    assert_eq!(interface.stats().dropped_settings, 1);
+    assert!(WARNINGS
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|warning| warning.contains("`label`")));
+}
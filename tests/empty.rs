@@ -0,0 +1,63 @@
+use miniconf::Miniconf;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Empty {}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    empty: Empty,
+    values: [u32; 0],
+    value: Option<u32>,
+}
+
+#[test]
+fn no_paths() {
+    let settings = Settings::default();
+
+    let metadata = settings.get_metadata();
+    assert_eq!(metadata.max_topic_size, "values".len());
+
+    let mut state = [0; 8];
+    assert!(settings
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .next()
+        .is_none());
+
+    let mut state = [0; 8];
+    assert!(Empty::default()
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .next()
+        .is_none());
+}
+
+#[tokio::test]
+async fn republish() {
+    env_logger::init();
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "empty/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    // The client should complete republishing without any settings paths and become active.
+    for _ in 0..500 {
+        interface.update().unwrap();
+        if interface.just_finished_republish() {
+            return;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    panic!("Republish did not complete");
+}
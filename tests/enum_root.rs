@@ -0,0 +1,102 @@
+use miniconf::{minimq, Miniconf};
+use serde::{Deserialize, Serialize};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+/// The mutually-exclusive operating modes of the device.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Miniconf)]
+enum Mode {
+    #[default]
+    Idle,
+    Fixed(f32),
+    Sweep([f32; 2]),
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "enum-root/tester/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    for (topic, value) in [
+        // The settings topic itself selects the active variant.
+        ("enum-root/device/settings", r#"{"Fixed":1.5}"#),
+        ("enum-root/device/settings", r#""Idle""#),
+        // Sub-paths address the data of a variant.
+        ("enum-root/device/settings/Sweep", "[1.0,2.0]"),
+    ] {
+        mqtt.client
+            .publish(
+                topic,
+                value.as_bytes(),
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[minimq::Property::ResponseTopic(response_topic)],
+            )
+            .unwrap();
+
+        let mut response = None;
+        while response.is_none() {
+            mqtt.poll(|_client, _topic, message, _properties| {
+                response.replace(message.to_vec());
+            })
+            .unwrap();
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(response.unwrap(), br#"{"code":0,"msg":"OK"}"#);
+    }
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    let mut interface: miniconf::MqttClient<Mode, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "enum-root/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Mode::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false);
+
+    let mut modes = Vec::new();
+    while !task.is_finished() {
+        if interface.update().unwrap() {
+            modes.push(interface.settings().clone());
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    assert_eq!(
+        modes,
+        [Mode::Fixed(1.5), Mode::Idle, Mode::Sweep([1.0, 2.0])]
+    );
+}
@@ -1,4 +1,4 @@
-use miniconf::Miniconf;
+use miniconf::{Error, Miniconf};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -28,6 +28,37 @@ fn simple_enum() {
     assert_eq!(metadata.max_topic_size, "v".len());
 }
 
+#[test]
+fn get_variant() {
+    #[derive(Miniconf, Debug, Deserialize, Serialize, PartialEq)]
+    enum Variant {
+        A,
+        B,
+    }
+
+    #[derive(Miniconf, Debug)]
+    struct S {
+        v: Variant,
+    }
+
+    let mut s = S { v: Variant::A };
+    s.set("v", b"\"B\"").unwrap();
+
+    // The active variant is serialized as its name.
+    let mut buffer = [0; 16];
+    let field = "v".split('/').peekable();
+    let len = s.string_get(field, &mut buffer).unwrap();
+    assert_eq!(len, "\"B\"".len());
+    assert_eq!(&buffer[..len], b"\"B\"");
+
+    // The variant name must fit within the buffer.
+    let field = "v".split('/').peekable();
+    assert_eq!(
+        s.string_get(field, &mut buffer[..2]),
+        Err(Error::SerializationFailed)
+    );
+}
+
 #[test]
 fn invalid_enum() {
     #[derive(Miniconf, Debug, Serialize, Deserialize, PartialEq)]
@@ -47,3 +78,93 @@ fn invalid_enum() {
 
     assert!(s.string_set(field, "\"C\"".as_bytes()).is_err());
 }
+
+#[test]
+fn variant_data() {
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Slope {
+        Rising,
+        Falling,
+    }
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Edge {
+        slope: Slope,
+        level: f32,
+    }
+
+    #[derive(Miniconf, Debug, Deserialize, Serialize, PartialEq)]
+    enum Trigger {
+        Off,
+        Edge(Edge),
+    }
+
+    #[derive(Miniconf, Debug)]
+    struct S {
+        trigger: Trigger,
+    }
+
+    let mut s = S {
+        trigger: Trigger::Off,
+    };
+    let mut buffer = [0; 64];
+
+    // Data of inactive variants cannot be retrieved.
+    assert_eq!(
+        s.get("trigger/Edge", &mut buffer),
+        Err(Error::InactiveVariant)
+    );
+
+    // Setting the variant data switches to the variant.
+    s.set("trigger/Edge", b"{\"slope\":\"rising\",\"level\":1.0}")
+        .unwrap();
+    assert_eq!(
+        s.trigger,
+        Trigger::Edge(Edge {
+            slope: Slope::Rising,
+            level: 1.0
+        })
+    );
+
+    let len = s.get("trigger/Edge", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"{\"slope\":\"rising\",\"level\":1.0}");
+
+    // The variant data is atomic.
+    assert_eq!(
+        s.set("trigger/Edge/level", b"2.0"),
+        Err(Error::AtomicUpdateRequired)
+    );
+
+    // Unit variants can still be selected at the enum path.
+    s.set("trigger", b"\"Off\"").unwrap();
+    assert_eq!(s.trigger, Trigger::Off);
+    assert_eq!(s.set("trigger/Off", b"null"), Err(Error::PathTooLong));
+}
+
+#[test]
+fn top_level_enum() {
+    #[derive(Miniconf, Debug, Deserialize, Serialize, PartialEq)]
+    enum Mode {
+        Idle,
+        Fixed(f32),
+    }
+
+    let mut mode = Mode::Idle;
+    let mut buffer = [0; 32];
+
+    // The empty path addresses the enum itself.
+    mode.set("", b"{\"Fixed\":1.5}").unwrap();
+    assert_eq!(mode, Mode::Fixed(1.5));
+
+    let len = mode.get("", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"{\"Fixed\":1.5}");
+
+    mode.set("Fixed", b"2.5").unwrap();
+    assert_eq!(mode, Mode::Fixed(2.5));
+
+    // The enum is iterated as a single empty path.
+    let mut state = [0; 4];
+    let paths: Vec<_> = mode.into_iter::<32>(&mut state).unwrap().collect();
+    assert_eq!(paths, [""]);
+}
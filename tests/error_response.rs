@@ -0,0 +1,88 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+mod common;
+use common::Tester;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    gain: f32,
+}
+
+// An error description that is too long to fit in a response.
+const LONG_ERROR: &str =
+    "The requested gain exceeds the limits of the amplifier of the selected channel";
+
+async fn request(mqtt: &mut Tester, topic: &str, value: &[u8]) -> (u8, String) {
+    common::request(mqtt, topic, value, "error/device/response").await
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    mqtt.client.subscribe("error/device/response", &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    // A binary payload that is neither UTF-8 nor JSON should report the deserialization failure.
+    let (code, msg) = request(&mut mqtt, "error/device/settings/data", b"\xff\xfe\x00").await;
+    assert!(code != 0);
+    assert!(msg.contains("Deserialization"), "{}", msg);
+
+    // Long error descriptions are truncated rather than replaced with a generic message.
+    let (code, msg) = request(&mut mqtt, "error/device/settings/gain", b"10.0").await;
+    assert!(code != 0);
+    assert_eq!(msg, LONG_ERROR[..64]);
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "error/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    while !task.is_finished() {
+        interface
+            .handled_update(|path, old, new| {
+                if path == "gain" {
+                    return Err(LONG_ERROR);
+                }
+
+                *old = new.clone();
+                Ok(())
+            })
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+}
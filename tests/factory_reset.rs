@@ -0,0 +1,111 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, PartialEq, Miniconf)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Miniconf)]
+struct Settings {
+    data: u32,
+    inner: Inner,
+    enabled: bool,
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "factory-reset/tester/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    mqtt.client
+        .publish(
+            "factory-reset/device/settings",
+            b"null",
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[minimq::Property::ResponseTopic(response_topic)],
+        )
+        .unwrap();
+
+    let mut response = None;
+    while response.is_none() {
+        mqtt.poll(|_client, _topic, message, _properties| {
+            response.replace(message.to_vec());
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(response.unwrap(), br#"{"code":0,"msg":"OK"}"#);
+
+    // Allow the device to republish the settings.
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    let initial = Settings {
+        data: 5,
+        inner: Inner { gain: 2.0 },
+        enabled: true,
+    };
+
+    // Settings are only republished after the reset.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "factory-reset/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        initial,
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .factory_reset(true);
+
+    let mut paths = Vec::new();
+    while !task.is_finished() {
+        interface
+            .handled_update(|path, old, new| {
+                paths.push(path.to_string());
+                *old = new.clone();
+                Result::<(), &'static str>::Ok(())
+            })
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    // The handler is called for the entire settings. Any further calls stem from the device
+    // receiving its own republished settings.
+    assert_eq!(paths.first().map(String::as_str), Some(""));
+    assert_eq!(interface.settings(), &Settings::default());
+    assert_eq!(interface.stats().republishes, 1);
+}
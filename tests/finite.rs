@@ -0,0 +1,53 @@
+use miniconf::{Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    #[miniconf(finite)]
+    gain: f32,
+    #[miniconf(finite, min = 0.0, max = 10.0)]
+    limit: f64,
+    unchecked: f32,
+}
+
+#[test]
+fn finite_value() {
+    let mut settings = Settings::default();
+
+    settings.set("gain", b"-2.5").unwrap();
+    assert_eq!(settings.gain, -2.5);
+
+    settings.set("limit", b"10").unwrap();
+    assert_eq!(settings.limit, 10.0);
+}
+
+#[test]
+fn infinite_value() {
+    let mut settings = Settings {
+        gain: 1.0,
+        limit: 2.0,
+        unchecked: 0.0,
+    };
+
+    // Values exceeding the range of the type are parsed as infinite.
+    assert_eq!(settings.set("gain", b"1e999"), Err(Error::NotFinite));
+    assert_eq!(settings.set("gain", b"-1e999"), Err(Error::NotFinite));
+    assert_eq!(settings.set("limit", b"1e999"), Err(Error::NotFinite));
+    assert_eq!(settings.gain, 1.0);
+    assert_eq!(settings.limit, 2.0);
+
+    // Fields without the attribute accept infinite values.
+    settings.set("unchecked", b"1e999").unwrap();
+    assert!(settings.unchecked.is_infinite());
+}
+
+#[test]
+fn nan_value() {
+    let mut settings = Settings {
+        gain: 1.0,
+        ..Default::default()
+    };
+
+    assert!(settings.set("gain", b"NaN").is_err());
+    assert!(settings.set("gain", b"\"NaN\"").is_err());
+    assert_eq!(settings.gain, 1.0);
+}
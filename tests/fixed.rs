@@ -0,0 +1,60 @@
+#![cfg(feature = "fixed")]
+
+use fixed::types::{I16F16, U8F8};
+use miniconf::{Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    gain: I16F16,
+    coefficients: [U8F8; 2],
+}
+
+#[test]
+fn round_trip() {
+    let mut settings = Settings::default();
+    let mut buffer = [0; 32];
+
+    settings.set("gain", b"-2.75").unwrap();
+    assert_eq!(settings.gain, I16F16::from_num(-2.75));
+
+    let len = settings.get("gain", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"-2.75");
+
+    settings.set("coefficients/1", b" 0.5 ").unwrap();
+    let len = settings.get("coefficients/1", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"0.5");
+}
+
+#[test]
+fn rounding() {
+    let mut settings = Settings::default();
+
+    // The closest representable value is used.
+    settings.set("coefficients/0", b"0.1").unwrap();
+    assert_eq!(settings.coefficients[0], U8F8::from_bits(26));
+}
+
+#[test]
+fn out_of_range() {
+    let mut settings = Settings::default();
+    let invalid = Err(Error::Deserialization(
+        serde_json_core::de::Error::InvalidNumber,
+    ));
+
+    assert_eq!(settings.set("gain", b"32768"), invalid);
+    assert_eq!(settings.set("coefficients/0", b"256"), invalid);
+    assert_eq!(settings.set("coefficients/0", b"-1"), invalid);
+    assert_eq!(settings.set("gain", b"\"1.5\""), invalid);
+
+    // Rejected values leave the setting unchanged.
+    assert_eq!(settings.gain, I16F16::ZERO);
+}
+
+#[test]
+fn iteration() {
+    let settings = Settings::default();
+
+    let mut state = [0; 8];
+    let paths: Vec<_> = settings.into_iter::<32>(&mut state).unwrap().collect();
+    assert_eq!(paths, ["gain", "coefficients/0", "coefficients/1"]);
+}
@@ -0,0 +1,112 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+mod common;
+use common::Tester;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Channel {
+    gain: u32,
+    enabled: bool,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    channels: [Channel; 2],
+    rate: u32,
+}
+
+/// Publish a request and collect all messages until the response to the request.
+async fn request(mqtt: &mut Tester, topic: &str) -> Vec<String> {
+    common::publish(mqtt, topic, b"", Some("get-request/tester/response"));
+
+    let mut messages = Vec::new();
+    common::poll_until(mqtt, |_topic, message| {
+        let message = String::from_utf8(message.to_vec()).unwrap();
+        let done = message.contains("\"code\"");
+        messages.push(message);
+        done
+    })
+    .await;
+
+    messages
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    mqtt.client
+        .subscribe("get-request/tester/response", &[])
+        .unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    // Values of all leaves below the path are published, followed by the response.
+    let messages = request(&mut mqtt, "get-request/device/get/channels/1").await;
+    assert_eq!(
+        messages,
+        [
+            r#"{"path":"channels/1/gain","value":7}"#,
+            r#"{"path":"channels/1/enabled","value":true}"#,
+            r#"{"code":0,"msg":"OK"}"#,
+        ]
+    );
+
+    // A wildcard requests all settings.
+    let messages = request(&mut mqtt, "get-request/device/get/#").await;
+    assert_eq!(messages.len(), 6);
+    assert!(messages.contains(&String::from(r#"{"path":"rate","value":100}"#)));
+    assert!(messages.contains(&String::from(r#"{"path":"channels/0/gain","value":3}"#)));
+
+    // Unknown paths are rejected.
+    let messages = request(&mut mqtt, "get-request/device/get/channels/0/phase").await;
+    assert_eq!(messages, [r#"{"code":255,"msg":"PathNotFound"}"#]);
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    let mut settings = Settings::default();
+    settings.channels[0].gain = 3;
+    settings.channels[1].gain = 7;
+    settings.channels[1].enabled = true;
+    settings.rate = 100;
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "get-request/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        settings,
+    )
+    .unwrap()
+    .republish_on_connect(false);
+
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+}
@@ -0,0 +1,117 @@
+use miniconf::{embedded_time::duration::Extensions, minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+type Interface = miniconf::MqttClient<Settings, Stack, StandardClock, 256>;
+type Tester = minimq::Minimq<Stack, StandardClock, 256, 1>;
+
+fn interface(prefix: &str, ignore: bool) -> Interface {
+    let mut interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        prefix,
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .ignore_unknown_paths(ignore);
+
+    interface
+        .block_until_active(|_, _, _| Result::<(), &str>::Ok(()), 10.seconds())
+        .unwrap();
+
+    interface
+}
+
+/// Publish a request and collect the responses of the interface to it.
+fn request(interface: &mut Interface, mqtt: &mut Tester, topic: &str, value: &[u8]) -> Vec<String> {
+    let response_topic = "ignore_unknown_paths/response";
+    mqtt.client
+        .publish(
+            topic,
+            value,
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[minimq::Property::ResponseTopic(response_topic)],
+        )
+        .unwrap();
+
+    let mut responses = Vec::new();
+    for _ in 0..100 {
+        interface.update().unwrap();
+        mqtt.poll(|_, topic, message, _| {
+            if topic == response_topic {
+                responses.push(String::from_utf8(message.to_vec()).unwrap());
+            }
+        })
+        .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    responses
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: Tester = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    mqtt.client
+        .subscribe("ignore_unknown_paths/response", &[])
+        .unwrap();
+    while mqtt.client.subscriptions_pending() {
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // Unknown paths are rejected by default.
+    let mut strict = interface("ignore_unknown_paths/strict", false);
+    let responses = request(
+        &mut strict,
+        &mut mqtt,
+        "ignore_unknown_paths/strict/settings/.meta",
+        b"1",
+    );
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].contains("PathNotFound"), "{}", responses[0]);
+
+    // Unknown paths are ignored without a response when enabled.
+    let mut lenient = interface("ignore_unknown_paths/lenient", true);
+    let responses = request(
+        &mut lenient,
+        &mut mqtt,
+        "ignore_unknown_paths/lenient/settings/.meta",
+        b"1",
+    );
+    assert!(responses.is_empty(), "{:?}", responses);
+
+    // Known paths are still updated.
+    let responses = request(
+        &mut lenient,
+        &mut mqtt,
+        "ignore_unknown_paths/lenient/settings/data",
+        b"5",
+    );
+    assert_eq!(responses.len(), 1);
+    assert_eq!(lenient.settings().data, 5);
+}
@@ -0,0 +1,127 @@
+use miniconf::{minimq, Miniconf};
+use serde::Deserialize;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+/// Settings that do not implement `Clone`.
+#[derive(Debug, Default, Miniconf)]
+struct Settings {
+    gain: u32,
+    enabled: bool,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    code: u8,
+    msg: heapless::String<64>,
+}
+
+type Interface = miniconf::MqttClient<Settings, Stack, StandardClock, 256>;
+type Tester = minimq::Minimq<Stack, StandardClock, 256, 1>;
+
+const RESPONSE_TOPIC: &str = "in_place_update/response";
+
+/// Validate updates before they are applied, rejecting gains above 10.
+fn validate(path: &str, _settings: &Settings, value: &[u8]) -> Result<(), &'static str> {
+    match (path, serde_json_core::from_slice::<u32>(value)) {
+        ("gain", Ok((gain, _))) if gain > 10 => Err("Gain too high"),
+        _ => Ok(()),
+    }
+}
+
+fn request(interface: &mut Interface, mqtt: &mut Tester, path: &str, value: &[u8]) -> (u8, String) {
+    let mut topic = String::from("in_place_update/device/settings/");
+    topic.push_str(path);
+    mqtt.client
+        .publish(
+            &topic,
+            value,
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[minimq::Property::ResponseTopic(RESPONSE_TOPIC)],
+        )
+        .unwrap();
+
+    let mut responses = Vec::new();
+    for _ in 0..100 {
+        interface.handled_update_in_place(validate).unwrap();
+        mqtt.poll(|_, _, message, _| {
+            let response: Response = serde_json_core::from_slice(message).unwrap().0;
+            responses.push((response.code, response.msg.as_str().to_string()));
+        })
+        .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert_eq!(responses.len(), 1);
+    responses.remove(0)
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: Tester = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    let mut interface: Interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "in_place_update/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false);
+
+    // Wait for both clients to be subscribed.
+    let mut subscribed = false;
+    for _ in 0..500 {
+        interface.handled_update_in_place(validate).unwrap();
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+
+        if mqtt.client.is_connected() && !subscribed {
+            mqtt.client.subscribe(RESPONSE_TOPIC, &[]).unwrap();
+            subscribed = true;
+        }
+
+        if subscribed && !mqtt.client.subscriptions_pending() && interface.is_subscribed() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(interface.is_subscribed());
+
+    // Accepted updates are applied directly.
+    let (code, _) = request(&mut interface, &mut mqtt, "gain", b"7");
+    assert_eq!(code, 0);
+    assert_eq!(interface.settings().gain, 7);
+
+    let (code, _) = request(&mut interface, &mut mqtt, "enabled", b"true");
+    assert_eq!(code, 0);
+    assert!(interface.settings().enabled);
+
+    // Updates rejected by the handler are not applied.
+    let (code, msg) = request(&mut interface, &mut mqtt, "gain", b"12");
+    assert!(code != 0);
+    assert_eq!(msg, "Gain too high");
+    assert_eq!(interface.settings().gain, 7);
+
+    // Values that fail to deserialize leave the settings unchanged.
+    let (code, _) = request(&mut interface, &mut mqtt, "gain", b"\"high\"");
+    assert!(code != 0);
+    assert_eq!(interface.settings().gain, 7);
+
+    // Invalid paths are reported.
+    let (code, _) = request(&mut interface, &mut mqtt, "offset", b"1");
+    assert!(code != 0);
+}
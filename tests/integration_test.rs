@@ -91,17 +91,13 @@ fn main() -> std::io::Result<()> {
     let localhost = "127.0.0.1".parse().unwrap();
 
     // Construct a Minimq client to the broker for publishing requests.
-    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
-        localhost,
-        "tester",
-        Stack::default(),
-        StandardClock::default(),
-    )
-    .unwrap();
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> =
+        miniconf::minimq::Minimq::new(localhost, "tester", Stack, StandardClock::default())
+            .unwrap();
 
     // Construct a settings configuration interface.
     let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
-        Stack::default(),
+        Stack,
         "",
         "device",
         localhost,
@@ -127,8 +123,8 @@ fn main() -> std::io::Result<()> {
         match state {
             TestState::Started(_) => {
                 if timer.is_complete() && mqtt.client.is_connected() {
-                    // Subscribe to the default device log topic.
-                    mqtt.client.subscribe("device/log", &[]).unwrap();
+                    // Subscribe to the default device response topic.
+                    mqtt.client.subscribe("device/response", &[]).unwrap();
 
                     // Send a request to set a property.
                     info!("Sending first settings value");
@@ -1,4 +1,4 @@
-use miniconf::Miniconf;
+use miniconf::{IterError, Miniconf};
 
 #[derive(Miniconf, Default)]
 struct Inner {
@@ -21,7 +21,10 @@ fn insufficient_space() {
 
     // Ensure that we can't iterate if we make a state vector that is too small.
     let mut small_state = [0; 2];
-    assert!(settings.into_iter::<256>(&mut small_state).is_err());
+    assert_eq!(
+        settings.into_iter::<256>(&mut small_state).err(),
+        Some(IterError::InsufficientStateDepth { needed: 3 })
+    );
 
     // Ensure that we can't iterate if the topic buffer is too small.
     let mut state = [0; 10];
@@ -40,10 +43,81 @@ fn test_iteration() {
 
     let mut iter_state = [0; 32];
     for field in settings.into_iter::<256>(&mut iter_state).unwrap() {
-        assert!(iterated.contains_key(&field.as_str().to_string()));
+        assert!(iterated.contains_key(field.as_str()));
         iterated.insert(field.as_str().to_string(), true);
     }
 
     // Ensure that all fields were iterated.
-    assert!(iterated.iter().map(|(_, value)| value).all(|&x| x));
+    assert!(iterated.values().all(|&x| x));
+}
+
+#[test]
+fn buffer_reuse() {
+    let settings = Settings::default();
+
+    let mut paths = Vec::new();
+    let mut state = [0; 3];
+    let mut topic: heapless::String<7> = heapless::String::new();
+    settings
+        .for_each_path(&mut state, &mut topic, |path| paths.push(path.to_string()))
+        .unwrap();
+
+    // Every path is reported exactly once, without remnants of the previous path.
+    assert_eq!(paths, ["a", "b", "c/inner"]);
+
+    // Iteration requires sufficient space.
+    let mut state = [0; 3];
+    let mut topic: heapless::String<6> = heapless::String::new();
+    assert!(settings
+        .for_each_path(&mut state, &mut topic, |_| unreachable!())
+        .is_err());
+}
+
+#[test]
+fn topic_overflow() {
+    #[derive(Miniconf, Default)]
+    struct Overflow {
+        a: f32,
+        long_array: [u32; 2],
+        c: Inner,
+    }
+
+    let settings = Overflow::default();
+
+    // Paths that do not fit in the topic buffer are dropped instead of panicking.
+    let mut state = [0; 10];
+    let paths: Vec<_> = settings
+        .unchecked_into_iter::<4>(&mut state)
+        .map(|path| path.as_str().to_string())
+        .collect();
+    assert_eq!(paths, ["a"]);
+
+    // Array indices that do not fit are dropped as well.
+    let array = [0u32; 12];
+    let mut state = [0; 10];
+    assert_eq!(array.unchecked_into_iter::<1>(&mut state).count(), 10);
+}
+
+#[test]
+fn owned_iteration() {
+    let mut settings = Settings {
+        b: 7,
+        ..Default::default()
+    };
+
+    assert!(miniconf::iter::PathIterator::<1, 3>::new(&settings).is_err());
+    assert!(miniconf::iter::PathIterator::<32, 2>::new(&settings).is_err());
+
+    let mut paths = miniconf::iter::PathIterator::<32, 3>::new(&settings).unwrap();
+    assert_eq!(paths.next(&settings).unwrap(), "a");
+    assert_eq!(paths.next(&settings).unwrap(), "b");
+
+    // The settings can be accessed while iteration is in progress.
+    let mut data = [0; 8];
+    let len = settings.get("b", &mut data).unwrap();
+    assert_eq!(&data[..len], b"7");
+    settings.set("c/inner", b"1.5").unwrap();
+
+    assert_eq!(paths.next(&settings).unwrap(), "c/inner");
+    assert!(paths.next(&settings).is_none());
 }
@@ -0,0 +1,160 @@
+use miniconf::{ApplyError, Error, Miniconf, MiniconfAtomic, Optional};
+use serde::{Deserialize, Serialize};
+
+#[derive(MiniconfAtomic, Default, PartialEq, Debug, Serialize, Deserialize)]
+struct Calibration {
+    scale: f32,
+    offset: i32,
+    label: heapless::String<32>,
+}
+
+#[derive(Miniconf, Default, PartialEq, Debug)]
+struct Channel {
+    gain: f32,
+    enabled: bool,
+}
+
+#[derive(Miniconf, Default, PartialEq, Debug)]
+struct Settings {
+    channels: [Channel; 2],
+    calibration: Calibration,
+    limit: Optional<u32>,
+    coefficients: [i32; 3],
+    rate: u32,
+}
+
+const DEFAULTS: &[u8] = br#"{
+    "channels": [
+        {"gain": 1.5, "enabled": true},
+        {"gain": -0.5, "enabled": false}
+    ],
+    "calibration": {"scale": 2.0, "offset": -3, "label": "factory, {default} [1]"},
+    "limit": {"enabled": true, "value": 10},
+    "coefficients": [1, 2, 3],
+    "rate": 1000
+}"#;
+
+#[test]
+fn apply_nested_object() {
+    let mut settings = Settings::default();
+    settings.apply_json_object(DEFAULTS).unwrap();
+
+    assert_eq!(
+        settings.channels,
+        [
+            Channel {
+                gain: 1.5,
+                enabled: true
+            },
+            Channel {
+                gain: -0.5,
+                enabled: false
+            }
+        ]
+    );
+    assert_eq!(
+        settings.calibration,
+        Calibration {
+            scale: 2.0,
+            offset: -3,
+            label: "factory, {default} [1]".into(),
+        }
+    );
+    assert_eq!(settings.limit.as_option(), Some(&10));
+    assert_eq!(settings.coefficients, [1, 2, 3]);
+    assert_eq!(settings.rate, 1000);
+}
+
+#[test]
+fn partial_object() {
+    let mut settings = Settings::default();
+    settings
+        .apply_json_object(br#"{"channels": [{}, {"gain": 2.0}]}"#)
+        .unwrap();
+
+    assert_eq!(settings.channels[0], Channel::default());
+    assert_eq!(settings.channels[1].gain, 2.0);
+    assert_eq!(settings.rate, 0);
+}
+
+#[test]
+fn reports_failing_path() {
+    let mut settings = Settings::default();
+
+    let result = settings.apply_json_object(br#"{"rate": 5, "channels": [{"gain": true}]}"#);
+    match result {
+        Err(ApplyError::Apply { path, error }) => {
+            assert_eq!(path.as_str(), "channels/0/gain");
+            assert!(matches!(error, Error::Deserialization(_)));
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    // Members preceding the failure are applied.
+    assert_eq!(settings.rate, 5);
+
+    let result = settings.apply_json_object(br#"{"channels": [{}, {}, {}]}"#);
+    assert_eq!(
+        result,
+        Err(ApplyError::Apply {
+            path: "channels/2".into(),
+            error: Error::BadIndex
+        })
+    );
+
+    let result = settings.apply_json_object(br#"{"unknown": 1}"#);
+    assert_eq!(
+        result,
+        Err(ApplyError::Apply {
+            path: "unknown".into(),
+            error: Error::PathNotFound
+        })
+    );
+}
+
+#[test]
+fn malformed() {
+    let mut settings = Settings::default();
+
+    for data in [
+        &b""[..],
+        b"[1, 2]",
+        b"{\"rate\": 1",
+        b"{\"rate\" 1}",
+        b"{\"rate\": 1,}",
+        b"{\"rate\": 1} trailing",
+        b"{\"calibration\": {\"label\": \"unterminated}}",
+    ] {
+        assert_eq!(
+            settings.apply_json_object(data),
+            Err(ApplyError::Malformed),
+            "{:?}",
+            core::str::from_utf8(data)
+        );
+    }
+}
+
+#[test]
+fn serialize_round_trip() {
+    let mut settings = Settings::default();
+    settings.apply_json_object(DEFAULTS).unwrap();
+
+    let mut data = [0; 512];
+    let len = settings.serialize_json_object::<32, 4>(&mut data).unwrap();
+
+    let mut restored = Settings::default();
+    restored.apply_json_object(&data[..len]).unwrap();
+    assert_eq!(restored, settings);
+
+    // Serialization fails if the buffer is too small.
+    assert_eq!(
+        settings.serialize_json_object::<32, 4>(&mut data[..len - 1]),
+        Err(Error::SerializationFailed)
+    );
+
+    // Serialization fails if the paths do not fit.
+    assert_eq!(
+        settings.serialize_json_object::<4, 4>(&mut data),
+        Err(Error::PathTooLong)
+    );
+}
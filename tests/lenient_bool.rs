@@ -0,0 +1,58 @@
+use miniconf::{Error, LenientBool, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    enable: LenientBool,
+}
+
+#[test]
+fn accepted_spellings() {
+    let mut settings = Settings::default();
+
+    for value in [
+        "true", "1", "on", "yes", "ON", "Yes", "TRUE", "\"on\"", " 1\n",
+    ] {
+        settings.set("enable", b"false").unwrap();
+        settings.set("enable", value.as_bytes()).unwrap();
+        assert!(*settings.enable, "{}", value);
+    }
+
+    for value in [
+        "false", "0", "off", "no", "OFF", "No", "FALSE", "\"off\"", " 0\n",
+    ] {
+        settings.set("enable", b"true").unwrap();
+        settings.set("enable", value.as_bytes()).unwrap();
+        assert!(!*settings.enable, "{}", value);
+    }
+}
+
+#[test]
+fn canonical_get() {
+    let mut settings = Settings::default();
+    let mut data = [0; 8];
+
+    settings.set("enable", b"on").unwrap();
+    let len = settings.get("enable", &mut data).unwrap();
+    assert_eq!(&data[..len], b"true");
+
+    settings.set("enable", b"0").unwrap();
+    let len = settings.get("enable", &mut data).unwrap();
+    assert_eq!(&data[..len], b"false");
+}
+
+#[test]
+fn unrecognized() {
+    let mut settings = Settings {
+        enable: LenientBool(true),
+    };
+
+    for value in [&b"2"[..], b"enabled", b"", b"\"\"", b"\xff"] {
+        assert!(matches!(
+            settings.set("enable", value),
+            Err(Error::Deserialization(_))
+        ));
+    }
+
+    // Rejected values leave the setting unchanged.
+    assert!(*settings.enable);
+}
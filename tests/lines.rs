@@ -0,0 +1,57 @@
+use miniconf::{ApplyError, Error, Miniconf, MiniconfAtomic};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, MiniconfAtomic, Serialize, Deserialize)]
+struct Calibration {
+    scale: f32,
+    label: heapless::String<16>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Miniconf)]
+struct Channel {
+    gain: f32,
+    enabled: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Miniconf)]
+struct Settings {
+    channels: [Channel; 2],
+    calibration: Calibration,
+    rate: u32,
+}
+
+#[test]
+fn round_trip() {
+    let mut settings = Settings::default();
+    settings.channels[1].gain = 1.5;
+    settings.channels[0].enabled = true;
+    settings.calibration.label = "a b, c".into();
+    settings.rate = 1000;
+
+    let mut lines = String::new();
+    settings.export_lines(&mut lines).unwrap();
+    assert!(lines.starts_with("channels/0/gain\t0.0\nchannels/0/enabled\ttrue\n"));
+    assert_eq!(lines.lines().count(), 6);
+
+    let mut imported = Settings::default();
+    imported.import_lines(&lines).unwrap();
+    assert_eq!(imported, settings);
+}
+
+#[test]
+fn import_failure() {
+    let mut settings = Settings::default();
+
+    assert_eq!(
+        settings.import_lines("rate\t5\n\nchannels/2/gain\t1.0\n"),
+        Err(ApplyError::Apply {
+            path: "channels/2/gain".into(),
+            error: Error::BadIndex,
+        })
+    );
+
+    // Lines preceding the failure are applied.
+    assert_eq!(settings.rate, 5);
+
+    assert_eq!(settings.import_lines("rate 5"), Err(ApplyError::Malformed));
+}
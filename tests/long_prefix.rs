@@ -0,0 +1,76 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+async fn client_task(prefix: String) {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 512, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    mqtt.client
+        .publish(
+            &format!("{}/settings/data", prefix),
+            b"7",
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[],
+        )
+        .unwrap();
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Construct a prefix that is longer than the default maximum topic length.
+    let prefix = format!("long_prefix/{}", "x".repeat(188));
+    assert_eq!(prefix.len(), 200);
+
+    // Spawn a task to send MQTT messages.
+    tokio::task::spawn({
+        let prefix = prefix.clone();
+        async move { client_task(prefix).await }
+    });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 512, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        &prefix,
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    // Update the client until the setting is received.
+    for _ in 0..300 {
+        if interface.update().unwrap() {
+            break;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(interface.settings().data, 7);
+}
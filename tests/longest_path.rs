@@ -0,0 +1,66 @@
+use miniconf::{IterError, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Channel {
+    gain: f32,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    rate: u32,
+    channels: [Option<Channel>; 12],
+}
+
+#[test]
+fn populated() {
+    let mut settings = Settings::default();
+
+    // Only the paths present are considered.
+    assert_eq!(settings.longest_path::<4>(), Ok("rate".len()));
+
+    settings.channels[0] = Some(Channel::default());
+    assert_eq!(settings.longest_path::<4>(), Ok("channels/0/gain".len()));
+    assert_eq!(
+        settings.get_metadata().max_topic_size,
+        "channels/11/gain".len()
+    );
+
+    settings.channels[10] = Some(Channel::default());
+    assert_eq!(settings.longest_path::<4>(), Ok("channels/10/gain".len()));
+
+    // The metadata is derived from the first element only.
+    settings.channels[0] = None;
+    assert_eq!(settings.longest_path::<4>(), Ok("channels/10/gain".len()));
+    assert!(settings.get_metadata().max_topic_size < "channels/10/gain".len());
+}
+
+#[test]
+fn empty() {
+    let settings: [Option<Channel>; 2] = Default::default();
+    assert_eq!(settings.longest_path::<4>(), Ok(0));
+}
+
+#[derive(Miniconf, Default)]
+struct Long {
+    a_setting_with_a_name_that_is_far_longer_than_any_of_the_path_buffers_commonly_used_for_iteration_over_the_paths_of_the_settings_values:
+        [u32; 2],
+}
+
+#[test]
+fn long_paths() {
+    let settings = Long::default();
+    let expected = settings.get_metadata().max_topic_size;
+    assert!(expected > 128);
+    assert_eq!(settings.longest_path::<4>(), Ok(expected));
+}
+
+#[test]
+fn insufficient_depth() {
+    let settings = Settings::default();
+    assert_eq!(
+        settings.longest_path::<1>(),
+        Err(IterError::InsufficientStateDepth {
+            needed: settings.get_metadata().max_depth
+        })
+    );
+}
@@ -0,0 +1,114 @@
+use miniconf::{Error, LenientBool, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Channel {
+    gain: f32,
+    offset: f32,
+    enabled: LenientBool,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    channels: [Channel; 2],
+    output: Option<Channel>,
+    gain: f32,
+    rate: u32,
+    label: miniconf::heapless::String<8>,
+}
+
+#[test]
+fn scale() {
+    let mut settings = Settings {
+        channels: [
+            Channel {
+                gain: 1.0,
+                offset: -2.0,
+                ..Default::default()
+            },
+            Channel {
+                gain: 0.5,
+                offset: 4.0,
+                ..Default::default()
+            },
+        ],
+        output: Some(Channel {
+            gain: 10.0,
+            ..Default::default()
+        }),
+        gain: 2.0,
+        rate: 100,
+        ..Default::default()
+    };
+
+    settings
+        .map_leaves::<32, 4>(|_, leaf| {
+            if let Some(value) = leaf.as_f32_mut() {
+                *value *= 0.9;
+            }
+        })
+        .unwrap();
+
+    assert_eq!(settings.channels[0].gain, 1.0 * 0.9);
+    assert_eq!(settings.channels[0].offset, -2.0 * 0.9);
+    assert_eq!(settings.channels[1].gain, 0.5 * 0.9);
+    assert_eq!(settings.channels[1].offset, 4.0 * 0.9);
+    assert_eq!(settings.output.as_ref().unwrap().gain, 10.0 * 0.9);
+    assert_eq!(settings.gain, 2.0 * 0.9);
+
+    // Leaves of other types are unaffected.
+    assert_eq!(settings.rate, 100);
+}
+
+#[test]
+fn typed_access() {
+    let mut settings = Settings::default();
+
+    let mut paths = Vec::new();
+    settings
+        .map_leaves::<32, 4>(|path, leaf| {
+            paths.push(path.to_string());
+
+            if let Some(enabled) = leaf.as_bool_mut() {
+                *enabled = true;
+            }
+
+            // Accessors of other types are not available.
+            if path == "rate" {
+                assert!(leaf.as_f32_mut().is_none());
+                *leaf.as_u32_mut().unwrap() = 5;
+            }
+        })
+        .unwrap();
+
+    assert!(settings.channels.iter().all(|channel| *channel.enabled));
+    assert_eq!(settings.rate, 5);
+
+    // Absent optional settings and strings are not reachable.
+    assert_eq!(
+        paths,
+        [
+            "channels/0/gain",
+            "channels/0/offset",
+            "channels/0/enabled",
+            "channels/1/gain",
+            "channels/1/offset",
+            "channels/1/enabled",
+            "gain",
+            "rate",
+        ]
+    );
+}
+
+#[test]
+fn insufficient_buffers() {
+    let mut settings = Settings {
+        rate: 100,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        settings.map_leaves::<8, 4>(|_, leaf| *leaf.as_u32_mut().unwrap() = 5),
+        Err(Error::PathTooLong)
+    );
+    assert_eq!(settings.rate, 100);
+}
@@ -0,0 +1,38 @@
+use miniconf::Miniconf;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "minimq/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    assert!(!interface.minimq_mut().client.is_connected());
+
+    // The connection state should be observable through the exposed MQTT client.
+    for _ in 0..100 {
+        interface.update().unwrap();
+        if interface.minimq_mut().client.is_connected() {
+            return;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    panic!("Client did not connect");
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "std")]
+
+use miniconf::{Error, Miniconf};
+use std::sync::Mutex;
+
+#[derive(Miniconf, Default)]
+struct Channel {
+    gain: u32,
+    offset: u32,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    channels: [Channel; 2],
+}
+
+#[test]
+fn threads() {
+    let settings = Mutex::new(Settings::default());
+
+    // Note: `Mutex` has unstable inherent `set()` and `get()` methods, which are avoided using
+    // fully qualified syntax.
+    std::thread::scope(|scope| {
+        for channel in 0..2 {
+            let mut settings = &settings;
+            scope.spawn(move || {
+                let gain = format!("channels/{channel}/gain");
+                let offset = format!("channels/{channel}/offset");
+                for value in 1..=100u32 {
+                    Miniconf::set(&mut settings, &gain, value.to_string().as_bytes()).unwrap();
+                    Miniconf::set(&mut settings, &offset, (value * 2).to_string().as_bytes())
+                        .unwrap();
+                }
+            });
+        }
+    });
+
+    let mut buf = [0; 8];
+    for channel in 0..2 {
+        let len = Miniconf::get(&settings, &format!("channels/{channel}/gain"), &mut buf).unwrap();
+        assert_eq!(&buf[..len], b"100");
+
+        let len =
+            Miniconf::get(&settings, &format!("channels/{channel}/offset"), &mut buf).unwrap();
+        assert_eq!(&buf[..len], b"200");
+    }
+
+    // The paths are the same as those of the settings themselves.
+    let mut state = [0; 4];
+    let paths: Vec<_> = settings
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .map(|path| path.to_string())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "channels/0/gain",
+            "channels/0/offset",
+            "channels/1/gain",
+            "channels/1/offset"
+        ]
+    );
+}
+
+#[test]
+fn poisoned() {
+    let mut settings = Mutex::new(Settings::default());
+
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let _guard = settings.lock().unwrap();
+                panic!("Poisoning the lock");
+            })
+            .join()
+            .unwrap_err();
+    });
+
+    assert_eq!(
+        Miniconf::set(&mut settings, "channels/0/gain", b"1"),
+        Err(Error::Poisoned)
+    );
+    assert_eq!(
+        Miniconf::get(&settings, "channels/0/gain", &mut [0; 8]),
+        Err(Error::Poisoned)
+    );
+
+    settings.clear_poison();
+    Miniconf::set(&mut settings, "channels/0/gain", b"1").unwrap();
+    assert_eq!(settings.into_inner().unwrap().channels[0].gain, 1);
+}
@@ -0,0 +1,76 @@
+use miniconf::{Error, Miniconf, NamedArray};
+
+const CHANNELS: [&str; 4] = ["left", "right", "aux1", "aux2"];
+
+#[derive(Miniconf, Default, Clone, Copy, Debug, PartialEq)]
+struct Channel {
+    gain: f32,
+}
+
+#[derive(Miniconf, Clone)]
+struct Settings {
+    channel: NamedArray<Channel, 4>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            channel: NamedArray::new(&CHANNELS, [Channel::default(); 4]),
+        }
+    }
+}
+
+#[test]
+fn name_and_index() {
+    let mut settings = Settings::default();
+
+    settings.set("channel/left/gain", b"1.5").unwrap();
+    assert_eq!(settings.channel[0].gain, 1.5);
+
+    // The same element is accessible by its numeric index.
+    let mut buffer = [0; 32];
+    let len = settings.get("channel/0/gain", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"1.5");
+
+    settings.set("channel/0/gain", b"2.5").unwrap();
+    let len = settings.get("channel/left/gain", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"2.5");
+
+    settings.set("channel/aux2/gain", b"3.0").unwrap();
+    assert_eq!(settings.channel[3].gain, 3.0);
+}
+
+#[test]
+fn invalid_element() {
+    let mut settings = Settings::default();
+
+    assert_eq!(
+        settings.set("channel/center/gain", b"1.0"),
+        Err(Error::BadIndex)
+    );
+    assert_eq!(settings.set("channel/4/gain", b"1.0"), Err(Error::BadIndex));
+}
+
+#[test]
+fn iterate_names() {
+    let settings = Settings::default();
+
+    let metadata = settings.get_metadata();
+    assert_eq!(metadata.max_topic_size, "channel/right/gain".len());
+
+    let mut state = [0; 4];
+    let paths: Vec<String> = settings
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .map(|path| path.to_string())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "channel/left/gain",
+            "channel/right/gain",
+            "channel/aux1/gain",
+            "channel/aux2/gain"
+        ]
+    );
+}
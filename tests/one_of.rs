@@ -0,0 +1,44 @@
+use miniconf::{Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    #[miniconf(one_of = ["a", "b", "c"])]
+    mode: heapless::String<8>,
+    label: heapless::String<8>,
+}
+
+#[test]
+fn allowed() {
+    let mut settings = Settings::default();
+
+    settings.set("mode", br#""b""#).unwrap();
+    assert_eq!(settings.mode, "b");
+
+    let mut buffer = [0; 16];
+    let len = settings.get("mode", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], br#""b""#);
+}
+
+#[test]
+fn not_allowed() {
+    let mut settings = Settings::default();
+    settings.set("mode", br#""a""#).unwrap();
+
+    assert_eq!(
+        settings.set("mode", br#""d""#),
+        Err(Error::NotAllowed {
+            allowed: &["a", "b", "c"]
+        })
+    );
+
+    // Rejected values leave the setting unchanged.
+    assert_eq!(settings.mode, "a");
+}
+
+#[test]
+fn unrestricted() {
+    let mut settings = Settings::default();
+
+    settings.set("label", br#""d""#).unwrap();
+    assert_eq!(settings.label, "d");
+}
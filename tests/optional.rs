@@ -0,0 +1,72 @@
+use miniconf::{Error, Miniconf, Optional};
+
+#[derive(Miniconf, Default, Clone, Copy, Debug, PartialEq)]
+struct Filter {
+    cutoff: f32,
+    order: u8,
+}
+
+#[derive(Miniconf, Default, Clone)]
+struct Settings {
+    gain: Optional<f32>,
+    filter: Optional<Filter>,
+}
+
+#[test]
+fn toggle_retains_value() {
+    let mut settings = Settings::default();
+    assert_eq!(settings.gain.as_option(), None);
+
+    settings.set("gain/value", b"2.5").unwrap();
+    settings.set("gain/enabled", b"true").unwrap();
+    assert_eq!(settings.gain.as_option(), Some(&2.5));
+
+    // Disabling the value should retain its configuration.
+    settings.set("gain/enabled", b"false").unwrap();
+    assert_eq!(settings.gain.as_option(), None);
+
+    let mut buffer = [0; 32];
+    let len = settings.get("gain/value", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"2.5");
+
+    settings.set("gain/enabled", b"true").unwrap();
+    assert_eq!(settings.gain.as_option(), Some(&2.5));
+
+    // Nested values can be configured while disabled.
+    settings.set("filter/value/order", b"3").unwrap();
+    assert_eq!(settings.filter.value.order, 3);
+    assert!(!settings.filter.enabled);
+}
+
+#[test]
+fn invalid_paths() {
+    let mut settings = Settings::default();
+
+    assert_eq!(settings.set("gain", b"true"), Err(Error::ExpectedLeaf));
+    assert_eq!(settings.set("gain/other", b"1.0"), Err(Error::PathNotFound));
+}
+
+#[test]
+fn iterate_paths() {
+    let settings = Settings::default();
+
+    let metadata = settings.get_metadata();
+    assert_eq!(metadata.max_topic_size, "filter/value/cutoff".len());
+
+    let mut state = [0; 8];
+    let paths: Vec<String> = settings
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .map(|path| path.to_string())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "gain/enabled",
+            "gain/value",
+            "filter/enabled",
+            "filter/value/cutoff",
+            "filter/value/order"
+        ]
+    );
+}
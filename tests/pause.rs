@@ -0,0 +1,127 @@
+use miniconf::{minimq, Miniconf, PauseBehavior};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+mod common;
+use common::Tester;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+async fn request(mqtt: &mut Tester, value: &[u8]) -> (u8, String) {
+    common::request(
+        mqtt,
+        "pause/device/settings/data",
+        value,
+        "pause/device/response",
+    )
+    .await
+}
+
+async fn client_task(stage: Arc<AtomicUsize>) {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    mqtt.client.subscribe("pause/device/response", &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    // The device is paused in rejecting mode, so the update should be refused.
+    let (code, msg) = request(&mut mqtt, b"3").await;
+    assert!(code != 0);
+    assert_eq!(msg, "Busy, retry");
+
+    // Switch the device to queueing mode.
+    stage.store(1, Ordering::SeqCst);
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let (code, msg) = request(&mut mqtt, b"5").await;
+    assert_eq!(code, 0);
+    assert_eq!(msg, "Queued");
+
+    // Verify that the device still indicates that it is alive while paused.
+    mqtt.client.subscribe("pause/device/alive", &[]).unwrap();
+    let mut alive = false;
+    while !alive {
+        mqtt.poll(|_client, topic, message, _properties| {
+            if topic == "pause/device/alive" {
+                alive = message == b"1";
+            }
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // Resume the device.
+    stage.store(2, Ordering::SeqCst);
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    let stage = Arc::new(AtomicUsize::new(0));
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn({
+        let stage = stage.clone();
+        async move { client_task(stage).await }
+    });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "pause/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    interface.pause(PauseBehavior::Reject);
+
+    loop {
+        interface.update().unwrap();
+
+        match stage.load(Ordering::SeqCst) {
+            0 => {}
+            1 => interface.pause(PauseBehavior::Queue),
+            _ => {
+                // Settings should not have been applied while paused.
+                assert_eq!(interface.settings().data, 0);
+                interface.resume();
+                break;
+            }
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // The queued update should be applied once the client is resumed.
+    assert!(interface.update().unwrap());
+    assert_eq!(interface.settings().data, 5);
+
+    task.await.expect("Client task failed");
+}
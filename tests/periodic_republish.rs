@@ -0,0 +1,87 @@
+use miniconf::{embedded_time, Miniconf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+use embedded_time::{duration::Extensions, fraction::Fraction, Clock, Instant};
+
+// The time in nanoseconds that the mock clock is advanced beyond the standard clock.
+static OFFSET: AtomicU64 = AtomicU64::new(0);
+
+/// A clock that can be advanced manually.
+#[derive(Copy, Clone, Debug, Default)]
+struct MockClock(StandardClock);
+
+impl Clock for MockClock {
+    type T = u64;
+
+    const SCALING_FACTOR: Fraction = Fraction::new(1, 1_000_000_000);
+
+    fn try_now(&self) -> Result<Instant<Self>, embedded_time::clock::Error> {
+        let now = self.0.try_now()?;
+        let ticks = now.duration_since_epoch().integer() + OFFSET.load(Ordering::Relaxed);
+        Ok(Instant::new(ticks))
+    }
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "periodic_republish/device",
+        "127.0.0.1".parse().unwrap(),
+        MockClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .periodic_republish(30.seconds());
+
+    // Settings are not republished before the interval has elapsed.
+    let mut completions = 0;
+    for _ in 0..300 {
+        interface.update().unwrap();
+        if interface.just_finished_republish() {
+            completions += 1;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(completions, 0);
+
+    // Advancing the clock past the interval starts a republish cycle.
+    OFFSET.fetch_add(31_000_000_000, Ordering::Relaxed);
+    for _ in 0..100 {
+        interface.update().unwrap();
+        if interface.just_finished_republish() {
+            completions += 1;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(completions, 1);
+
+    // The next cycle is scheduled relative to the completion of the previous one.
+    OFFSET.fetch_add(31_000_000_000, Ordering::Relaxed);
+    for _ in 0..100 {
+        interface.update().unwrap();
+        if interface.just_finished_republish() {
+            completions += 1;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(completions, 2);
+}
@@ -0,0 +1,172 @@
+use miniconf::{
+    minimq::{
+        self,
+        embedded_nal::{nb, SocketAddr, TcpClientStack},
+    },
+    Miniconf,
+};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+/// A network stack that can simulate the loss of the TCP connection.
+struct FlakyStack {
+    stack: Stack,
+    disconnect: Arc<AtomicBool>,
+}
+
+impl TcpClientStack for FlakyStack {
+    type TcpSocket = <Stack as TcpClientStack>::TcpSocket;
+    type Error = <Stack as TcpClientStack>::Error;
+
+    fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
+        self.stack.socket()
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        remote: SocketAddr,
+    ) -> nb::Result<(), Self::Error> {
+        self.stack.connect(socket, remote)
+    }
+
+    fn is_connected(&mut self, socket: &Self::TcpSocket) -> Result<bool, Self::Error> {
+        if self.disconnect.swap(false, Ordering::SeqCst) {
+            return Ok(false);
+        }
+
+        self.stack.is_connected(socket)
+    }
+
+    fn send(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &[u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.stack.send(socket, buffer)
+    }
+
+    fn receive(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.stack.receive(socket, buffer)
+    }
+
+    fn close(&mut self, socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        self.stack.close(socket)
+    }
+}
+
+async fn client_task(stage: Arc<AtomicUsize>) {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    // Wait for the device to reconnect.
+    while stage.load(Ordering::SeqCst) == 0 {
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // The preserved session should still deliver settings updates to the device.
+    mqtt.client
+        .publish(
+            "persistent/device/settings/data",
+            b"5",
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[],
+        )
+        .unwrap();
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    let stage = Arc::new(AtomicUsize::new(0));
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn({
+        let stage = stage.clone();
+        async move { client_task(stage).await }
+    });
+
+    let disconnect = Arc::new(AtomicBool::new(false));
+    let stack = FlakyStack {
+        stack: Stack,
+        disconnect: disconnect.clone(),
+    };
+
+    // Construct a settings configuration interface. A fixed client ID is used so that the broker
+    // can associate the reconnect with the previous session.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        stack,
+        "persistent-device",
+        "persistent/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .persistent_session(true);
+
+    // Wait for the initial subscription and republish.
+    while !interface.just_finished_republish() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // Simulate a loss of the connection and wait for the client to reconnect.
+    disconnect.store(true, Ordering::SeqCst);
+    let mut disconnected = false;
+    loop {
+        interface.update().unwrap();
+
+        let connected = interface.minimq_mut().client.is_connected();
+        disconnected |= !connected;
+        if disconnected && connected {
+            break;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // Settings should not be republished after the session is resumed, even after the republish
+    // timeout has elapsed.
+    for _ in 0..300 {
+        interface.update().unwrap();
+        assert!(!interface.just_finished_republish());
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    stage.store(1, Ordering::SeqCst);
+    while interface.settings().data != 5 {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+}
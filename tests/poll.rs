@@ -0,0 +1,152 @@
+use miniconf::{
+    minimq::{
+        self,
+        embedded_nal::{nb, SocketAddr, TcpClientStack},
+    },
+    Miniconf, PollOutcome,
+};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+/// A network stack that never establishes a connection and delivers nothing.
+struct SilentStack;
+
+impl TcpClientStack for SilentStack {
+    type TcpSocket = ();
+    type Error = ();
+
+    fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
+        Ok(())
+    }
+
+    fn connect(
+        &mut self,
+        _socket: &mut Self::TcpSocket,
+        _remote: SocketAddr,
+    ) -> nb::Result<(), Self::Error> {
+        // The connection attempt is accepted, but never completes.
+        Ok(())
+    }
+
+    fn is_connected(&mut self, _socket: &Self::TcpSocket) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn send(
+        &mut self,
+        _socket: &mut Self::TcpSocket,
+        _buffer: &[u8],
+    ) -> nb::Result<usize, Self::Error> {
+        Err(nb::Error::WouldBlock)
+    }
+
+    fn receive(
+        &mut self,
+        _socket: &mut Self::TcpSocket,
+        _buffer: &mut [u8],
+    ) -> nb::Result<usize, Self::Error> {
+        Err(nb::Error::WouldBlock)
+    }
+
+    fn close(&mut self, _socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn accept(_: &str, old: &mut Settings, new: &Settings) -> Result<(), &'static str> {
+    *old = new.clone();
+    Ok(())
+}
+
+#[test]
+fn no_message() {
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        SilentStack,
+        "",
+        "poll/silent",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    for _ in 0..10 {
+        assert_eq!(interface.poll(accept).unwrap(), PollOutcome::NoMessage);
+    }
+
+    // The compatibility interface reports no change.
+    assert!(!interface.update().unwrap());
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    for value in [&b"5"[..], b"\"invalid\""] {
+        mqtt.client
+            .publish(
+                "poll/device/settings/data",
+                value,
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[],
+            )
+            .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    }
+}
+
+#[tokio::test]
+async fn received_messages() {
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Settings are not republished, so the client only receives the requests.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "poll/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false);
+
+    let mut outcomes = Vec::new();
+    while !task.is_finished() {
+        match interface.poll(accept).unwrap() {
+            PollOutcome::NoMessage => {}
+            outcome => outcomes.push(outcome),
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.unwrap();
+
+    // The valid request changed the settings, the invalid one did not.
+    assert_eq!(outcomes, [PollOutcome::Changed, PollOutcome::Unchanged]);
+    assert_eq!(interface.settings().data, 5);
+}
@@ -0,0 +1,101 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    frequency: u32,
+    gain: u32,
+}
+
+/// Convert frequencies received in kHz into Hz.
+fn khz_to_hz(path: &str, value: &[u8], scratch: &mut [u8]) -> Option<usize> {
+    if path != "frequency" {
+        return None;
+    }
+
+    let (khz, _): (u32, _) = miniconf::serde_json_core::from_slice(value).ok()?;
+    miniconf::serde_json_core::to_slice(&(khz * 1000), scratch).ok()
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "pre-set/tester/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    for (topic, value) in [
+        ("pre-set/device/settings/frequency", "12"),
+        ("pre-set/device/settings/gain", "3"),
+    ] {
+        mqtt.client
+            .publish(
+                topic,
+                value.as_bytes(),
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[minimq::Property::ResponseTopic(response_topic)],
+            )
+            .unwrap();
+
+        let mut response = None;
+        while response.is_none() {
+            mqtt.poll(|_client, _topic, message, _properties| {
+                response.replace(message.to_vec());
+            })
+            .unwrap();
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(response.unwrap(), br#"{"code":0,"msg":"OK"}"#);
+    }
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "pre-set/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .pre_set(khz_to_hz);
+
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    // Only the frequency is converted.
+    assert_eq!(interface.settings().frequency, 12000);
+    assert_eq!(interface.settings().gain, 3);
+}
@@ -1,5 +1,3 @@
-use tokio;
-
 use miniconf::{minimq, Miniconf};
 use std_embedded_nal::Stack;
 use std_embedded_time::StandardClock;
@@ -20,7 +18,7 @@ async fn verify_settings() {
     let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
         "127.0.0.1".parse().unwrap(),
         "tester",
-        Stack::default(),
+        Stack,
         StandardClock::default(),
     )
     .unwrap();
@@ -49,16 +47,12 @@ async fn verify_settings() {
     for _ in 0..50 {
         mqtt.poll(|_, topic, value, _properties| {
             log::info!("{}: {:?}", &topic, value);
-            let element = received_settings.get_mut(&topic.to_string()).unwrap();
+            let element = received_settings.get_mut(topic).unwrap();
             *element += 1;
         })
         .unwrap();
 
-        if received_settings
-            .iter()
-            .map(|(_, value)| value)
-            .all(|&x| x >= 1)
-        {
+        if received_settings.values().all(|&x| x >= 1) {
             break;
         }
 
@@ -66,10 +60,7 @@ async fn verify_settings() {
     }
 
     // Ensure that all fields were iterated exactly once.
-    assert!(received_settings
-        .iter()
-        .map(|(_, value)| value)
-        .all(|&x| x == 1));
+    assert!(received_settings.values().all(|&x| x == 1));
 }
 
 #[tokio::test]
@@ -81,7 +72,7 @@ async fn main() {
 
     // Construct a settings configuration interface.
     let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
-        Stack::default(),
+        Stack,
         "",
         "republish/device",
         "127.0.0.1".parse().unwrap(),
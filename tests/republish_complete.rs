@@ -0,0 +1,53 @@
+use miniconf::Miniconf;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    values: [u8; 3],
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "republish_complete/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    // Poll the client for 5 seconds. This is sufficient for the initial republish timeout to expire
+    // and all settings to be published.
+    let mut completions = 0;
+    for _ in 0..500 {
+        interface.update().unwrap();
+        if interface.just_finished_republish() {
+            completions += 1;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // The completion edge should fire exactly once for the republish cycle.
+    assert_eq!(completions, 1);
+
+    // Forcing a republish should fire the edge once more.
+    interface.force_republish();
+    for _ in 0..100 {
+        interface.update().unwrap();
+        if interface.just_finished_republish() {
+            completions += 1;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(completions, 2);
+}
@@ -0,0 +1,115 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct AdditionalSettings {
+    inner: u8,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    more: AdditionalSettings,
+}
+
+async fn verify_settings(start: std::time::Instant) {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    // Subscribe to the settings topic.
+    mqtt.client
+        .subscribe("republish-on-connect/device/settings/#", &[])
+        .unwrap();
+
+    let mut received_settings = std::collections::HashMap::from([
+        ("republish-on-connect/device/settings/data".to_string(), 0),
+        (
+            "republish-on-connect/device/settings/more/inner".to_string(),
+            0,
+        ),
+    ]);
+
+    // The device should not publish any settings after connecting.
+    while start.elapsed() < std::time::Duration::from_secs(4) {
+        mqtt.poll(|_, topic, value, _properties| {
+            log::info!("{}: {:?}", &topic, value);
+            *received_settings.get_mut(topic).unwrap() += 1;
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert!(received_settings.values().all(|&x| x == 0));
+
+    // Settings are published once the republish is forced.
+    for _ in 0..300 {
+        mqtt.poll(|_, topic, value, _properties| {
+            log::info!("{}: {:?}", &topic, value);
+            *received_settings.get_mut(topic).unwrap() += 1;
+        })
+        .unwrap();
+
+        if received_settings.values().all(|&x| x >= 1) {
+            break;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert!(received_settings.values().all(|&x| x == 1));
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let start = std::time::Instant::now();
+    let task = tokio::task::spawn(async move { verify_settings(start).await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "republish-on-connect/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false);
+
+    // Poll the client for longer than the republish timeout after connecting.
+    while start.elapsed() < std::time::Duration::from_millis(4500) {
+        interface.update().unwrap();
+        assert!(!interface.just_finished_republish());
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(interface.stats().republishes, 0);
+
+    // Settings can still be republished manually.
+    interface.force_republish();
+    for _ in 0..300 {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(interface.stats().republishes, 1);
+    task.await.expect("Unexpected settings received");
+}
@@ -0,0 +1,105 @@
+use miniconf::{minimq::embedded_nal, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+use embedded_nal::{nb, SocketAddr, TcpClientStack};
+
+/// A network stack that only accepts a few bytes at a time, so that republication of the
+/// settings spans multiple updates.
+struct Throttled(Stack);
+
+impl TcpClientStack for Throttled {
+    type TcpSocket = <Stack as TcpClientStack>::TcpSocket;
+    type Error = <Stack as TcpClientStack>::Error;
+
+    fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
+        self.0.socket()
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        remote: SocketAddr,
+    ) -> nb::Result<(), Self::Error> {
+        self.0.connect(socket, remote)
+    }
+
+    fn is_connected(&mut self, socket: &Self::TcpSocket) -> Result<bool, Self::Error> {
+        self.0.is_connected(socket)
+    }
+
+    fn send(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &[u8],
+    ) -> nb::Result<usize, Self::Error> {
+        let len = buffer.len().min(16);
+        self.0.send(socket, &buffer[..len])
+    }
+
+    fn receive(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.0.receive(socket, buffer)
+    }
+
+    fn close(&mut self, socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        self.0.close(socket)
+    }
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Inner {
+    a: u32,
+    b: bool,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    values: [u32; 10],
+    inner: Inner,
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Throttled(Stack),
+        "",
+        "republish_progress/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    assert_eq!(interface.republish_progress(), None);
+
+    let mut progress = Vec::new();
+    for _ in 0..1000 {
+        interface.update().unwrap();
+
+        if let Some(current) = interface.republish_progress() {
+            progress.push(current);
+        }
+
+        if interface.just_finished_republish() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    // Progress is reported across multiple updates and increases monotonically up to completion.
+    assert_eq!(progress.last(), Some(&(12, 12)));
+    assert!(progress.iter().all(|&(_, total)| total == 12));
+    assert!(progress.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    assert!(progress.iter().any(|&(count, _)| count > 0 && count < 12));
+
+    // Progress is no longer reported once the republication completed.
+    interface.update().unwrap();
+    assert_eq!(interface.republish_progress(), None);
+}
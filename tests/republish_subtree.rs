@@ -0,0 +1,123 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Dsp {
+    gain: f32,
+    offset: f32,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Network {
+    port: u16,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    dsp: Dsp,
+    dsp_enabled: bool,
+    net: Network,
+}
+
+type Interface = miniconf::MqttClient<Settings, Stack, StandardClock, 256>;
+type Tester = minimq::Minimq<Stack, StandardClock, 256, 1>;
+
+/// Poll the interface and the tester, collecting all settings topics published by the interface.
+///
+/// # Returns
+/// The published topics and whether a republication completed.
+fn poll(interface: &mut Interface, mqtt: &mut Tester) -> (Vec<String>, bool) {
+    let mut topics = Vec::new();
+    let mut republished = false;
+
+    for _ in 0..100 {
+        interface.update().unwrap();
+        republished |= interface.just_finished_republish();
+
+        mqtt.poll(|_, topic, _, _| topics.push(topic.to_string()))
+            .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    topics.sort();
+    (topics, republished)
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: Tester = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    let mut interface: Interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "republish_subtree/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false);
+
+    // Wait for both clients to be subscribed.
+    let mut subscribed = false;
+    for _ in 0..500 {
+        interface.update().unwrap();
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+
+        if mqtt.client.is_connected() && !subscribed {
+            mqtt.client
+                .subscribe("republish_subtree/device/settings/#", &[])
+                .unwrap();
+            subscribed = true;
+        }
+
+        if subscribed && !mqtt.client.subscriptions_pending() && interface.is_subscribed() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(interface.is_subscribed());
+
+    // Only the settings below the base are republished. Paths sharing a prefix with the base
+    // but not the path segment are not.
+    interface.republish_subtree("dsp/*");
+    let (topics, republished) = poll(&mut interface, &mut mqtt);
+    assert!(republished);
+    assert_eq!(
+        topics,
+        [
+            "republish_subtree/device/settings/dsp/gain",
+            "republish_subtree/device/settings/dsp/offset"
+        ]
+    );
+
+    // A single leaf can be republished.
+    interface.republish_subtree("net/port");
+    let (topics, republished) = poll(&mut interface, &mut mqtt);
+    assert!(republished);
+    assert_eq!(topics, ["republish_subtree/device/settings/net/port"]);
+
+    // Bases that do not match any settings do not republish anything.
+    interface.republish_subtree("unknown");
+    let (topics, republished) = poll(&mut interface, &mut mqtt);
+    assert!(!republished);
+    assert!(topics.is_empty());
+
+    // A subsequent full republish is not restricted.
+    interface.force_republish();
+    let (topics, republished) = poll(&mut interface, &mut mqtt);
+    assert!(republished);
+    assert_eq!(topics.len(), 4);
+}
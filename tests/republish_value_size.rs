@@ -0,0 +1,108 @@
+use miniconf::{minimq, Miniconf, MiniconfAtomic};
+use serde::{Deserialize, Serialize};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, MiniconfAtomic, Serialize, Deserialize)]
+struct Label {
+    text: heapless::String<32>,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    label: Label,
+    more: u8,
+}
+
+async fn verify_settings() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    // Subscribe to the settings topic.
+    mqtt.client
+        .subscribe("republish-value-size/device/settings/#", &[])
+        .unwrap();
+
+    // Wait the other device to connect and publish settings.
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+    let mut received = Vec::new();
+    for _ in 0..50 {
+        mqtt.poll(|_, topic, value, _properties| {
+            received.push((topic.to_string(), value.to_vec()));
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // Values fitting the value size are republished, even though the label would fit within a
+    // message.
+    received.sort();
+    assert_eq!(
+        received,
+        [
+            (
+                "republish-value-size/device/settings/data".to_string(),
+                b"1234567".to_vec()
+            ),
+            (
+                "republish-value-size/device/settings/more".to_string(),
+                b"5".to_vec()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn main() {
+    // Spawn a task to receive the republished settings.
+    let task = tokio::task::spawn(async move { verify_settings().await });
+
+    // The label exceeds the value size of 16 bytes.
+    let mut settings = Settings {
+        data: 1234567,
+        more: 5,
+        ..Default::default()
+    };
+    settings
+        .label
+        .text
+        .push_str("exceeds the value size")
+        .unwrap();
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256, 128, 0, 16> =
+        miniconf::MqttClient::new(
+            Stack,
+            "",
+            "republish-value-size/device",
+            "127.0.0.1".parse().unwrap(),
+            StandardClock::default(),
+            settings,
+        )
+        .unwrap();
+
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Unexpected settings received");
+
+    assert_eq!(interface.stats().republishes, 1);
+    assert_eq!(interface.stats().dropped_settings, 1);
+}
@@ -0,0 +1,101 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "respond-value/tester/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    let mut responses: Vec<Vec<u8>> = Vec::new();
+    for value in [&b"20"[..], b"\"invalid\""] {
+        mqtt.client
+            .publish(
+                "respond-value/device/settings/data",
+                value,
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[minimq::Property::ResponseTopic(response_topic)],
+            )
+            .unwrap();
+
+        let count = responses.len();
+        while responses.len() == count {
+            mqtt.poll(|_client, topic, message, _properties| {
+                assert_eq!(topic, response_topic);
+                responses.push(message.to_vec());
+            })
+            .unwrap();
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    // The handler limited the requested value, which is confirmed by the response.
+    assert_eq!(
+        std::str::from_utf8(&responses[0]).unwrap(),
+        r#"{"code":0,"msg":"OK","path":"data","value":"10"}"#
+    );
+
+    // Failed updates do not carry a value.
+    let failure = std::str::from_utf8(&responses[1]).unwrap();
+    assert!(failure.starts_with(r#"{"code":255,"msg":"#));
+    assert!(!failure.contains("value"));
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "respond-value/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .respond_with_value(true);
+
+    while !task.is_finished() {
+        interface
+            .handled_update(|_path, old, new| {
+                *old = new.clone();
+                old.data = old.data.min(10);
+                Result::<(), &'static str>::Ok(())
+            })
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+    assert_eq!(interface.settings().data, 10);
+}
@@ -0,0 +1,121 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+mod common;
+use common::Tester;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+/// Publish a settings update without a response topic and collect the messages received on the
+/// response and log topics of the device.
+async fn request(mqtt: &mut Tester, prefix: &str) -> (Vec<String>, Vec<String>) {
+    let topic = format!("{}/settings/data", prefix);
+    common::publish(mqtt, &topic, b"5", None);
+
+    let response_topic = format!("{}/response", prefix);
+    let log_topic = format!("{}/log", prefix);
+    let mut responses = Vec::new();
+    let mut logs = Vec::new();
+
+    // Wait for the response and any audit line that may follow it.
+    for _ in 0..50 {
+        mqtt.poll(|_client, topic, message, _properties| {
+            let message = String::from_utf8(message.to_vec()).unwrap();
+            if topic == response_topic {
+                responses.push(message);
+            } else if topic == log_topic {
+                logs.push(message);
+            }
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    (responses, logs)
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    mqtt.client
+        .subscribe("response_topic/+/response", &[])
+        .unwrap();
+    mqtt.client.subscribe("response_topic/+/log", &[]).unwrap();
+
+    // Wait for the devices to connect and republish their settings, which they also respond to.
+    // Those responses are discarded.
+    for _ in 0..400 {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // Without auditing, only the response is published.
+    let (responses, logs) = request(&mut mqtt, "response_topic/quiet").await;
+    assert_eq!(responses, [r#"{"code":0,"msg":"OK"}"#]);
+    assert!(logs.is_empty());
+
+    // With auditing, a human-readable line is additionally published to the log.
+    let (responses, logs) = request(&mut mqtt, "response_topic/audited").await;
+    assert_eq!(responses, [r#"{"code":0,"msg":"OK"}"#]);
+    assert_eq!(logs, ["data: OK (0)"]);
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Construct settings configuration interfaces with and without auditing.
+    let mut quiet: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "response_topic/quiet",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    let mut audited: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "response_topic/audited",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .audit_log(true);
+
+    while !task.is_finished() {
+        quiet.update().unwrap();
+        audited.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+    assert_eq!(quiet.settings().data, 5);
+    assert_eq!(audited.settings().data, 5);
+}
@@ -0,0 +1,220 @@
+use miniconf::{minimq, Miniconf};
+use serde::Deserialize;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    gain: f32,
+    rate: u32,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    code: u8,
+    msg: heapless::String<64>,
+}
+
+type Interface = miniconf::MqttClient<Settings, Stack, StandardClock, 256>;
+type Tester = minimq::Minimq<Stack, StandardClock, 256, 1>;
+
+const RESPONSE_TOPIC: &str = "schema_check/response";
+const SCHEMA_TOPIC: &str = "schema_check/device/schema";
+const WARNING_TOPIC: &str = "schema_check/device/schema/warning";
+
+/// Poll the interface and the tester, collecting all messages received by the tester.
+fn poll(interface: &mut Interface, mqtt: &mut Tester) -> Vec<(String, String)> {
+    let mut messages = Vec::new();
+
+    for _ in 0..100 {
+        interface.update().unwrap();
+        mqtt.poll(|_, topic, message, _| {
+            messages.push((
+                topic.to_string(),
+                String::from_utf8(message.to_vec()).unwrap(),
+            ));
+        })
+        .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    messages
+}
+
+fn publish(mqtt: &mut Tester, topic: &str, value: &[u8]) {
+    mqtt.client
+        .publish(
+            topic,
+            value,
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[minimq::Property::ResponseTopic(RESPONSE_TOPIC)],
+        )
+        .unwrap();
+}
+
+fn request(interface: &mut Interface, mqtt: &mut Tester, value: &[u8]) -> (u8, String) {
+    publish(mqtt, "schema_check/device/settings/rate", value);
+
+    let mut responses: Vec<_> = poll(interface, mqtt)
+        .into_iter()
+        .filter(|(topic, _)| topic == RESPONSE_TOPIC)
+        .map(|(_, message)| {
+            let response: Response = serde_json_core::from_str(&message).unwrap().0;
+            (response.code, response.msg.as_str().to_string())
+        })
+        .collect();
+    assert_eq!(responses.len(), 1);
+    responses.remove(0)
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: Tester = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    let mut interface: Interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "schema_check/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .schema_check(1, true)
+    .unwrap();
+
+    let hash = format!(
+        "{:08x}",
+        Settings::default().schema_hash::<32, 2>(1).unwrap()
+    );
+
+    // Wait for both clients to be subscribed.
+    let mut subscribed = false;
+    let mut published = None;
+    for _ in 0..500 {
+        interface.update().unwrap();
+        mqtt.poll(|_, topic, message, _| {
+            if topic == SCHEMA_TOPIC {
+                published.replace(String::from_utf8(message.to_vec()).unwrap());
+            }
+        })
+        .unwrap();
+
+        if mqtt.client.is_connected() && !subscribed {
+            mqtt.client.subscribe(SCHEMA_TOPIC, &[]).unwrap();
+            mqtt.client.subscribe(WARNING_TOPIC, &[]).unwrap();
+            mqtt.client.subscribe(RESPONSE_TOPIC, &[]).unwrap();
+            subscribed = true;
+        }
+
+        if subscribed
+            && !mqtt.client.subscriptions_pending()
+            && interface.is_subscribed()
+            && published.is_some()
+        {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(interface.is_subscribed());
+
+    // The hash of the settings is published.
+    assert_eq!(published.unwrap(), hash);
+
+    // A mismatching expected hash is warned about.
+    let expected = if hash == "00000000" {
+        "00000001"
+    } else {
+        "00000000"
+    };
+    publish(
+        &mut mqtt,
+        "schema_check/device/schema/expected",
+        expected.as_bytes(),
+    );
+    let messages = poll(&mut interface, &mut mqtt);
+    assert_eq!(
+        messages,
+        [(
+            WARNING_TOPIC.to_string(),
+            format!("Schema mismatch: expected {}, found {}", expected, hash)
+        )]
+    );
+    assert!(interface.schema_mismatch());
+
+    // Updates are rejected until the mismatch is resolved.
+    let (code, msg) = request(&mut interface, &mut mqtt, b"10");
+    assert!(code != 0);
+    assert_eq!(msg, "Schema mismatch");
+    assert_eq!(interface.settings().rate, 0);
+
+    interface.acknowledge_schema_mismatch();
+    let (code, _) = request(&mut interface, &mut mqtt, b"10");
+    assert_eq!(code, 0);
+    assert_eq!(interface.settings().rate, 10);
+
+    // A mismatching hash is reported again, while a matching hash resolves the mismatch.
+    publish(
+        &mut mqtt,
+        "schema_check/device/schema/expected",
+        expected.as_bytes(),
+    );
+    poll(&mut interface, &mut mqtt);
+    assert!(interface.schema_mismatch());
+
+    publish(
+        &mut mqtt,
+        "schema_check/device/schema/expected",
+        hash.as_bytes(),
+    );
+    assert!(poll(&mut interface, &mut mqtt).is_empty());
+    assert!(!interface.schema_mismatch());
+
+    let (code, _) = request(&mut interface, &mut mqtt, b"20");
+    assert_eq!(code, 0);
+    assert_eq!(interface.settings().rate, 20);
+}
+
+#[test]
+fn hash() {
+    #[derive(Miniconf, Default)]
+    struct Renamed {
+        gain: f32,
+        rates: u32,
+    }
+
+    #[derive(Miniconf, Default)]
+    struct Retyped {
+        gain: f32,
+        rate: i32,
+    }
+
+    let hash = Settings::default().schema_hash::<32, 2>(1).unwrap();
+    assert_eq!(hash, Settings::default().schema_hash::<32, 2>(1).unwrap());
+    assert_ne!(hash, Renamed::default().schema_hash::<32, 2>(1).unwrap());
+
+    // Types are not part of the hash, so retyped settings require a new version.
+    assert_eq!(hash, Retyped::default().schema_hash::<32, 2>(1).unwrap());
+    assert_ne!(hash, Retyped::default().schema_hash::<32, 2>(2).unwrap());
+}
+
+#[test]
+fn insufficient_buffers() {
+    assert_eq!(
+        Settings::default().schema_hash::<2, 2>(1),
+        Err(miniconf::IterError::InsufficientTopicLength)
+    );
+}
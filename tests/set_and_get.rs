@@ -0,0 +1,69 @@
+use miniconf::{Error, Miniconf};
+
+/// Stores a percentage, clamping values outside of the range 0-100.
+mod clamped {
+    use miniconf::Error;
+
+    pub fn set(field: &mut u8, value: &[u8]) -> Result<(), Error> {
+        let percent: i32 = serde_json_core::from_slice(value)?.0;
+        *field = percent.clamp(0, 100) as u8;
+        Ok(())
+    }
+
+    pub fn get(field: &u8, buffer: &mut [u8]) -> Result<usize, Error> {
+        serde_json_core::to_slice(field, buffer).map_err(|_| Error::SerializationFailed)
+    }
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    #[miniconf(with = "clamped")]
+    duty: u8,
+    #[miniconf(min = 0.0, max = 10.0)]
+    gain: f32,
+}
+
+#[test]
+fn clamped_value() {
+    let mut settings = Settings::default();
+    let mut buffer = [0; 32];
+
+    let len = settings.set_and_get("duty", b"150", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"100");
+    assert_eq!(settings.duty, 100);
+
+    let len = settings.set_and_get("duty", b"-5", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"0");
+}
+
+#[test]
+fn normalized_value() {
+    let mut settings = Settings::default();
+    let mut buffer = [0; 32];
+
+    // The stored value is retrieved in its canonical form.
+    let len = settings.set_and_get("gain", b"2", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"2.0");
+}
+
+#[test]
+fn failed_update() {
+    let mut settings = Settings::default();
+    let mut buffer = [0xFF; 32];
+
+    // Errors of the update are returned without retrieving the value.
+    assert_eq!(
+        settings.set_and_get("gain", b"11", &mut buffer),
+        Err(Error::OutOfRange {
+            min: Some("0.0"),
+            max: Some("10.0")
+        })
+    );
+    assert_eq!(buffer, [0xFF; 32]);
+
+    assert_eq!(
+        settings.set_and_get("missing", b"1", &mut buffer),
+        Err(Error::PathNotFound)
+    );
+    assert_eq!(buffer, [0xFF; 32]);
+}
@@ -0,0 +1,63 @@
+use miniconf::{Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    rate: u32,
+    inner: Inner,
+}
+
+#[test]
+fn match_applies() {
+    let mut settings = Settings::default();
+    let mut scratch = [0; 32];
+
+    assert!(settings.set_if("rate", b"0", b"10", &mut scratch).unwrap());
+    assert_eq!(settings.rate, 10);
+
+    settings.inner.gain = 1.5;
+    assert!(settings
+        .set_if("inner/gain", b"1.5", b"2.0", &mut scratch)
+        .unwrap());
+    assert_eq!(settings.inner.gain, 2.0);
+}
+
+#[test]
+fn mismatch_rejects() {
+    let mut settings = Settings {
+        rate: 5,
+        ..Default::default()
+    };
+    let mut scratch = [0; 32];
+
+    assert!(!settings.set_if("rate", b"4", b"10", &mut scratch).unwrap());
+    assert_eq!(settings.rate, 5);
+
+    // Errors are reported for invalid paths and values.
+    assert_eq!(
+        settings.set_if("inner", b"0", b"10", &mut scratch),
+        Err(Error::PathTooShort)
+    );
+    assert!(settings
+        .set_if("rate", b"5", b"\"ten\"", &mut scratch)
+        .is_err());
+    assert_eq!(settings.rate, 5);
+}
+
+#[test]
+fn insufficient_scratch() {
+    let mut settings = Settings {
+        rate: 500,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        settings.set_if("rate", b"500", b"10", &mut [0; 2]),
+        Err(Error::SerializationFailed)
+    );
+    assert_eq!(settings.rate, 500);
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "set-only")]
+
+use miniconf::{Error, Miniconf, MiniconfAtomic, Optional};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, MiniconfAtomic, Default, Debug, PartialEq)]
+struct Coefficients {
+    forward: f32,
+    backward: f32,
+}
+
+#[derive(Deserialize, Serialize, Miniconf, Default, Debug, PartialEq)]
+enum Mode {
+    #[default]
+    Off,
+    On,
+}
+
+#[derive(Miniconf, Default)]
+struct Channel {
+    gain: f32,
+    offset: Optional<i32>,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    filter: Coefficients,
+    mode: Mode,
+    channels: [Channel; 2],
+    rate: Option<u32>,
+}
+
+#[test]
+fn set_and_get() {
+    let mut settings = Settings::default();
+    let mut buffer = [0; 64];
+
+    settings
+        .set("filter", b"{\"forward\": 1.5, \"backward\": 2.0}")
+        .unwrap();
+    settings.set("mode", b"\"On\"").unwrap();
+    settings.set("channels/1/gain", b"3.5").unwrap();
+    settings.set("channels/0/offset/value", b"-4").unwrap();
+
+    assert_eq!(settings.filter.forward, 1.5);
+    assert_eq!(settings.mode, Mode::On);
+    assert_eq!(settings.channels[1].gain, 3.5);
+    assert_eq!(settings.channels[0].offset.value, -4);
+
+    let len = settings.get("channels/1/gain", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"3.5");
+}
+
+#[test]
+fn struct_object_rejected() {
+    let mut settings = Settings::default();
+
+    // Applying an object member by member requires path metadata.
+    assert_eq!(
+        settings.set("channels/0", b"{\"gain\": 1.0}"),
+        Err(Error::ExpectedLeaf)
+    );
+}
+
+#[test]
+fn errors() {
+    let mut settings = Settings::default();
+
+    assert_eq!(settings.set("unknown", b"1"), Err(Error::PathNotFound));
+    assert_eq!(settings.set("rate", b"5"), Err(Error::PathNotFound));
+}
@@ -0,0 +1,83 @@
+use miniconf::{Change, Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    rate: u32,
+    inner: Inner,
+}
+
+#[test]
+fn repeated_value() {
+    let mut settings = Settings::default();
+    let mut scratch = [0; 32];
+
+    assert_eq!(
+        settings.set_tracked("inner/gain", b"1.5", &mut scratch),
+        Ok(Change::Changed)
+    );
+    assert_eq!(
+        settings.set_tracked("inner/gain", b"1.5", &mut scratch),
+        Ok(Change::Unchanged)
+    );
+    assert_eq!(settings.inner.gain, 1.5);
+
+    // Values are compared in their serialized form, regardless of their representation.
+    assert_eq!(
+        settings.set_tracked("rate", b"0", &mut scratch),
+        Ok(Change::Unchanged)
+    );
+    assert_eq!(
+        settings.set_tracked("rate", b"10", &mut scratch),
+        Ok(Change::Changed)
+    );
+    assert_eq!(
+        settings.set_tracked("rate", b" 10", &mut scratch),
+        Ok(Change::Unchanged)
+    );
+}
+
+#[test]
+fn failed_update() {
+    let mut settings = Settings::default();
+    let mut scratch = [0; 32];
+
+    assert_eq!(
+        settings.set_tracked("inner/missing", b"1", &mut scratch),
+        Err(Error::PathNotFound)
+    );
+    assert!(settings.set_tracked("rate", b"-1", &mut scratch).is_err());
+    assert_eq!(settings.rate, 0);
+}
+
+#[test]
+fn insufficient_scratch() {
+    let mut settings = Settings {
+        rate: 10,
+        ..Default::default()
+    };
+
+    // The previous value does not fit.
+    assert_eq!(
+        settings.set_tracked("rate", b"5", &mut [0; 1]),
+        Err(Error::SerializationFailed)
+    );
+    assert_eq!(settings.rate, 10);
+
+    // The updated value does not fit, so the previous value is restored.
+    assert_eq!(
+        settings.set_tracked("rate", b"1000", &mut [0; 4]),
+        Err(Error::SerializationFailed)
+    );
+    assert_eq!(settings.rate, 10);
+
+    assert_eq!(
+        settings.set_tracked("rate", b"1000", &mut [0; 6]),
+        Ok(Change::Changed)
+    );
+    assert_eq!(settings.rate, 1000);
+}
@@ -0,0 +1,86 @@
+use miniconf::Miniconf;
+
+#[derive(Miniconf, Default, Clone)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Miniconf, Default, Clone)]
+struct Settings {
+    rate: u32,
+    inner: Inner,
+    channel: Option<Inner>,
+    #[miniconf(with = "raw")]
+    raw: u8,
+}
+
+mod raw {
+    use miniconf::Error;
+
+    pub fn set(raw: &mut u8, data: &[u8]) -> Result<(), Error> {
+        *raw = *data.first().ok_or(Error::SerializationFailed)?;
+        Ok(())
+    }
+
+    pub fn get(raw: &u8, data: &mut [u8]) -> Result<usize, Error> {
+        *data.first_mut().ok_or(Error::SerializationFailed)? = *raw;
+        Ok(1)
+    }
+}
+
+#[test]
+fn identical_equal() {
+    let settings = Settings {
+        rate: 10,
+        ..Default::default()
+    };
+
+    assert!(settings
+        .settings_eq::<64, 32, 4>(&settings.clone())
+        .unwrap());
+}
+
+#[test]
+fn nested_float_differs() {
+    let settings = Settings::default();
+    let mut other = settings.clone();
+    other.inner.gain = 0.5;
+
+    assert!(!settings.settings_eq::<64, 32, 4>(&other).unwrap());
+    assert!(!other.settings_eq::<64, 32, 4>(&settings).unwrap());
+}
+
+#[test]
+fn option_presence_differs() {
+    let settings = Settings::default();
+    let mut other = settings.clone();
+    other.channel.replace(Inner::default());
+
+    assert!(!settings.settings_eq::<64, 32, 4>(&other).unwrap());
+    assert!(!other.settings_eq::<64, 32, 4>(&settings).unwrap());
+}
+
+#[test]
+fn unmeasured_value_differs() {
+    let settings = Settings::default();
+    let mut other = settings.clone();
+    other.raw = 1;
+
+    assert!(settings
+        .settings_eq::<64, 32, 4>(&settings.clone())
+        .unwrap());
+    assert!(!settings.settings_eq::<64, 32, 4>(&other).unwrap());
+}
+
+#[test]
+fn value_too_large() {
+    let settings = Settings {
+        rate: 10,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        settings.settings_eq::<64, 1, 4>(&settings.clone()),
+        Err(miniconf::Error::SerializationFailed)
+    ));
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "alloc")]
+
+use miniconf::{embedded_time::duration::Extensions, minimq, Miniconf, MiniconfAtomic};
+use serde::{Deserialize, Serialize};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Copy, Debug, Default, MiniconfAtomic, Serialize, Deserialize)]
+struct Block {
+    values: [u32; 16],
+}
+
+#[derive(Clone, Copy, Debug, Default, Miniconf)]
+struct Settings {
+    block: Block,
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "settings_reader/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    // Read the settings concurrently to all updates, checking that every read is consistent.
+    let reader = interface.reader();
+    let done = Arc::new(AtomicBool::new(false));
+    let task = std::thread::spawn({
+        let reader = reader.clone();
+        let done = done.clone();
+        move || {
+            let mut reads = 0;
+            let mut latest = 0;
+            while !done.load(Ordering::Relaxed) {
+                let (settings, version) = reader.read_versioned();
+                let values = settings.block.values;
+                assert!(values.iter().all(|&value| value == values[0]));
+                assert!(version >= latest);
+                latest = version;
+                reads += 1;
+            }
+            reads
+        }
+    });
+
+    interface
+        .block_until_active(|_, _, _| Result::<(), &str>::Ok(()), 10.seconds())
+        .unwrap();
+
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    for value in 1..=20u32 {
+        let block = Block {
+            values: [value; 16],
+        };
+        let mut payload = [0; 128];
+        let len = serde_json_core::to_slice(&block, &mut payload).unwrap();
+        mqtt.client
+            .publish(
+                "settings_reader/device/settings/block",
+                &payload[..len],
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[],
+            )
+            .unwrap();
+
+        while interface.settings().block.values[0] != value {
+            interface.update().unwrap();
+            mqtt.poll(|_, _, _, _| {}).unwrap();
+        }
+    }
+
+    done.store(true, Ordering::Relaxed);
+    assert!(task.join().unwrap() > 0);
+
+    // The reader observes the most recent update.
+    let (settings, version) = reader.read_versioned();
+    assert_eq!(settings.block.values, [20; 16]);
+    assert!(version >= 20);
+}
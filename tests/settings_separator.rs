@@ -0,0 +1,149 @@
+use miniconf::{embedded_time::duration::Extensions, minimq, ConstructionError, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Inner {
+    gain: f32,
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+    inner: Inner,
+}
+
+type Interface = miniconf::MqttClient<Settings, Stack, StandardClock, 256>;
+type Tester = minimq::Minimq<Stack, StandardClock, 256, 1>;
+
+/// Poll the interface and the tester, collecting the messages received by the tester.
+fn poll(interface: &mut Interface, mqtt: &mut Tester) -> Vec<(String, Vec<u8>)> {
+    let mut messages = Vec::new();
+    for _ in 0..100 {
+        interface.update().unwrap();
+        mqtt.poll(|_, topic, message, _| messages.push((topic.to_string(), message.to_vec())))
+            .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    messages
+}
+
+/// Publish a settings update and wait for the response of the interface.
+fn set(interface: &mut Interface, mqtt: &mut Tester, topic: &str, value: &[u8]) -> Vec<Vec<u8>> {
+    mqtt.client
+        .publish(
+            topic,
+            value,
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[minimq::Property::ResponseTopic(
+                "settings_separator/response",
+            )],
+        )
+        .unwrap();
+
+    poll(interface, mqtt)
+        .into_iter()
+        .filter(|(topic, _)| topic == "settings_separator/response")
+        .map(|(_, message)| message)
+        .collect()
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: Tester = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    mqtt.client
+        .subscribe("settings_separator/response", &[])
+        .unwrap();
+    mqtt.client
+        .subscribe("settings_separator/device/settings/#", &[])
+        .unwrap();
+    while mqtt.client.subscriptions_pending() {
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    let mut interface: Interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "settings_separator/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .settings_separator("//")
+    .unwrap();
+
+    interface
+        .block_until_active(|_, _, _| Result::<(), &str>::Ok(()), 10.seconds())
+        .unwrap();
+
+    // Settings are republished using the separator.
+    let mut republished: Vec<_> = poll(&mut interface, &mut mqtt)
+        .into_iter()
+        .map(|(topic, _)| topic)
+        .collect();
+    republished.sort();
+    assert_eq!(
+        republished,
+        [
+            "settings_separator/device/settings//data",
+            "settings_separator/device/settings//inner/gain",
+        ]
+    );
+
+    // Received topics are stripped using the same separator.
+    let responses = set(
+        &mut interface,
+        &mut mqtt,
+        "settings_separator/device/settings//inner/gain",
+        b"1.5",
+    );
+    assert_eq!(responses, [br#"{"code":0,"msg":"OK"}"#]);
+    assert_eq!(interface.settings().inner.gain, 1.5);
+
+    // Topics not using the separator are ignored.
+    let responses = set(
+        &mut interface,
+        &mut mqtt,
+        "settings_separator/device/settings/data",
+        b"5",
+    );
+    assert!(responses.is_empty());
+    assert_eq!(interface.settings().data, 0);
+}
+
+#[test]
+fn invalid_separator() {
+    let interface: Interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "settings_separator/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        interface.settings_separator("."),
+        Err(ConstructionError::InvalidSeparator)
+    ));
+}
@@ -0,0 +1,109 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, PartialEq, Miniconf)]
+struct AdditionalSettings {
+    inner: u8,
+    gains: [f32; 2],
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Miniconf)]
+struct Settings {
+    data: u32,
+    more: AdditionalSettings,
+    enabled: bool,
+}
+
+fn settings() -> Settings {
+    Settings {
+        data: 7,
+        more: AdditionalSettings {
+            inner: 3,
+            gains: [1.5, -2.0],
+        },
+        enabled: true,
+    }
+}
+
+async fn verify_snapshot() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    // Wait for the device to connect and publish its snapshot.
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+
+    // The snapshot is retained, so it is received after subscribing.
+    mqtt.client
+        .subscribe("snapshot/device/settings/#", &[])
+        .unwrap();
+
+    let mut snapshot = None;
+    for _ in 0..100 {
+        mqtt.poll(|_, topic, value, _properties| {
+            log::info!("{}: {:?}", &topic, value);
+            assert_eq!(topic, "snapshot/device/settings/snapshot");
+            snapshot.replace(String::from_utf8(value.to_vec()).unwrap());
+        })
+        .unwrap();
+
+        if snapshot.is_some() {
+            break;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // Every setting is contained in nested form.
+    let snapshot = snapshot.expect("Snapshot not received");
+    assert_eq!(
+        snapshot,
+        r#"{"data":7,"more":{"inner":3,"gains":{"0":1.5,"1":-2.0}},"enabled":true}"#
+    );
+
+    let mut restored = Settings::default();
+    restored.apply_json_object(snapshot.as_bytes()).unwrap();
+    assert_eq!(restored, settings());
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { verify_snapshot().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "snapshot/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        settings(),
+    )
+    .unwrap()
+    .republish_snapshot(true);
+
+    // Poll the client for 5 seconds. This should be enough time for the miniconf client to publish
+    // the snapshot.
+    for _ in 0..500 {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Snapshot not received");
+}
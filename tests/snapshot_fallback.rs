@@ -0,0 +1,95 @@
+use miniconf::{minimq, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    values: [u32; 8],
+}
+
+async fn verify_settings() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    // Subscribe to the settings topic.
+    mqtt.client
+        .subscribe("snapshot_fallback/device/settings/#", &[])
+        .unwrap();
+
+    // Wait the other device to connect and publish settings.
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+    // The snapshot does not fit within a message, so every setting is republished individually.
+    let mut received_settings: std::collections::HashMap<String, usize> = (0..8)
+        .map(|index| {
+            (
+                format!("snapshot_fallback/device/settings/values/{}", index),
+                0,
+            )
+        })
+        .collect();
+
+    for _ in 0..50 {
+        mqtt.poll(|_, topic, value, _properties| {
+            log::info!("{}: {:?}", &topic, value);
+            assert_eq!(value, b"1000000000");
+            let element = received_settings.get_mut(topic).unwrap();
+            *element += 1;
+        })
+        .unwrap();
+
+        if received_settings.values().all(|&x| x >= 1) {
+            break;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // Ensure that all fields were iterated exactly once.
+    assert!(received_settings.values().all(|&x| x == 1));
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { verify_settings().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 128> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "snapshot_fallback/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings {
+            values: [1_000_000_000; 8],
+        },
+    )
+    .unwrap()
+    .republish_snapshot(true);
+
+    // Poll the client for 5 seconds. This should be enough time for the miniconf client to publish
+    // all settings values.
+    for _ in 0..500 {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    // Next, verify that all of the settings have been published by the client.
+    task.await.expect("Not all settings received");
+}
@@ -0,0 +1,124 @@
+use miniconf::{
+    minimq::embedded_nal::{nb, SocketAddr, TcpClientStack},
+    Miniconf,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+/// A network stack that can simulate the loss of the TCP connection.
+struct FlakyStack {
+    stack: Stack,
+    disconnect: Arc<AtomicBool>,
+}
+
+impl TcpClientStack for FlakyStack {
+    type TcpSocket = <Stack as TcpClientStack>::TcpSocket;
+    type Error = <Stack as TcpClientStack>::Error;
+
+    fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
+        self.stack.socket()
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        remote: SocketAddr,
+    ) -> nb::Result<(), Self::Error> {
+        self.stack.connect(socket, remote)
+    }
+
+    fn is_connected(&mut self, socket: &Self::TcpSocket) -> Result<bool, Self::Error> {
+        if self.disconnect.swap(false, Ordering::SeqCst) {
+            return Ok(false);
+        }
+
+        self.stack.is_connected(socket)
+    }
+
+    fn send(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &[u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.stack.send(socket, buffer)
+    }
+
+    fn receive(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.stack.receive(socket, buffer)
+    }
+
+    fn close(&mut self, socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        self.stack.close(socket)
+    }
+}
+
+/// Simulate a loss of the connection and wait for the client to reconnect.
+async fn reconnect(
+    interface: &mut miniconf::MqttClient<Settings, FlakyStack, StandardClock, 256>,
+    disconnect: &AtomicBool,
+) {
+    disconnect.store(true, Ordering::SeqCst);
+    let mut disconnected = false;
+    loop {
+        interface.update().unwrap();
+
+        let connected = interface.minimq_mut().client.is_connected();
+        disconnected |= !connected;
+        if disconnected && connected {
+            break;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    let disconnect = Arc::new(AtomicBool::new(false));
+    let stack = FlakyStack {
+        stack: Stack,
+        disconnect: disconnect.clone(),
+    };
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        stack,
+        "",
+        "stats/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    // Wait for the initial subscription and republish.
+    while !interface.just_finished_republish() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    let stats = interface.stats();
+    assert_eq!(stats.reconnects, 0);
+    assert_eq!(stats.republishes, 1);
+
+    reconnect(&mut interface, &disconnect).await;
+    reconnect(&mut interface, &disconnect).await;
+
+    let stats = interface.stats();
+    assert_eq!(stats.reconnects, 2);
+    assert_eq!(stats.last_error_kind, None);
+}
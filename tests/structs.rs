@@ -1,4 +1,4 @@
-use miniconf::{Miniconf, MiniconfAtomic};
+use miniconf::{Error, Miniconf, MiniconfAtomic};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -42,6 +42,224 @@ fn atomic_struct() {
     assert_eq!(metadata.max_topic_size, "c".len());
 }
 
+#[test]
+fn atomic_struct_member_get() {
+    #[derive(Default, PartialEq, Debug, Serialize, Deserialize)]
+    struct Tap {
+        #[serde(rename = "coefficient")]
+        coeff: f32,
+        delay: Option<u32>,
+    }
+
+    #[derive(MiniconfAtomic, Default, PartialEq, Debug, Serialize, Deserialize)]
+    struct Filter {
+        inner: [u32; 3],
+        taps: (Tap, Tap),
+    }
+
+    #[derive(Miniconf, Default, PartialEq, Debug)]
+    struct Settings {
+        atomic: Filter,
+    }
+
+    let mut settings = Settings::default();
+    settings.atomic.inner = [1, 2, 3];
+    settings.atomic.taps.1 = Tap {
+        coeff: 0.5,
+        delay: Some(4),
+    };
+
+    let mut buf = [0; 64];
+    let len = settings.get("atomic/inner/0", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"1");
+
+    let len = settings.get("atomic/inner", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"[1,2,3]");
+
+    let len = settings.get("atomic/taps/1", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"{\"coefficient\":0.5,\"delay\":4}");
+
+    let len = settings.get("atomic/taps/1/delay", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"4");
+
+    // Members are addressed by their serialized names.
+    let len = settings.get("atomic/taps/1/coefficient", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"0.5");
+    assert_eq!(
+        settings.get("atomic/taps/1/coeff", &mut buf),
+        Err(Error::PathNotFound)
+    );
+
+    assert_eq!(
+        settings.get("atomic/inner/3", &mut buf),
+        Err(Error::BadIndex)
+    );
+    assert_eq!(
+        settings.get("atomic/inner/0/a", &mut buf),
+        Err(Error::PathTooLong)
+    );
+
+    // Absent options are read as a whole.
+    let len = settings.get("atomic/taps/0/delay", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"null");
+
+    // Members cannot be set individually.
+    assert_eq!(
+        settings.set("atomic/inner/0", b"5"),
+        Err(Error::AtomicUpdateRequired)
+    );
+    assert_eq!(settings.atomic.inner, [1, 2, 3]);
+}
+
+#[test]
+fn atomic_struct_patch() {
+    #[derive(MiniconfAtomic, Default, PartialEq, Debug, Serialize, Deserialize)]
+    struct Filter {
+        gain: f32,
+        length: u32,
+    }
+
+    #[derive(Miniconf, Default, PartialEq, Debug)]
+    struct Settings {
+        filter: Filter,
+    }
+
+    let mut settings = Settings::default();
+    settings.filter.length = 4;
+
+    // Only the provided fields are updated.
+    settings.set("filter/~patch", b"{\"gain\": 0.5}").unwrap();
+    assert_eq!(
+        settings.filter,
+        Filter {
+            gain: 0.5,
+            length: 4
+        }
+    );
+
+    settings
+        .set("filter/~patch", b"{\"length\":8,\"gain\":1.5}")
+        .unwrap();
+    assert_eq!(
+        settings.filter,
+        Filter {
+            gain: 1.5,
+            length: 8
+        }
+    );
+
+    // Nothing is updated if any of the fields is unknown or invalid.
+    assert_eq!(
+        settings.set("filter/~patch", b"{\"length\":2,\"offset\":1}"),
+        Err(Error::UnknownField)
+    );
+    assert!(matches!(
+        settings.set("filter/~patch", b"{\"length\":2,\"gain\":true}"),
+        Err(Error::Deserialization(_))
+    ));
+    assert_eq!(settings.filter.length, 8);
+
+    assert_eq!(
+        settings.set("filter/~patch/gain", b"0"),
+        Err(Error::AtomicUpdateRequired)
+    );
+}
+
+#[test]
+fn atomic_struct_unknown_fields() {
+    #[derive(MiniconfAtomic, Default, PartialEq, Debug, Serialize, Deserialize)]
+    #[miniconf(deny_unknown_fields)]
+    struct Filter {
+        gain: f32,
+        length: u32,
+    }
+
+    #[derive(Miniconf, Default, PartialEq, Debug)]
+    struct Settings {
+        filter: Filter,
+    }
+
+    let mut settings = Settings::default();
+
+    // Keys other than the field names are rejected and leave the settings unchanged.
+    assert_eq!(
+        settings.set("filter", br#"{"gian": 3.0, "length": 2}"#),
+        Err(Error::UnknownField)
+    );
+    assert_eq!(settings, Settings::default());
+
+    settings
+        .set("filter", br#"{"gain": 3.0, "length": 2}"#)
+        .unwrap();
+    assert_eq!(
+        settings.filter,
+        Filter {
+            gain: 3.0,
+            length: 2
+        }
+    );
+}
+
+#[test]
+fn atomic_struct_unknown_renamed_fields() {
+    #[derive(MiniconfAtomic, Default, PartialEq, Debug, Serialize, Deserialize)]
+    #[miniconf(deny_unknown_fields)]
+    #[serde(rename_all = "camelCase")]
+    struct Filter {
+        cutoff_frequency: f32,
+        #[serde(rename = "taps")]
+        length: u32,
+        r#type: u8,
+    }
+
+    #[derive(Miniconf, Default, PartialEq, Debug)]
+    struct Settings {
+        filter: Filter,
+    }
+
+    let mut settings = Settings::default();
+
+    // Fields are known by their serde names.
+    settings
+        .set(
+            "filter",
+            br#"{"cutoffFrequency": 3.0, "taps": 2, "type": 1}"#,
+        )
+        .unwrap();
+    assert_eq!(
+        settings.filter,
+        Filter {
+            cutoff_frequency: 3.0,
+            length: 2,
+            r#type: 1,
+        }
+    );
+
+    for value in [
+        &br#"{"cutoff_frequency": 3.0, "taps": 2, "type": 1}"#[..],
+        br#"{"cutoffFrequency": 3.0, "length": 2, "type": 1}"#,
+    ] {
+        assert_eq!(settings.set("filter", value), Err(Error::UnknownField));
+        assert_eq!(
+            settings.set("filter/~patch", value),
+            Err(Error::UnknownField)
+        );
+    }
+
+    // Patches address the fields by their serde names as well.
+    settings
+        .set("filter/~patch", br#"{"taps": 4, "cutoffFrequency": 1.5}"#)
+        .unwrap();
+    assert_eq!(
+        settings.filter,
+        Filter {
+            cutoff_frequency: 1.5,
+            length: 4,
+            r#type: 1,
+        }
+    );
+}
+
 #[test]
 fn recursive_struct() {
     #[derive(Miniconf, Default, PartialEq, Debug)]
@@ -69,12 +287,82 @@ fn recursive_struct() {
 
     assert_eq!(settings, expected);
 
-    // It is not allowed to set a non-terminal node.
+    // Non-terminal nodes accept an object of their members.
+    let field = "c".split('/').peekable();
+    settings.string_set(field, b"{\"a\": 5}").unwrap();
+    assert_eq!(settings.c.a, 5);
+
+    // Other values are not accepted at non-terminal nodes.
     let field = "c".split('/').peekable();
-    assert!(settings.string_set(field, b"{\"a\": 5}").is_err());
+    assert!(settings.string_set(field, b"5").is_err());
 
     // Check that metadata is correct.
     let metadata = settings.get_metadata();
     assert_eq!(metadata.max_depth, 3);
     assert_eq!(metadata.max_topic_size, "c/a".len());
 }
+
+#[test]
+fn set_non_leaf() {
+    #[derive(Miniconf, Default, PartialEq, Debug)]
+    struct Inner {
+        a: u32,
+    }
+
+    #[derive(Miniconf, Default, PartialEq, Debug)]
+    struct Settings {
+        c: Inner,
+    }
+
+    let mut settings = Settings::default();
+
+    // Setting a value other than an object at a non-atomic struct node requires a leaf.
+    assert_eq!(settings.set("c", b"5"), Err(Error::ExpectedLeaf));
+
+    // Paths that do not exist are still reported as such.
+    assert_eq!(settings.set("c/b", b"5"), Err(Error::PathNotFound));
+}
+
+#[test]
+fn set_struct_object() {
+    #[derive(Miniconf, Default, PartialEq, Debug)]
+    struct Adc {
+        gain: f32,
+        offset: f32,
+    }
+
+    #[derive(Miniconf, Default, PartialEq, Debug)]
+    struct Settings {
+        adc: [Adc; 2],
+    }
+
+    let mut settings = Settings::default();
+
+    // Non-atomic structs accept an object of their members at their own path.
+    settings
+        .set("adc/0", b"{\"gain\":1.0,\"offset\":2.0}")
+        .unwrap();
+
+    let mut buf = [0; 16];
+    let len = settings.get("adc/0/gain", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"1.0");
+    let len = settings.get("adc/0/offset", &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"2.0");
+
+    // Members can still be set individually.
+    settings.set("adc/0/offset", b"3.0").unwrap();
+    assert_eq!(settings.adc[0].offset, 3.0);
+
+    // Unknown keys are rejected. Members preceding them remain updated.
+    assert_eq!(
+        settings.set("adc/1", b"{\"gain\":4.0,\"phase\":1.0}"),
+        Err(Error::PathNotFound)
+    );
+    assert_eq!(settings.adc[1].gain, 4.0);
+
+    // Malformed objects are rejected.
+    assert!(matches!(
+        settings.set("adc/1", b"{\"gain\":"),
+        Err(Error::Deserialization(_))
+    ));
+}
@@ -0,0 +1,118 @@
+use miniconf::{minimq, Miniconf};
+use serde::Serialize;
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    gain: u32,
+    offset: u32,
+}
+
+/// An error identifying the setting that failed validation.
+#[derive(Serialize)]
+struct ValidationError {
+    code: u8,
+    field: &'static str,
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "structured-error/tester/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    for (topic, value, expected) in [
+        (
+            "structured-error/device/settings/gain",
+            "100",
+            r#"{"code":255,"msg":"Rejected","error":{"code":3,"field":"gain"}}"#,
+        ),
+        (
+            "structured-error/device/settings/offset",
+            "5",
+            r#"{"code":0,"msg":"OK"}"#,
+        ),
+    ] {
+        mqtt.client
+            .publish(
+                topic,
+                value.as_bytes(),
+                minimq::QoS::AtMostOnce,
+                minimq::Retain::NotRetained,
+                &[minimq::Property::ResponseTopic(response_topic)],
+            )
+            .unwrap();
+
+        let mut response = None;
+        while response.is_none() {
+            mqtt.poll(|_client, _topic, message, _properties| {
+                response.replace(message.to_vec());
+            })
+            .unwrap();
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(core::str::from_utf8(&response.unwrap()).unwrap(), expected);
+    }
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "structured-error/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .republish_on_connect(false);
+
+    while !task.is_finished() {
+        interface
+            .handled_update_serde(|_path, old_settings, new_settings| {
+                if new_settings.gain > 10 {
+                    return Err(ValidationError {
+                        code: 3,
+                        field: "gain",
+                    });
+                }
+
+                *old_settings = new_settings.clone();
+                Ok(())
+            })
+            .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    assert_eq!(interface.settings().gain, 0);
+    assert_eq!(interface.settings().offset, 5);
+}
@@ -0,0 +1,109 @@
+use miniconf::{minimq, ConstructionError, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+/// Wait for the next message on the liveness topic of the device.
+fn liveness(
+    mqtt: &mut minimq::Minimq<Stack, StandardClock, 256, 1>,
+    interface: &mut Option<miniconf::MqttClient<Settings, Stack, StandardClock, 256>>,
+) -> String {
+    for _ in 0..500 {
+        if let Some(interface) = interface.as_mut() {
+            interface.update().unwrap();
+        }
+
+        let mut payload = None;
+        mqtt.poll(|_, topic, message, _| {
+            if topic == "structured_liveness/device/alive" {
+                payload.replace(String::from_utf8(message.to_vec()).unwrap());
+            }
+        })
+        .unwrap();
+
+        if let Some(payload) = payload {
+            return payload;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    panic!("No liveness received");
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    let mut interface = Some(
+        miniconf::MqttClient::new(
+            Stack,
+            "structured-liveness-device",
+            "structured_liveness/device",
+            "127.0.0.1".parse().unwrap(),
+            StandardClock::default(),
+            Settings::default(),
+        )
+        .unwrap()
+        .structured_liveness(true)
+        .unwrap(),
+    );
+
+    // Connect the device before subscribing, so that no stale retained liveness is received.
+    while !interface.as_mut().unwrap().is_subscribed() {
+        interface.as_mut().unwrap().update().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    mqtt.client
+        .subscribe("structured_liveness/device/alive", &[])
+        .unwrap();
+    assert_eq!(
+        liveness(&mut mqtt, &mut interface),
+        r#"{"id":"structured-liveness-device","online":true}"#
+    );
+
+    // The broker publishes the will once the device disconnects without notice.
+    interface.take();
+    assert_eq!(
+        liveness(&mut mqtt, &mut interface),
+        r#"{"id":"structured-liveness-device","online":false}"#
+    );
+}
+
+#[test]
+fn will_too_large() {
+    // The will carries the client ID, so it exceeds the message size while the settings topics
+    // fit.
+    let interface: miniconf::MqttClient<Settings, _, _, 64> = miniconf::MqttClient::new(
+        Stack,
+        "structured-liveness-device-with-a-long-id",
+        "structured_liveness/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        interface.structured_liveness(true),
+        Err(ConstructionError::Mqtt(_))
+    ));
+}
@@ -0,0 +1,162 @@
+use miniconf::{minimq::embedded_nal, Miniconf};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+use embedded_nal::{nb, SocketAddr, TcpClientStack};
+
+// Specifies whether subscription acknowledgements from the broker are withheld from the client.
+static HOLD_SUBACK: AtomicBool = AtomicBool::new(true);
+
+/// A network stack that delays subscription acknowledgements from the broker.
+struct DelayedSubAck {
+    stack: Stack,
+    received: Vec<u8>,
+    released: VecDeque<u8>,
+    held: Vec<u8>,
+}
+
+impl DelayedSubAck {
+    fn new() -> Self {
+        Self {
+            stack: Stack,
+            received: Vec::new(),
+            released: VecDeque::new(),
+            held: Vec::new(),
+        }
+    }
+
+    /// Get the length of the first complete MQTT packet received, if any.
+    fn packet_length(&self) -> Option<usize> {
+        let mut remaining = 0;
+        for (i, byte) in self.received.iter().skip(1).take(4).enumerate() {
+            remaining |= ((byte & 0x7F) as usize) << (7 * i);
+            if byte & 0x80 == 0 {
+                let length = 1 + (i + 1) + remaining;
+                return (self.received.len() >= length).then_some(length);
+            }
+        }
+
+        None
+    }
+}
+
+impl TcpClientStack for DelayedSubAck {
+    type TcpSocket = <Stack as TcpClientStack>::TcpSocket;
+    type Error = <Stack as TcpClientStack>::Error;
+
+    fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
+        self.received.clear();
+        self.released.clear();
+        self.held.clear();
+        self.stack.socket()
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        remote: SocketAddr,
+    ) -> nb::Result<(), Self::Error> {
+        self.stack.connect(socket, remote)
+    }
+
+    fn is_connected(&mut self, socket: &Self::TcpSocket) -> Result<bool, Self::Error> {
+        self.stack.is_connected(socket)
+    }
+
+    fn send(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &[u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.stack.send(socket, buffer)
+    }
+
+    fn receive(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<usize, Self::Error> {
+        let mut data = [0; 1024];
+        match self.stack.receive(socket, &mut data) {
+            Ok(len) => self.received.extend_from_slice(&data[..len]),
+            Err(nb::Error::WouldBlock) => {}
+            Err(err) => return Err(err),
+        }
+
+        // Sort complete packets into those released to the client and held acknowledgements.
+        while let Some(length) = self.packet_length() {
+            let packet: Vec<u8> = self.received.drain(..length).collect();
+            if packet[0] == 0x90 {
+                self.held.extend(packet);
+            } else {
+                self.released.extend(packet);
+            }
+        }
+
+        if !HOLD_SUBACK.load(Ordering::Relaxed) {
+            self.released.extend(self.held.drain(..));
+        }
+
+        if self.released.is_empty() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        let len = buffer.len().min(self.released.len());
+        for (byte, released) in buffer.iter_mut().zip(self.released.drain(..len)) {
+            *byte = released;
+        }
+
+        Ok(len)
+    }
+
+    fn close(&mut self, socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        self.stack.close(socket)
+    }
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        DelayedSubAck::new(),
+        "",
+        "subscription_ack/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap();
+
+    // The client waits for the acknowledgement and does not start republishing settings, even
+    // after the republish timeout has elapsed.
+    for _ in 0..300 {
+        interface.update().unwrap();
+        assert!(!interface.is_subscribed());
+        assert!(!interface.just_finished_republish());
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // Once acknowledged, the subscription is reported and settings are republished.
+    HOLD_SUBACK.store(false, Ordering::Relaxed);
+    let mut republished = false;
+    for _ in 0..500 {
+        interface.update().unwrap();
+        republished |= interface.just_finished_republish();
+        if republished {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert!(interface.is_subscribed());
+    assert!(republished);
+}
@@ -0,0 +1,83 @@
+use miniconf::{Error, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Channel {
+    gain: f32,
+    offset: f32,
+    enabled: bool,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    channels: [Channel; 2],
+    optional: Option<Channel>,
+    rate: u32,
+}
+
+#[test]
+fn array_element() {
+    let settings = Settings::default();
+    let all = settings.get_metadata();
+    let subtree = settings.subtree_metadata("channels/0").unwrap();
+
+    // The subtree only contains the members of a single channel.
+    assert_eq!(subtree.max_topic_size, "enabled".len());
+    assert_eq!(subtree.max_depth, 2);
+    assert_eq!(subtree.path_count, 3);
+
+    assert!(subtree.max_topic_size < all.max_topic_size);
+    assert!(subtree.max_depth < all.max_depth);
+    assert!(subtree.path_count < all.path_count);
+
+    let channels = settings.subtree_metadata("channels").unwrap();
+    assert_eq!(channels.max_topic_size, "0/enabled".len());
+    assert_eq!(channels.path_count, 6);
+}
+
+#[test]
+fn root() {
+    let settings = Settings::default();
+    let all = settings.get_metadata();
+    let root = settings.subtree_metadata("").unwrap();
+
+    assert_eq!(root.max_topic_size, all.max_topic_size);
+    assert_eq!(root.max_depth, all.max_depth);
+    assert_eq!(root.path_count, all.path_count);
+}
+
+#[test]
+fn leaf() {
+    let settings = Settings::default();
+
+    let leaf = settings.subtree_metadata("channels/1/gain").unwrap();
+    assert_eq!(leaf.max_topic_size, 0);
+    assert_eq!(leaf.path_count, 1);
+
+    let leaf = settings.subtree_metadata("rate").unwrap();
+    assert_eq!(leaf.max_topic_size, 0);
+    assert_eq!(leaf.path_count, 1);
+}
+
+#[test]
+fn invalid_paths() {
+    let settings = Settings::default();
+
+    assert!(matches!(
+        settings.subtree_metadata("channels/2"),
+        Err(Error::BadIndex)
+    ));
+    assert!(matches!(
+        settings.subtree_metadata("rate/extra"),
+        Err(Error::PathTooLong)
+    ));
+    assert!(matches!(
+        settings.subtree_metadata("unknown"),
+        Err(Error::PathNotFound)
+    ));
+
+    // Absent optional settings have no subtree.
+    assert!(matches!(
+        settings.subtree_metadata("optional"),
+        Err(Error::PathNotFound)
+    ));
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "alloc")]
+
+use miniconf::{Error, Miniconf};
+use serde::{Deserialize, Serialize};
+
+#[derive(Miniconf, Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+enum Mode {
+    #[default]
+    Off,
+    Sweep,
+}
+
+#[derive(Miniconf, Default, Debug)]
+struct Channel {
+    gain: f32,
+    enabled: bool,
+}
+
+#[derive(Miniconf, Default, Debug)]
+struct Settings {
+    channels: [Channel; 2],
+    mode: Mode,
+    offset: Option<i32>,
+    label: miniconf::heapless::String<8>,
+}
+
+/// The mirror of the settings on the host.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct HostChannel {
+    gain: f32,
+    enabled: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct HostSettings {
+    channels: [HostChannel; 2],
+    mode: Mode,
+    offset: Option<i32>,
+    label: miniconf::heapless::String<8>,
+}
+
+#[test]
+fn round_trip() {
+    let mut settings = Settings {
+        mode: Mode::Sweep,
+        ..Default::default()
+    };
+    settings.channels[1].gain = 1.5;
+    settings.channels[1].enabled = true;
+    settings.label.push_str("ch").unwrap();
+
+    let host: HostSettings = settings.to_serde_value().unwrap();
+    assert_eq!(
+        host,
+        HostSettings {
+            channels: [
+                HostChannel {
+                    gain: 0.0,
+                    enabled: false
+                },
+                HostChannel {
+                    gain: 1.5,
+                    enabled: true
+                },
+            ],
+            mode: Mode::Sweep,
+            offset: None,
+            label: "ch".into(),
+        }
+    );
+
+    // The host representation can be applied to the settings again.
+    let mut host = host;
+    host.channels[0].gain = -2.0;
+    host.offset = Some(3);
+
+    let mut restored = Settings {
+        offset: Some(0),
+        ..Default::default()
+    };
+    let json = miniconf::serde_json_core::to_vec::<_, 256>(&host).unwrap();
+    restored.apply_json_object(&json).unwrap();
+
+    assert_eq!(restored.channels[0].gain, -2.0);
+    assert_eq!(restored.channels[1].gain, 1.5);
+    assert!(restored.channels[1].enabled);
+    assert_eq!(restored.mode, Mode::Sweep);
+    assert_eq!(restored.offset, Some(3));
+    assert_eq!(restored.label.as_str(), "ch");
+}
+
+#[test]
+fn mismatch() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Other {
+        channels: u32,
+    }
+
+    let settings = Settings::default();
+    assert!(matches!(
+        settings.to_serde_value::<Other>(),
+        Err(Error::Deserialization(_))
+    ));
+}
+
+#[derive(Miniconf, Default)]
+struct Large {
+    notes: miniconf::heapless::String<512>,
+    #[miniconf(with = "quoted")]
+    quoted: usize,
+}
+
+/// Serializes a string of the given length.
+mod quoted {
+    use miniconf::Error;
+
+    pub fn set(_: &mut usize, _: &[u8]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub fn get(len: &usize, data: &mut [u8]) -> Result<usize, Error> {
+        let data = data.get_mut(..*len + 2).ok_or(Error::SerializationFailed)?;
+        data.fill(b'q');
+        data[0] = b'"';
+        data[*len + 1] = b'"';
+        Ok(*len + 2)
+    }
+}
+
+#[test]
+fn large_values() {
+    #[derive(Debug, Deserialize)]
+    struct HostLarge {
+        notes: miniconf::heapless::String<512>,
+        quoted: miniconf::heapless::String<1024>,
+    }
+
+    let settings = Large {
+        notes: "n".repeat(300).as_str().into(),
+        quoted: 1000,
+    };
+
+    let host: HostLarge = settings.to_serde_value().unwrap();
+    assert_eq!(host.notes.as_str(), "n".repeat(300));
+    assert_eq!(host.quoted.as_str(), "q".repeat(1000));
+}
@@ -0,0 +1,117 @@
+use miniconf::{heapless::String, minimq, Miniconf, TopicMapper};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    data: u32,
+}
+
+/// Places all settings topics below an organization segment on the broker.
+struct Organization;
+
+impl TopicMapper<128> for Organization {
+    fn to_broker(&self, topic: &str) -> Option<String<128>> {
+        let mut mapped = String::from("org/");
+        mapped.push_str(topic).ok()?;
+        Some(mapped)
+    }
+
+    fn from_broker(&self, topic: &str) -> Option<String<128>> {
+        topic.strip_prefix("org/").map(String::from)
+    }
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    mqtt.client
+        .subscribe("org/topic-mapper/device/settings/#", &[])
+        .unwrap();
+
+    // The device subscribes and republishes its settings on the broker topics.
+    let mut republished = false;
+    while !republished {
+        mqtt.poll(|_client, topic, message, _properties| {
+            if topic == "org/topic-mapper/device/settings/data" && message == b"0" {
+                republished = true;
+            }
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    let response_topic = "topic-mapper/tester/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait for the subscription to complete.
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    mqtt.client
+        .publish(
+            "org/topic-mapper/device/settings/data",
+            b"5",
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[minimq::Property::ResponseTopic(response_topic)],
+        )
+        .unwrap();
+
+    let mut response = None;
+    while response.is_none() {
+        mqtt.poll(|_client, topic, message, _properties| {
+            if topic == response_topic {
+                response.replace(message.to_vec());
+            }
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(response.unwrap(), br#"{"code":0,"msg":"OK"}"#);
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "topic-mapper/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        Settings::default(),
+    )
+    .unwrap()
+    .topic_mapper(&Organization);
+
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+
+    // The update on the broker topic was routed to the settings path.
+    assert_eq!(interface.settings().data, 5);
+}
@@ -0,0 +1,86 @@
+use miniconf::{embedded_time::duration::Extensions, minimq};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+// The prefix leaves exactly enough space for the settings topic within `MAX_TOPIC_LENGTH`.
+const PREFIX: &str = "topic_overflow/device";
+const MAX_TOPIC_LENGTH: usize = 31;
+
+type Interface = miniconf::MqttClient<u32, Stack, StandardClock, 256, MAX_TOPIC_LENGTH>;
+
+#[test]
+fn main() {
+    env_logger::init();
+
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    let response_topic = format!("{}/response", PREFIX);
+    let log_topic = format!("{}/log", PREFIX);
+    mqtt.client.subscribe(&response_topic, &[]).unwrap();
+    mqtt.client.subscribe(&log_topic, &[]).unwrap();
+    while mqtt.client.subscriptions_pending() {
+        mqtt.poll(|_, _, _, _| {}).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // Neither the settings wildcard nor the custom response topic fit, so the client falls back
+    // to the settings prefix and the default response topic instead of panicking.
+    let mut interface: Interface = miniconf::MqttClient::new(
+        Stack,
+        "",
+        PREFIX,
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        0,
+    )
+    .unwrap()
+    .republish_on_connect(false)
+    .response_topic_suffix("/settings-response")
+    .audit_log(true);
+
+    interface
+        .block_until_active(|_, _, _| Result::<(), &str>::Ok(()), 10.seconds())
+        .unwrap();
+
+    mqtt.client
+        .publish(
+            &format!("{}/settings", PREFIX),
+            b"5",
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &[],
+        )
+        .unwrap();
+
+    let mut responses = Vec::new();
+    let mut logs = Vec::new();
+    for _ in 0..100 {
+        interface.update().unwrap();
+        mqtt.poll(|_, topic, message, _| {
+            let message = String::from_utf8(message.to_vec()).unwrap();
+            if topic == response_topic {
+                responses.push(message);
+            } else if topic == log_topic {
+                logs.push(message);
+            }
+        })
+        .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert_eq!(*interface.settings(), 5);
+    assert_eq!(responses, [r#"{"code":0,"msg":"OK"}"#]);
+    assert_eq!(logs.len(), 1);
+}
@@ -0,0 +1,90 @@
+use miniconf::{Error, Miniconf};
+
+#[derive(Miniconf, Default, Debug, PartialEq)]
+struct Network {
+    port: u16,
+}
+
+#[derive(Miniconf, Default, Debug, PartialEq)]
+struct Dsp {
+    gain: f32,
+    taps: [i32; 2],
+}
+
+#[derive(Miniconf, Default, Debug, PartialEq)]
+struct Power {
+    enabled: bool,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    groups: (Network, Dsp, Power),
+}
+
+#[test]
+fn set_and_get() {
+    let mut settings = Settings::default();
+    let mut buffer = [0; 32];
+
+    settings.set("groups/0/port", b"1883").unwrap();
+    settings.set("groups/1/gain", b"2.5").unwrap();
+    settings.set("groups/1/taps/1", b"-3").unwrap();
+    settings.set("groups/2/enabled", b"true").unwrap();
+
+    assert_eq!(settings.groups.0, Network { port: 1883 });
+    assert_eq!(
+        settings.groups.1,
+        Dsp {
+            gain: 2.5,
+            taps: [0, -3]
+        }
+    );
+    assert_eq!(settings.groups.2, Power { enabled: true });
+
+    let len = settings.get("groups/1/taps/1", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"-3");
+}
+
+#[test]
+fn bad_index() {
+    let mut settings = Settings::default();
+
+    assert_eq!(
+        settings.set("groups/3/enabled", b"true"),
+        Err(Error::BadIndex)
+    );
+    assert_eq!(settings.set("groups/01/port", b"1"), Err(Error::BadIndex));
+    assert_eq!(settings.set("groups", b"1"), Err(Error::ExpectedLeaf));
+}
+
+#[test]
+fn iteration() {
+    let settings = Settings::default();
+
+    let metadata = settings.get_metadata();
+    assert_eq!(metadata.max_topic_size, "groups/2/enabled".len());
+    assert_eq!(metadata.max_depth, 5);
+    assert_eq!(metadata.path_count, 5);
+
+    let mut state = [0; 8];
+    let paths: Vec<_> = settings.into_iter::<32>(&mut state).unwrap().collect();
+    assert_eq!(
+        paths,
+        [
+            "groups/0/port",
+            "groups/1/gain",
+            "groups/1/taps/0",
+            "groups/1/taps/1",
+            "groups/2/enabled"
+        ]
+    );
+}
+
+#[test]
+fn root() {
+    let mut settings = (1u32, Power::default());
+
+    settings.set("0", b"7").unwrap();
+    settings.set("1/enabled", b"true").unwrap();
+    assert_eq!(settings, (7, Power { enabled: true }));
+}
@@ -0,0 +1,54 @@
+use miniconf::{Error, Miniconf};
+use serde::{Deserialize, Serialize};
+
+#[derive(Miniconf, Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+enum Variant {
+    #[default]
+    A,
+    B,
+}
+
+#[derive(Miniconf, Default)]
+struct Inner {
+    gain: f32,
+    variant: Variant,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    inner: Inner,
+    channels: [u8; 2],
+    offset: Option<i32>,
+}
+
+#[test]
+fn leaves() {
+    let settings = Settings::default();
+
+    assert_eq!(settings.type_name("inner/gain"), Ok("f32"));
+    assert_eq!(
+        settings.type_name("inner/variant"),
+        Ok(core::any::type_name::<Variant>())
+    );
+    assert!(settings
+        .type_name("inner/variant")
+        .unwrap()
+        .ends_with("Variant"));
+    assert_eq!(settings.type_name("channels/1"), Ok("u8"));
+}
+
+#[test]
+fn invalid_paths() {
+    let settings = Settings::default();
+
+    assert_eq!(settings.type_name("inner"), Err(Error::PathTooShort));
+    assert_eq!(settings.type_name("inner/gain/x"), Err(Error::PathTooLong));
+    assert_eq!(
+        settings.type_name("inner/missing"),
+        Err(Error::PathNotFound)
+    );
+    assert_eq!(settings.type_name("channels/2"), Err(Error::BadIndex));
+
+    // Absent options have no members.
+    assert_eq!(settings.type_name("offset"), Err(Error::PathNotFound));
+}
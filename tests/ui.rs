@@ -0,0 +1,7 @@
+// The `std` feature adds implementations of `Miniconf` that are listed in the diagnostics.
+#[cfg(not(feature = "std"))]
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,11 @@
+use miniconf::Miniconf;
+
+struct Calibration(f32);
+
+#[derive(Miniconf)]
+struct Settings {
+    calibration: Calibration,
+    enabled: bool,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use miniconf::MiniconfAtomic;
+use serde::{Deserialize, Serialize};
+
+#[derive(MiniconfAtomic, Serialize, Deserialize)]
+#[serde(rename_all = "Title Case")]
+struct Calibration {
+    scale: f32,
+    offset: i32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use miniconf::Miniconf;
+use serde::{Deserialize, Serialize};
+
+#[derive(Miniconf, Serialize, Deserialize)]
+#[serde(rename_all = "Title Case")]
+enum Mode {
+    Off,
+    On,
+}
+
+fn main() {}
@@ -0,0 +1,58 @@
+use miniconf::{ApplyError, Error, Miniconf};
+
+#[derive(Miniconf, Clone, Default, Debug, PartialEq)]
+struct Channel {
+    #[miniconf(min = "-1.5", max = 1.5)]
+    gain: f32,
+    enabled: bool,
+}
+
+#[derive(Miniconf, Clone, Default, Debug, PartialEq)]
+struct Settings {
+    channels: [Channel; 2],
+    #[miniconf(max = 1000)]
+    rate: u32,
+}
+
+#[test]
+fn valid() {
+    let settings = Settings::default();
+
+    settings
+        .validate_json_object(br#"{"channels": [{"gain": 1.0}, {"enabled": true}], "rate": 10}"#)
+        .unwrap();
+
+    // The settings are not modified by validation.
+    assert_eq!(settings, Settings::default());
+}
+
+#[test]
+fn out_of_range() {
+    let settings = Settings::default();
+
+    let result = settings.validate_json_object(
+        br#"{"rate": 10, "channels": [{"gain": 1.0}, {"gain": 2.0, "enabled": true}]}"#,
+    );
+
+    assert_eq!(
+        result,
+        Err(ApplyError::Apply {
+            path: "channels/1/gain".into(),
+            error: Error::OutOfRange {
+                min: Some("-1.5"),
+                max: Some("1.5"),
+            },
+        })
+    );
+    assert_eq!(settings, Settings::default());
+}
+
+#[test]
+fn malformed() {
+    let settings = Settings::default();
+
+    assert_eq!(
+        settings.validate_json_object(br#"{"rate": "#),
+        Err(ApplyError::Malformed)
+    );
+}
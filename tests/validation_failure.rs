@@ -19,7 +19,7 @@ async fn client_task() {
     let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
         "127.0.0.1".parse().unwrap(),
         "tester",
-        Stack::default(),
+        Stack,
         StandardClock::default(),
     )
     .unwrap();
@@ -32,13 +32,13 @@ async fn client_task() {
     }
 
     let response_topic = "validation_failure/device/response";
-    mqtt.client.subscribe(&response_topic, &[]).unwrap();
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
 
     // Wait the other device to connect.
     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
     // Configure the error variable to trigger an internal validation failure.
-    let properties = [minimq::Property::ResponseTopic(&response_topic)];
+    let properties = [minimq::Property::ResponseTopic(response_topic)];
 
     log::info!("Publishing error setting");
     mqtt.client
@@ -78,19 +78,20 @@ async fn main() {
 
     // Construct a settings configuration interface.
     let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
-        Stack::default(),
+        Stack,
         "",
         "validation_failure/device",
         "127.0.0.1".parse().unwrap(),
         StandardClock::default(),
         Settings::default(),
     )
-    .unwrap();
+    .unwrap()
+    .escalate_handler_errors(1);
 
     // Update the client until the exit
     let mut should_exit = false;
     loop {
-        interface
+        let updated = interface
             .handled_update(|_path, _old_settings, new_settings| {
                 log::info!("Handling setting update");
                 if new_settings.error {
@@ -98,11 +99,13 @@ async fn main() {
                     return Err("Exiting now");
                 }
 
-                return Ok(());
+                Ok(())
             })
             .unwrap();
 
         if should_exit {
+            // The rejected update must not be reported as a change.
+            assert!(!updated);
             break;
         }
 
@@ -0,0 +1,183 @@
+use miniconf::{Error, Miniconf, MiniconfAtomic};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, MiniconfAtomic, Serialize, Deserialize)]
+struct Calibration {
+    scale: f32,
+    offset: i32,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+enum Mode {
+    #[default]
+    Off,
+    Fixed(f64),
+    Ramp {
+        start: i64,
+        step: u8,
+    },
+}
+
+#[derive(Clone, Default, MiniconfAtomic, Serialize, Deserialize)]
+struct Profile {
+    name: heapless::String<16>,
+    modes: [Mode; 3],
+    limit: Option<u16>,
+    fallback: Option<u16>,
+}
+
+#[derive(Default, Miniconf)]
+struct Settings {
+    rate: u32,
+    gain: f32,
+    enabled: bool,
+    label: heapless::String<8>,
+    taps: [i16; 3],
+    calibration: Calibration,
+    profile: Profile,
+    notes: heapless::String<512>,
+    #[miniconf(with = "blob")]
+    blob: (),
+}
+
+mod blob {
+    use miniconf::Error;
+
+    pub fn set(_: &mut (), _: &[u8]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub fn get(_: &(), data: &mut [u8]) -> Result<usize, Error> {
+        data.get_mut(..300)
+            .ok_or(Error::SerializationFailed)?
+            .fill(b'1');
+        Ok(300)
+    }
+}
+
+#[test]
+fn matches_get() {
+    let mut settings = Settings {
+        rate: 48000,
+        gain: -1.25,
+        enabled: true,
+        label: "abc".into(),
+        taps: [1, -200, 3],
+        calibration: Calibration {
+            scale: 0.5,
+            offset: -7,
+        },
+        profile: Profile {
+            name: "a\"b\\c\n\u{1}é".into(),
+            modes: [
+                Mode::Off,
+                Mode::Fixed(-1.5e-7),
+                Mode::Ramp {
+                    start: i64::MIN,
+                    step: 255,
+                },
+            ],
+            limit: Some(7),
+            fallback: None,
+        },
+        notes: "x".repeat(300).as_str().into(),
+        blob: (),
+    };
+
+    for path in [
+        "rate",
+        "gain",
+        "enabled",
+        "label",
+        "taps/1",
+        "calibration",
+        "calibration/scale",
+        "profile",
+        "profile/name",
+        "profile/modes/2",
+        "notes",
+    ] {
+        let mut buffer = [0; 512];
+        let len = settings.get(path, &mut buffer).unwrap();
+        assert_eq!(settings.value_len(path), Ok(len), "{}", path);
+    }
+
+    assert_eq!(settings.value_len("taps/1"), Ok(4));
+
+    // Values larger than any scratch buffer are measured.
+    assert_eq!(settings.value_len("notes"), Ok(302));
+
+    // The length reflects the current value.
+    settings.rate = 0;
+    assert_eq!(settings.value_len("rate"), Ok(1));
+}
+
+#[test]
+fn errors() {
+    let settings = Settings::default();
+
+    assert_eq!(settings.value_len("unknown"), Err(Error::PathNotFound));
+    assert_eq!(settings.value_len("taps/3"), Err(Error::BadIndex));
+    assert_eq!(settings.value_len("rate/0"), Err(Error::PathTooLong));
+
+    // Values serialized by a module cannot be measured.
+    assert_eq!(settings.value_len("blob"), Err(Error::SerializationFailed));
+}
+
+#[test]
+fn matches_serde_json_core() {
+    fn check<T: Serialize>(value: &T) {
+        let mut buffer = [0; 512];
+        let len = serde_json_core::to_slice(value, &mut buffer).unwrap();
+        assert_eq!(
+            miniconf::json_len(value),
+            Ok(len),
+            "{}",
+            core::str::from_utf8(&buffer[..len]).unwrap()
+        );
+    }
+
+    // Strings with escapes.
+    check(&"plain");
+    check(&"a\"b\\c/d\n\r\t\u{8}\u{c}\u{1}\u{1f}é€😀");
+
+    // Floats.
+    for value in [
+        0.0,
+        -0.0,
+        1.5,
+        -1.25e-7,
+        3.4e38,
+        f32::MIN_POSITIVE,
+        f32::NAN,
+    ] {
+        check(&value);
+    }
+    for value in [0.1, -2.5e300, f64::MAX, f64::EPSILON, f64::INFINITY] {
+        check(&value);
+    }
+
+    // Enums.
+    check(&Mode::Off);
+    check(&Mode::Fixed(-1.5e-7));
+    check(&Mode::Ramp {
+        start: i64::MIN,
+        step: 255,
+    });
+
+    // Nested structs.
+    check(&Calibration {
+        scale: 0.5,
+        offset: -7,
+    });
+    check(&Profile {
+        name: "a\"b".into(),
+        modes: [
+            Mode::Off,
+            Mode::Fixed(2.0),
+            Mode::Ramp { start: 1, step: 2 },
+        ],
+        limit: Some(7),
+        fallback: None,
+    });
+}
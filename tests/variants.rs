@@ -0,0 +1,108 @@
+use miniconf::{Error, Miniconf};
+use serde::{Deserialize, Serialize};
+
+#[derive(Miniconf, Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+enum Mode {
+    #[default]
+    Off,
+    Continuous,
+    #[serde(rename = "single")]
+    SingleShot,
+}
+
+#[derive(Miniconf, Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Coupling {
+    #[default]
+    DirectCurrent,
+    AlternatingCurrent,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    Internal,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+struct Edge {
+    level: f32,
+}
+
+#[derive(Miniconf, Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+enum Trigger {
+    #[default]
+    FreeRunning,
+    Edge(Edge),
+}
+
+#[derive(Miniconf, Default)]
+struct Channel {
+    gain: f32,
+    coupling: Coupling,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    mode: Mode,
+    trigger: Trigger,
+    channels: [Channel; 2],
+    fallback: Option<Mode>,
+}
+
+#[test]
+fn enums() {
+    let settings = Settings::default();
+
+    assert_eq!(
+        settings.variants("mode"),
+        Ok(&["Off", "Continuous", "single"][..])
+    );
+    assert_eq!(
+        settings.variants("channels/1/coupling"),
+        Ok(&["direct_current", "alternating_current"][..])
+    );
+    assert_eq!(
+        settings.variants("trigger"),
+        Ok(&["FREE-RUNNING", "EDGE"][..])
+    );
+}
+
+#[test]
+fn listed_names_are_accepted() {
+    let mut settings = Settings::default();
+
+    for name in settings.variants("channels/0/coupling").unwrap() {
+        let value = format!("\"{}\"", name);
+        settings
+            .set("channels/0/coupling", value.as_bytes())
+            .unwrap();
+    }
+    assert_eq!(settings.channels[0].coupling, Coupling::AlternatingCurrent);
+}
+
+#[test]
+fn not_an_enum() {
+    let settings = Settings::default();
+
+    assert_eq!(settings.variants("channels/0/gain"), Err(Error::NotAnEnum));
+
+    // The data of variants is not an enum either.
+    assert_eq!(settings.variants("trigger/Edge"), Err(Error::NotAnEnum));
+}
+
+#[test]
+fn invalid_paths() {
+    let mut settings = Settings::default();
+
+    assert_eq!(settings.variants("channels/0"), Err(Error::PathTooShort));
+    assert_eq!(settings.variants("mode/Off"), Err(Error::PathTooLong));
+    assert_eq!(settings.variants("missing"), Err(Error::PathNotFound));
+    assert_eq!(settings.variants("channels/2/gain"), Err(Error::BadIndex));
+
+    // Absent options have no members.
+    assert_eq!(settings.variants("fallback"), Err(Error::PathNotFound));
+    settings.fallback = Some(Mode::Off);
+    assert_eq!(
+        settings.variants("fallback"),
+        Ok(&["Off", "Continuous", "single"][..])
+    );
+}
@@ -0,0 +1,92 @@
+use miniconf::{IterError, Miniconf};
+
+#[derive(Miniconf, Default)]
+struct Channel {
+    gain: f32,
+    offset: f32,
+    enabled: bool,
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    channels: [Channel; 2],
+    optional: Option<Channel>,
+    rate: u32,
+}
+
+#[test]
+fn visit_leaves() {
+    let settings = Settings {
+        optional: Some(Channel::default()),
+        ..Default::default()
+    };
+
+    let mut count = 0;
+    settings
+        .visit_leaves::<32, 4>(|path, leaf| {
+            assert_eq!(path, leaf.path());
+
+            let mut data = [0; 16];
+            let len = leaf.get(&mut data).unwrap();
+            assert!(len > 0);
+
+            count += 1;
+        })
+        .unwrap();
+
+    assert_eq!(count, 10);
+    assert_eq!(count, settings.get_metadata().path_count);
+
+    // Absent optional settings are not visited.
+    let settings = Settings::default();
+    let mut count = 0;
+    settings.visit_leaves::<32, 4>(|_, _| count += 1).unwrap();
+    assert_eq!(count, 7);
+    assert_eq!(count, settings.get_metadata().path_count);
+}
+
+#[test]
+fn visit_leaves_mut() {
+    let mut settings = Settings::default();
+    settings.channels[1].gain = 1.5;
+
+    // Scale all gains in place.
+    settings
+        .visit_leaves_mut::<32, 4>(|path, mut leaf| {
+            if !path.ends_with("gain") {
+                return;
+            }
+
+            let mut data = [0; 16];
+            let len = leaf.get(&mut data).unwrap();
+            let gain: f32 = serde_json_core::from_slice(&data[..len]).unwrap().0;
+
+            let value: heapless::Vec<u8, 16> =
+                serde_json_core::to_vec(&(gain * 2.0 + 1.0)).unwrap();
+            leaf.set(&value).unwrap();
+        })
+        .unwrap();
+
+    assert_eq!(settings.channels[0].gain, 1.0);
+    assert_eq!(settings.channels[1].gain, 4.0);
+    assert_eq!(settings.channels[1].offset, 0.0);
+}
+
+#[test]
+fn insufficient_buffers() {
+    let mut settings = Settings::default();
+    let metadata = settings.get_metadata();
+
+    let mut count = 0;
+    assert_eq!(
+        settings.visit_leaves::<8, 4>(|_, _| count += 1),
+        Err(IterError::InsufficientTopicLength)
+    );
+    assert_eq!(
+        settings.visit_leaves_mut::<32, 2>(|_, _| count += 1),
+        Err(IterError::InsufficientStateDepth {
+            needed: metadata.max_depth
+        })
+    );
+    assert_eq!(count, 0);
+}
@@ -0,0 +1,171 @@
+use miniconf::{minimq, Error, Miniconf};
+use std_embedded_nal::Stack;
+use std_embedded_time::StandardClock;
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Channel {
+    gain: f32,
+    offset: i32,
+    filter: [u8; 2],
+}
+
+#[derive(Clone, Debug, Default, Miniconf)]
+struct Settings {
+    adc: [Channel; 2],
+    rate: u32,
+}
+
+fn settings() -> Settings {
+    let mut settings = Settings {
+        rate: 10,
+        ..Default::default()
+    };
+    settings.adc[0].gain = 1.5;
+    settings.adc[0].offset = -3;
+    settings.adc[0].filter = [4, 5];
+    settings
+}
+
+#[test]
+fn get_subtree() {
+    let settings = settings();
+    let mut buffer = [0; 256];
+
+    let len = settings.get_wildcard("adc/0/*", &mut buffer).unwrap();
+    assert_eq!(
+        &buffer[..len],
+        br#"{"gain":1.5,"offset":-3,"filter/0":4,"filter/1":5}"#
+    );
+
+    // The `#` wildcard is equivalent.
+    let len = settings
+        .get_wildcard("adc/0/filter/#", &mut buffer)
+        .unwrap();
+    assert_eq!(&buffer[..len], br#"{"0":4,"1":5}"#);
+}
+
+#[test]
+fn get_all() {
+    let settings = settings();
+    let mut buffer = [0; 256];
+
+    let len = settings.get_wildcard("*", &mut buffer).unwrap();
+    let expected = br#"{"adc/0/gain":1.5,"adc/0/offset":-3,"adc/0/filter/0":4,"adc/0/filter/1":5,"adc/1/gain":0.0,"adc/1/offset":0,"adc/1/filter/0":0,"adc/1/filter/1":0,"rate":10}"#;
+    assert_eq!(&buffer[..len], &expected[..]);
+}
+
+#[test]
+fn invalid_wildcards() {
+    let settings = settings();
+    let mut buffer = [0; 256];
+
+    assert_eq!(
+        settings.get_wildcard("adc/0", &mut buffer),
+        Err(Error::PathNotFound)
+    );
+    assert_eq!(
+        settings.get_wildcard("adc/0/gain*", &mut buffer),
+        Err(Error::PathNotFound)
+    );
+    assert_eq!(
+        settings.get_wildcard("adc/2/*", &mut buffer),
+        Err(Error::PathNotFound)
+    );
+    assert_eq!(
+        settings.get_wildcard("adc/0/*", &mut buffer[..10]),
+        Err(Error::SerializationFailed)
+    );
+}
+
+#[derive(Miniconf, Default)]
+struct Long {
+    a_setting_with_a_name_that_is_far_too_long_to_be_retrieved_using_a_wildcard_since_its_path_exceeds_the_supported_length_of_wildcard_paths:
+        u32,
+}
+
+#[test]
+fn oversized_settings() {
+    let mut buffer = [0; 256];
+    assert_eq!(
+        Long::default().get_wildcard("*", &mut buffer),
+        Err(Error::PathTooLong)
+    );
+}
+
+async fn client_task() {
+    // Construct a Minimq client to the broker for publishing requests.
+    let mut mqtt: minimq::Minimq<_, _, 256, 1> = miniconf::minimq::Minimq::new(
+        "127.0.0.1".parse().unwrap(),
+        "tester",
+        Stack,
+        StandardClock::default(),
+    )
+    .unwrap();
+
+    // Wait for the broker connection
+    while !mqtt.client.is_connected() {
+        mqtt.poll(|_client, _topic, _message, _properties| {})
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+
+    let response_topic = "wildcard/device/response";
+    mqtt.client.subscribe(response_topic, &[]).unwrap();
+
+    // Wait the other device to connect.
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+    // An empty request to a wildcard path should return the subtree.
+    let properties = [minimq::Property::ResponseTopic(response_topic)];
+    mqtt.client
+        .publish(
+            "wildcard/device/settings/adc/0/*",
+            b"",
+            minimq::QoS::AtMostOnce,
+            minimq::Retain::NotRetained,
+            &properties,
+        )
+        .unwrap();
+
+    let mut response = None;
+    while response.is_none() {
+        mqtt.poll(|_client, _topic, message, _properties| {
+            response.replace(message.to_vec());
+        })
+        .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(
+        response.unwrap(),
+        br#"{"gain":1.5,"offset":-3,"filter/0":4,"filter/1":5}"#
+    );
+}
+
+#[tokio::test]
+async fn main() {
+    env_logger::init();
+
+    // Spawn a task to send MQTT messages.
+    let task = tokio::task::spawn(async move { client_task().await });
+
+    // Construct a settings configuration interface.
+    let mut interface: miniconf::MqttClient<Settings, _, _, 256> = miniconf::MqttClient::new(
+        Stack,
+        "",
+        "wildcard/device",
+        "127.0.0.1".parse().unwrap(),
+        StandardClock::default(),
+        settings(),
+    )
+    .unwrap();
+
+    // Update the client until the response has been verified.
+    while !task.is_finished() {
+        interface.update().unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    task.await.expect("Client task failed");
+}
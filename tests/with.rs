@@ -0,0 +1,66 @@
+use miniconf::{Error, Miniconf};
+
+/// Stores a percentage in the range 0-100, but exposes it as a fraction in the range 0.0-1.0.
+mod percentage {
+    use miniconf::Error;
+
+    pub fn set(field: &mut u8, value: &[u8]) -> Result<(), Error> {
+        let fraction: f32 = serde_json_core::from_slice(value)?.0;
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(Error::Deserialization(
+                serde_json_core::de::Error::CustomError,
+            ));
+        }
+
+        *field = (fraction * 100.0) as u8;
+        Ok(())
+    }
+
+    pub fn get(field: &u8, buffer: &mut [u8]) -> Result<usize, Error> {
+        serde_json_core::to_slice(&(*field as f32 / 100.0), buffer)
+            .map_err(|_| Error::SerializationFailed)
+    }
+}
+
+#[derive(Miniconf, Default)]
+struct Settings {
+    #[miniconf(with = "percentage")]
+    duty: u8,
+    other: [u32; 2],
+}
+
+#[test]
+fn custom_set_get() {
+    let mut settings = Settings::default();
+
+    settings.set("duty", b"0.5").unwrap();
+    assert_eq!(settings.duty, 50);
+
+    let mut buffer = [0; 32];
+    let len = settings.get("duty", &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], b"0.5");
+
+    // Values outside of the supported range are rejected by the custom module.
+    assert!(settings.set("duty", b"1.5").is_err());
+    assert_eq!(settings.duty, 50);
+
+    // The field is a leaf, so it cannot be indexed into.
+    assert_eq!(settings.set("duty/0", b"0.5"), Err(Error::PathTooLong));
+}
+
+#[test]
+fn custom_metadata_and_iteration() {
+    let settings = Settings::default();
+
+    let metadata = settings.get_metadata();
+    assert_eq!(metadata.max_depth, 3);
+    assert_eq!(metadata.max_topic_size, "other/0".len());
+
+    let mut state = [0; 8];
+    let paths: Vec<_> = settings
+        .into_iter::<32>(&mut state)
+        .unwrap()
+        .map(|path| path.as_str().to_string())
+        .collect();
+    assert_eq!(paths, ["duty", "other/0", "other/1"]);
+}